@@ -1,4 +1,5 @@
 use crate::ai::providers::{ContextMessage, MessageRole};
+use crate::config::settings::PromptTemplate;
 
 pub const SYSTEM_PROMPT: &str =
     "You predict the next few words to complete the sender's unfinished chat message. \
@@ -8,6 +9,123 @@ pub const SYSTEM_PROMPT: &str =
      Reply with ONLY the short completion (a few words to finish the sentence they started). \
      No explanation. No quotes. No punctuation prefix.";
 
+pub const SMART_REPLY_PROMPT: &str =
+    "You suggest replies the sender could send to the latest message in their chat. \
+     Match their writing style, tone and language from their past messages (labeled [You]). \
+     Give exactly 3 short, distinct replies, one per line. \
+     No numbering. No quotes. No explanation.";
+
+pub const SUMMARY_PROMPT: &str = "You summarise conversations concisely.";
+
+/// Instruction sent with [`SMART_REPLY_PROMPT`].
+pub const SMART_REPLY_REQUEST: &str = "Suggest 3 replies to the last message.";
+
+/// Most suggested replies shown at once.
+pub const MAX_SMART_REPLIES: usize = 3;
+
+pub const CATCH_UP_PROMPT: &str =
+    "You summarise unread chat messages for someone catching up on a conversation. \
+     Write 1 to 4 short bullet points covering what was said, questions asked of them \
+     and anything they need to do. One bullet per line. No introduction.";
+
+/// Most bullets kept per chat in a catch-up summary.
+pub const MAX_CATCH_UP_BULLETS: usize = 4;
+
+/// Most unread messages per chat sent for a catch-up summary.
+pub const MAX_CATCH_UP_MESSAGES: usize = 100;
+
+pub const TRANSLATE_PROMPT: &str =
+    "You translate chat messages. Keep the tone, emoji and formatting of the original. \
+     Reply with ONLY the translation. No quotes. No explanation.";
+
+/// System prompts for one chat: an `[ai.prompts]` template over the
+/// built-in prompts, with the chat's style notes added to those that write
+/// in the user's voice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatPrompts {
+    pub completion: String,
+    pub smart_reply: String,
+    pub summary: String,
+}
+
+impl Default for ChatPrompts {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl ChatPrompts {
+    pub fn new(template: Option<&PromptTemplate>, style: Option<&str>) -> Self {
+        let pick = |custom: Option<&String>, builtin: &str| {
+            custom.map_or_else(|| builtin.to_string(), |p| p.trim().to_string())
+        };
+        let style = style.map(str::trim).filter(|s| !s.is_empty());
+        let with_style = |prompt: String| match style {
+            Some(style) => format!("{}\n\nStyle notes for this chat: {}", prompt, style),
+            None => prompt,
+        };
+        Self {
+            completion: with_style(pick(
+                template.and_then(|t| t.completion.as_ref()),
+                SYSTEM_PROMPT,
+            )),
+            smart_reply: with_style(pick(
+                template.and_then(|t| t.smart_reply.as_ref()),
+                SMART_REPLY_PROMPT,
+            )),
+            summary: pick(template.and_then(|t| t.summary.as_ref()), SUMMARY_PROMPT),
+        }
+    }
+}
+
+/// Instruction translating `text` into `language`.
+pub fn translate_request(text: &str, language: &str) -> String {
+    format!("Translate into {}:\n\n{}", language, text)
+}
+
+/// Instruction translating our `draft` into the language of `their_messages`.
+pub fn translate_draft_request(draft: &str, their_messages: &[String]) -> String {
+    format!(
+        "Their recent messages:\n{}\n\nTranslate my message into the language they write in:\n\n{}",
+        their_messages.join("\n"),
+        draft
+    )
+}
+
+/// `line` without a leading `1.`, `2)`, `-`, `*` or `•`.
+fn strip_list_marker(line: &str) -> &str {
+    let after_digits = line.trim_start_matches(|c: char| c.is_ascii_digit());
+    if after_digits.len() < line.len() {
+        return after_digits.strip_prefix(['.', ')']).unwrap_or(line);
+    }
+    line.strip_prefix(['-', '*', '•']).unwrap_or(line)
+}
+
+/// Split a smart-reply completion into replies, dropping list markers and
+/// quotes models add despite being told not to.
+pub fn parse_smart_replies(text: &str) -> Vec<String> {
+    parse_list(text, MAX_SMART_REPLIES)
+}
+
+/// Split a completion written one item per line into at most `max` distinct
+/// items, without list markers or surrounding quotes.
+pub fn parse_list(text: &str, max: usize) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = strip_list_marker(line.trim())
+            .trim()
+            .trim_matches(['"', '“', '”'])
+            .trim();
+        if !line.is_empty() && !items.iter().any(|r| r == line) {
+            items.push(line.to_string());
+        }
+        if items.len() == max {
+            break;
+        }
+    }
+    items
+}
+
 pub struct RawMessage {
     pub is_outgoing: bool,
     pub text: String,
@@ -99,4 +217,33 @@ mod tests {
         assert!(matches!(ctx[0].role, MessageRole::Assistant));
         assert!(ctx[0].content.contains("cats"));
     }
+
+    #[test]
+    fn parses_smart_replies() {
+        let text =
+            "1. \"Sounds good!\"\n\n2) 5 min away\n- Sounds good!\n* Can't today, sorry\nOne more";
+        assert_eq!(
+            parse_smart_replies(text),
+            ["Sounds good!", "5 min away", "Can't today, sorry"]
+        );
+    }
+
+    #[test]
+    fn template_and_style_shape_prompts() {
+        assert_eq!(ChatPrompts::default().completion, SYSTEM_PROMPT);
+
+        let template = PromptTemplate {
+            completion: Some("You write to customers. Polite and concise.\n".to_string()),
+            summary: None,
+            smart_reply: None,
+        };
+        let prompts = ChatPrompts::new(Some(&template), Some(" no emoji "));
+        assert_eq!(
+            prompts.completion,
+            "You write to customers. Polite and concise.\n\nStyle notes for this chat: no emoji"
+        );
+        assert!(prompts.smart_reply.starts_with(SMART_REPLY_PROMPT));
+        assert!(prompts.smart_reply.ends_with("no emoji"));
+        assert_eq!(prompts.summary, SUMMARY_PROMPT);
+    }
 }
@@ -0,0 +1,248 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::config::settings::EmbeddingsConfig;
+
+/// Turns text into vectors whose cosine similarity tracks similarity of meaning.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// One vector per text, in the same order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    fn clone_box(&self) -> Box<dyn EmbeddingProvider>;
+}
+
+/// The embedding backend named by `config.provider`.
+pub fn provider_for(config: &EmbeddingsConfig) -> Box<dyn EmbeddingProvider> {
+    match config.provider.as_str() {
+        "ollama" => Box::new(OllamaEmbeddings::new(
+            config.base_url.clone(),
+            config.model.clone(),
+        )),
+        _ => Box::new(OpenAiEmbeddings::new(
+            config.base_url.clone(),
+            config.api_key.clone(),
+            config.model.clone(),
+        )),
+    }
+}
+
+/// Cosine similarity of two vectors; 0 when either is empty or zero, or
+/// their lengths differ.
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Client for the OpenAI-compatible `/v1/embeddings` endpoint (llama.cpp,
+/// LM Studio, vLLM, OpenAI).
+#[derive(Clone)]
+pub struct OpenAiEmbeddings {
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl OpenAiEmbeddings {
+    pub fn new(base_url: String, api_key: Option<String>, model: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct OpenAiRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OpenAiResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAiEmbeddings {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut builder = self
+            .client
+            .post(format!("{}/v1/embeddings", self.base_url))
+            .json(&OpenAiRequest {
+                model: &self.model,
+                input: texts,
+            });
+        if let Some(ref key) = self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+        let mut resp: OpenAiResponse = builder.send().await?.error_for_status()?.json().await?;
+        if resp.data.len() != texts.len() {
+            return Err(anyhow!(
+                "expected {} embeddings, got {}",
+                texts.len(),
+                resp.data.len()
+            ));
+        }
+        resp.data.sort_by_key(|e| e.index);
+        Ok(resp.data.into_iter().map(|e| e.embedding).collect())
+    }
+
+    fn clone_box(&self) -> Box<dyn EmbeddingProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Client for Ollama's `/api/embeddings`, which embeds one prompt per request.
+#[derive(Clone)]
+pub struct OllamaEmbeddings {
+    base_url: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl OllamaEmbeddings {
+    pub fn new(base_url: String, model: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct OllamaRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponse {
+    embedding: Vec<f32>,
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaEmbeddings {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for text in texts {
+            let resp: OllamaResponse = self
+                .client
+                .post(format!("{}/api/embeddings", self.base_url))
+                .json(&OllamaRequest {
+                    model: &self.model,
+                    prompt: text,
+                })
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            vectors.push(resp.embedding);
+        }
+        Ok(vectors)
+    }
+
+    fn clone_box(&self) -> Box<dyn EmbeddingProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Deterministic embeddings for tests: each word adds weight to a dimension
+/// picked by hashing it, so texts sharing words score as similar.
+#[cfg(test)]
+#[derive(Clone)]
+pub struct FakeEmbeddings;
+
+#[cfg(test)]
+#[async_trait]
+impl EmbeddingProvider for FakeEmbeddings {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let mut vector = vec![0.0; 64];
+                for word in text.split_whitespace() {
+                    let mut hasher = DefaultHasher::new();
+                    word.to_lowercase()
+                        .trim_matches(|c: char| !c.is_alphanumeric())
+                        .hash(&mut hasher);
+                    vector[hasher.finish() as usize % 64] += 1.0;
+                }
+                vector
+            })
+            .collect())
+    }
+
+    fn clone_box(&self) -> Box<dyn EmbeddingProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ai::providers::sse::test_server;
+
+    #[test]
+    fn cosine_ranks_by_direction() {
+        assert!((cosine(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine(&[1.0, 0.0], &[0.0, 3.0]), 0.0);
+        assert_eq!(cosine(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn openai_embeddings_follow_input_order() {
+        let url = test_server::serve(
+            vec![
+                r#"{"data":[{"index":1,"embedding":[0.0,1.0]},{"index":0,"embedding":[1.0,0.0]}]}"#,
+            ],
+            Duration::ZERO,
+        )
+        .await;
+        let client = OpenAiEmbeddings::new(url, None, "nomic-embed-text".to_string());
+        let vectors = client
+            .embed(&["first".to_string(), "second".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(vectors, [vec![1.0, 0.0], vec![0.0, 1.0]]);
+    }
+
+    #[tokio::test]
+    async fn ollama_embeds_a_prompt() {
+        let url = test_server::serve(vec![r#"{"embedding":[0.5,-0.25]}"#], Duration::ZERO).await;
+        let client = OllamaEmbeddings::new(url, "nomic-embed-text".to_string());
+        let vectors = client.embed(&["hello".to_string()]).await.unwrap();
+        assert_eq!(vectors, [vec![0.5, -0.25]]);
+    }
+}
@@ -0,0 +1,431 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::ai::providers::{AiProvider, CompletionRequest, TextStream};
+use crate::tui::event::AppEvent;
+
+/// Consecutive failures that take a backend out of the chain.
+const FAILURES_TO_OPEN: u32 = 3;
+/// How long a failing backend is skipped before it gets another try.
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+/// One provider in the chain.
+pub struct Backend {
+    pub name: String,
+    /// Replaces the request's model when set.
+    pub model: Option<String>,
+    /// How long to wait for an answer before trying the next backend.
+    pub timeout: Duration,
+    pub provider: Box<dyn AiProvider>,
+}
+
+/// Skips a backend for [`COOLDOWN`] after [`FAILURES_TO_OPEN`] failures in
+/// a row; one more failure after the cooldown skips it again.
+#[derive(Debug, Default)]
+struct Breaker {
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Breaker {
+    fn is_closed(&self, now: Instant) -> bool {
+        self.open_until.is_none_or(|until| now >= until)
+    }
+
+    fn fail(&mut self, now: Instant) {
+        self.failures += 1;
+        if self.failures >= FAILURES_TO_OPEN {
+            self.open_until = Some(now + COOLDOWN);
+        }
+    }
+}
+
+struct Chain {
+    breakers: Vec<Breaker>,
+    /// The backend that answered last, shown in the status bar.
+    active: Option<usize>,
+}
+
+/// Tries each backend in order, moving on when one can't be reached, is
+/// overloaded, or doesn't answer in time. Any other error is the backend's
+/// answer and is returned as it is.
+pub struct FallbackProvider {
+    backends: Vec<Backend>,
+    chain: Arc<Mutex<Chain>>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+}
+
+/// Connection failures, timeouts, server errors and rate limits, as opposed
+/// to errors that say something is wrong with the request.
+fn is_unavailable(e: &anyhow::Error) -> bool {
+    e.chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|e| {
+            e.is_connect()
+                || e.is_timeout()
+                || e.status().is_some_and(|status| {
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                })
+        })
+}
+
+impl FallbackProvider {
+    /// The first backend starts out as the active one.
+    pub fn new(backends: Vec<Backend>, event_tx: mpsc::UnboundedSender<AppEvent>) -> Self {
+        let chain = Chain {
+            breakers: backends.iter().map(|_| Breaker::default()).collect(),
+            active: Some(0),
+        };
+        Self {
+            backends,
+            chain: Arc::new(Mutex::new(chain)),
+            event_tx,
+        }
+    }
+
+    fn chain(&self) -> std::sync::MutexGuard<'_, Chain> {
+        self.chain.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set_active(&self, chain: &mut Chain, active: Option<usize>) {
+        if chain.active != active {
+            chain.active = active;
+            let name = active.map(|i| self.backends[i].name.clone());
+            let _ = self.event_tx.send(AppEvent::AiBackend(name));
+        }
+    }
+
+    fn answered(&self, i: usize) {
+        let mut chain = self.chain();
+        chain.breakers[i] = Breaker::default();
+        self.set_active(&mut chain, Some(i));
+    }
+
+    /// `req` sent to each backend in turn through `call` until one answers.
+    async fn attempt<T, F, Fut>(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+        call: F,
+    ) -> Result<T>
+    where
+        F: Fn(Box<dyn AiProvider>, CompletionRequest, CancellationToken) -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T>> + Send,
+        T: Send,
+    {
+        let mut last_err = None;
+        for (i, backend) in self.backends.iter().enumerate() {
+            if !self.chain().breakers[i].is_closed(Instant::now()) {
+                continue;
+            }
+            let mut req = req.clone();
+            if let Some(ref model) = backend.model {
+                req.model = model.clone();
+            }
+            req.usage.set_backend(&backend.name, &req.model);
+            let answer = call(backend.provider.clone_box(), req, cancel.clone());
+            let outcome = if i + 1 == self.backends.len() {
+                Some(answer.await)
+            } else {
+                tokio::time::timeout(backend.timeout, answer).await.ok()
+            };
+            let err = match outcome {
+                Some(Err(e)) if cancel.is_cancelled() => return Err(e),
+                Some(Err(e)) if is_unavailable(&e) => anyhow!("{}: {}", backend.name, e),
+                Some(result) => {
+                    self.answered(i);
+                    return result;
+                }
+                None => anyhow!(
+                    "{}: no answer within {}s",
+                    backend.name,
+                    backend.timeout.as_secs()
+                ),
+            };
+            tracing::info!(backend = %backend.name, error = %err, "AI backend failed, trying the next");
+            self.chain().breakers[i].fail(Instant::now());
+            last_err = Some(err);
+        }
+
+        let mut chain = self.chain();
+        self.set_active(&mut chain, None);
+        Err(last_err.unwrap_or_else(|| {
+            let now = Instant::now();
+            let retry = chain
+                .breakers
+                .iter()
+                .filter_map(|b| b.open_until)
+                .min()
+                .map_or(0, |until| until.saturating_duration_since(now).as_secs());
+            anyhow!("every AI backend is failing; trying again in {}s", retry)
+        }))
+    }
+}
+
+#[async_trait]
+impl AiProvider for FallbackProvider {
+    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+        self.attempt(req, cancel, |provider, req, cancel| async move {
+            provider.complete(req, cancel).await
+        })
+        .await
+    }
+
+    /// Falls through only until a backend starts streaming; errors after
+    /// that end the stream.
+    async fn complete_stream(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        self.attempt(req, cancel, |provider, req, cancel| async move {
+            provider.complete_stream(req, cancel).await
+        })
+        .await
+    }
+
+    fn clone_box(&self) -> Box<dyn AiProvider> {
+        Box::new(Self {
+            backends: self
+                .backends
+                .iter()
+                .map(|b| Backend {
+                    name: b.name.clone(),
+                    model: b.model.clone(),
+                    timeout: b.timeout,
+                    provider: b.provider.clone_box(),
+                })
+                .collect(),
+            chain: self.chain.clone(),
+            event_tx: self.event_tx.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::providers::CompletionKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Copy)]
+    enum Behaviour {
+        Up,
+        Down,
+        Slow,
+        Refuses,
+        Status(u16),
+    }
+
+    #[derive(Clone)]
+    struct Fake {
+        behaviour: Behaviour,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl AiProvider for Fake {
+        async fn complete(&self, req: CompletionRequest, _: CancellationToken) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.behaviour {
+                Behaviour::Up => Ok(format!("{} says hi", req.model)),
+                Behaviour::Down => {
+                    // Nothing listens on port 1, so this is a real connection error
+                    reqwest::get("http://127.0.0.1:1").await?;
+                    unreachable!("port 1 answered");
+                }
+                Behaviour::Slow => {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Ok("too late".to_string())
+                }
+                Behaviour::Refuses => Err(anyhow!("401 Unauthorized")),
+                Behaviour::Status(code) => {
+                    let url = serve_status(code).await;
+                    reqwest::get(url).await?.error_for_status()?;
+                    unreachable!("{} is not an error status", code);
+                }
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn AiProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// A local server that answers one request with `code`.
+    async fn serve_status(code: u16) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0; 1024];
+            let _ = socket.read(&mut buf).await;
+            let response = format!("HTTP/1.1 {} Error\r\ncontent-length: 0\r\n\r\n", code);
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+        format!("http://{}", addr)
+    }
+
+    fn backend(name: &str, behaviour: Behaviour) -> (Backend, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let backend = Backend {
+            name: name.to_string(),
+            model: Some(format!("{}-model", name)),
+            timeout: Duration::from_millis(50),
+            provider: Box::new(Fake {
+                behaviour,
+                calls: calls.clone(),
+            }),
+        };
+        (backend, calls)
+    }
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new(
+            "m".to_string(),
+            String::new(),
+            vec![],
+            "hello".to_string(),
+            CompletionKind::Prompt,
+        )
+    }
+
+    fn switches(rx: &mut mpsc::UnboundedReceiver<AppEvent>) -> Vec<Option<String>> {
+        let mut switches = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::AiBackend(name) = event {
+                switches.push(name);
+            }
+        }
+        switches
+    }
+
+    #[tokio::test]
+    async fn falls_through_unreachable_backends_and_opens_the_breaker() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (local, local_calls) = backend("llama", Behaviour::Down);
+        let (remote, remote_calls) = backend("openai", Behaviour::Up);
+        let chain = FallbackProvider::new(vec![local, remote], tx);
+
+        let req = request();
+        let usage = req.usage.clone();
+        let text = chain.complete(req, CancellationToken::new()).await.unwrap();
+        assert_eq!(text, "openai-model says hi");
+        assert_eq!(
+            usage.backend(),
+            Some(("openai".to_string(), "openai-model".to_string()))
+        );
+        assert_eq!(switches(&mut rx), [Some("openai".to_string())]);
+
+        for _ in 0..FAILURES_TO_OPEN + 2 {
+            chain
+                .complete(request(), CancellationToken::new())
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            local_calls.load(Ordering::SeqCst),
+            FAILURES_TO_OPEN as usize
+        );
+        assert_eq!(
+            remote_calls.load(Ordering::SeqCst),
+            3 + FAILURES_TO_OPEN as usize
+        );
+        assert!(switches(&mut rx).is_empty());
+    }
+
+    #[tokio::test]
+    async fn slow_backends_time_out_but_the_last_one_waits() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (slow, _) = backend("slow", Behaviour::Slow);
+        let (up, _) = backend("up", Behaviour::Up);
+        let chain = FallbackProvider::new(vec![slow, up], tx);
+        let stream = chain
+            .complete_stream(request(), CancellationToken::new())
+            .await
+            .unwrap();
+        let text: Vec<String> = futures::StreamExt::collect::<Vec<_>>(stream)
+            .await
+            .into_iter()
+            .map(|d| d.unwrap())
+            .collect();
+        assert_eq!(text, ["up-model says hi"]);
+    }
+
+    #[tokio::test]
+    async fn errors_from_a_reachable_backend_are_returned() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (refuses, _) = backend("anthropic", Behaviour::Refuses);
+        let (up, up_calls) = backend("up", Behaviour::Up);
+        let chain = FallbackProvider::new(vec![refuses, up], tx);
+        let err = chain
+            .complete(request(), CancellationToken::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "401 Unauthorized");
+        assert_eq!(up_calls.load(Ordering::SeqCst), 0);
+        assert!(switches(&mut rx).is_empty(), "first backend stays active");
+    }
+
+    #[tokio::test]
+    async fn falls_through_server_errors_and_rate_limits() {
+        for code in [500, 503, 429] {
+            let (tx, _rx) = mpsc::unbounded_channel();
+            let (mut failing, _) = backend("busy", Behaviour::Status(code));
+            failing.timeout = Duration::from_secs(5);
+            let (up, _) = backend("up", Behaviour::Up);
+            let chain = FallbackProvider::new(vec![failing, up], tx);
+            let text = chain
+                .complete(request(), CancellationToken::new())
+                .await
+                .unwrap();
+            assert_eq!(text, "up-model says hi", "status {}", code);
+        }
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (mut bad, _) = backend("bad", Behaviour::Status(400));
+        bad.timeout = Duration::from_secs(5);
+        let (up, up_calls) = backend("up", Behaviour::Up);
+        let chain = FallbackProvider::new(vec![bad, up], tx);
+        assert!(chain
+            .complete(request(), CancellationToken::new())
+            .await
+            .is_err());
+        assert_eq!(up_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn reports_when_no_backend_answers() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (down, _) = backend("llama", Behaviour::Down);
+        let chain = FallbackProvider::new(vec![down], tx);
+        let err = chain
+            .complete(request(), CancellationToken::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("llama: "), "{}", err);
+        assert_eq!(switches(&mut rx), [None]);
+    }
+
+    #[test]
+    fn breaker_gives_one_try_after_the_cooldown() {
+        let now = Instant::now();
+        let mut breaker = Breaker::default();
+        for _ in 0..FAILURES_TO_OPEN {
+            assert!(breaker.is_closed(now));
+            breaker.fail(now);
+        }
+        assert!(!breaker.is_closed(now));
+        let later = now + COOLDOWN;
+        assert!(breaker.is_closed(later));
+        breaker.fail(later);
+        assert!(!breaker.is_closed(later));
+    }
+}
@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::ai::embeddings::EmbeddingProvider;
+use crate::core::types::MessageContent;
+use crate::tui::event::AppEvent;
+
+/// Embeds stored messages in the background and search queries on demand.
+/// The app owns the database: it hands over batches of unembedded messages
+/// and stores the vectors that come back as [`AppEvent::Embedded`].
+pub struct Indexer {
+    provider: Box<dyn EmbeddingProvider>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+    debounce_ms: u64,
+    query_token: Option<CancellationToken>,
+    shutdown_token: CancellationToken,
+}
+
+/// The text worth embedding for a message: its text or image caption.
+pub fn embeddable_text(content: &MessageContent) -> Option<&str> {
+    let text = match content {
+        MessageContent::Text(text) | MessageContent::Formatted { text, .. } => text,
+        MessageContent::Image {
+            caption: Some(caption),
+            ..
+        } => caption,
+        _ => return None,
+    };
+    Some(text.as_str()).filter(|t| !t.trim().is_empty())
+}
+
+impl Indexer {
+    pub fn new(
+        provider: Box<dyn EmbeddingProvider>,
+        debounce_ms: u64,
+        event_tx: mpsc::UnboundedSender<AppEvent>,
+    ) -> Self {
+        Self {
+            provider,
+            event_tx,
+            debounce_ms,
+            query_token: None,
+            shutdown_token: CancellationToken::new(),
+        }
+    }
+
+    /// Embed a batch of `(message id, text)`, answering with one
+    /// [`AppEvent::Embedded`] for the whole batch.
+    pub fn index(&self, batch: Vec<(String, String)>) {
+        let provider = self.provider.clone_box();
+        let event_tx = self.event_tx.clone();
+        let cancel = self.shutdown_token.clone();
+
+        tokio::spawn(async move {
+            let (ids, texts): (Vec<String>, Vec<String>) = batch.into_iter().unzip();
+            let result = tokio::select! {
+                _ = cancel.cancelled() => return,
+                result = provider.embed(&texts) => result,
+            };
+            let vectors = result
+                .map(|vectors| ids.into_iter().zip(vectors).collect())
+                .map_err(|e| e.to_string());
+            let _ = event_tx.send(AppEvent::Embedded(vectors));
+        });
+    }
+
+    /// Embed a search query once typing pauses, answering with
+    /// [`AppEvent::QueryEmbedding`]. Replaces any query still pending.
+    pub fn embed_query(&mut self, query: String) {
+        self.cancel_query();
+        let token = self.shutdown_token.child_token();
+        self.query_token = Some(token.clone());
+
+        let provider = self.provider.clone_box();
+        let event_tx = self.event_tx.clone();
+        let debounce = Duration::from_millis(self.debounce_ms);
+
+        tokio::spawn(async move {
+            let result = tokio::select! {
+                _ = token.cancelled() => return,
+                result = async {
+                    tokio::time::sleep(debounce).await;
+                    provider.embed(std::slice::from_ref(&query)).await
+                } => result,
+            };
+            let vector = result
+                .map_err(|e| e.to_string())
+                .and_then(|mut v| v.pop().ok_or_else(|| "no embedding returned".to_string()));
+            let _ = event_tx.send(AppEvent::QueryEmbedding(query, vector));
+        });
+    }
+
+    pub fn cancel_query(&mut self) {
+        if let Some(token) = self.query_token.take() {
+            token.cancel();
+        }
+    }
+
+    pub fn shutdown(&mut self) {
+        self.shutdown_token.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::embeddings::FakeEmbeddings;
+
+    async fn next_event(rx: &mut mpsc::UnboundedReceiver<AppEvent>) -> Option<AppEvent> {
+        tokio::time::timeout(Duration::from_millis(500), rx.recv())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[test]
+    fn only_text_and_captions_are_embedded() {
+        assert_eq!(
+            embeddable_text(&MessageContent::Text("hi".to_string())),
+            Some("hi")
+        );
+        assert_eq!(
+            embeddable_text(&MessageContent::Text("  ".to_string())),
+            None
+        );
+        let photo = |caption: Option<&str>| MessageContent::Image {
+            url: String::new(),
+            caption: caption.map(str::to_string),
+            decrypt_params: None,
+        };
+        assert_eq!(embeddable_text(&photo(Some("beach"))), Some("beach"));
+        assert_eq!(embeddable_text(&photo(None)), None);
+    }
+
+    #[tokio::test]
+    async fn batch_vectors_keep_their_message_ids() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let indexer = Indexer::new(Box::new(FakeEmbeddings), 0, tx);
+        indexer.index(vec![
+            ("m1".to_string(), "dinner at eight".to_string()),
+            ("m2".to_string(), "see you at dinner".to_string()),
+        ]);
+
+        match next_event(&mut rx).await {
+            Some(AppEvent::Embedded(Ok(vectors))) => {
+                let ids: Vec<&str> = vectors.iter().map(|(id, _)| id.as_str()).collect();
+                assert_eq!(ids, ["m1", "m2"]);
+                assert!(vectors.iter().all(|(_, v)| v.len() == 64));
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn only_the_last_query_is_embedded() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut indexer = Indexer::new(Box::new(FakeEmbeddings), 20, tx);
+        indexer.embed_query("din".to_string());
+        indexer.embed_query("dinner".to_string());
+
+        match next_event(&mut rx).await {
+            Some(AppEvent::QueryEmbedding(query, Ok(_))) => assert_eq!(query, "dinner"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(next_event(&mut rx).await.is_none());
+    }
+}
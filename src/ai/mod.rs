@@ -1,3 +1,8 @@
 pub mod context;
+pub mod embeddings;
+pub mod fallback;
+pub mod indexer;
 pub mod providers;
+pub mod redact;
+pub mod usage;
 pub mod worker;
@@ -1,9 +1,10 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use futures::{future, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
-use super::{AiProvider, CompletionRequest, MessageRole};
+use super::{sse, AiProvider, CompletionRequest, MessageRole, TextStream, UsageTally};
 
 #[derive(Clone)]
 pub struct AnthropicClient {
@@ -26,6 +27,7 @@ struct AnthropicRequest {
     max_tokens: u32,
     system: String,
     messages: Vec<AnthropicMessage>,
+    stream: bool,
 }
 
 #[derive(Serialize)]
@@ -37,6 +39,27 @@ struct AnthropicMessage {
 #[derive(Deserialize)]
 struct AnthropicResponse {
     content: Vec<AnthropicContent>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+}
+
+impl AnthropicUsage {
+    fn report(&self, usage: &UsageTally) {
+        if let Some(tokens) = self.input_tokens {
+            usage.set_input(tokens);
+        }
+        if let Some(tokens) = self.output_tokens {
+            usage.set_output(tokens);
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -44,9 +67,73 @@ struct AnthropicContent {
     text: String,
 }
 
-#[async_trait]
-impl AiProvider for AnthropicClient {
-    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+/// Server-sent event of a streaming Messages response.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    /// Opens the stream with the input token count.
+    MessageStart {
+        message: StreamMessage,
+    },
+    ContentBlockDelta {
+        delta: StreamDelta,
+    },
+    /// Carries the output token count so far.
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<AnthropicUsage>,
+    },
+    Error {
+        error: StreamError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct StreamMessage {
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StreamError {
+    message: String,
+}
+
+/// Text of one streaming event; `None` for events without text.
+fn parse_delta(data: &str, usage: &UsageTally) -> Result<Option<String>> {
+    match serde_json::from_str(data)? {
+        StreamEvent::MessageStart { message } => {
+            if let Some(reported) = message.usage {
+                reported.report(usage);
+            }
+            Ok(None)
+        }
+        StreamEvent::MessageDelta { usage: reported } => {
+            if let Some(reported) = reported {
+                reported.report(usage);
+            }
+            Ok(None)
+        }
+        StreamEvent::ContentBlockDelta { delta } => Ok(delta.text.filter(|t| !t.is_empty())),
+        StreamEvent::Error { error } => Err(anyhow!("Anthropic: {}", error.message)),
+        StreamEvent::Other => Ok(None),
+    }
+}
+
+impl AnthropicClient {
+    fn build_request(
+        &self,
+        req: &CompletionRequest,
+        stream: bool,
+    ) -> Result<reqwest::RequestBuilder> {
         let api_key = self
             .api_key
             .as_deref()
@@ -70,30 +157,37 @@ impl AiProvider for AnthropicClient {
 
         messages.push(AnthropicMessage {
             role: "user".to_string(),
-            content: format!(
-                "Complete this message (reply with ONLY the completion): {}",
-                req.partial_input
-            ),
+            content: req.user_prompt("Complete this message (reply with ONLY the completion)"),
         });
 
         let body = AnthropicRequest {
             model: req.model.clone(),
-            max_tokens: 80,
+            max_tokens: req.max_tokens(80),
             system: req.system.clone(),
             messages,
+            stream,
         };
 
-        let request = self
+        Ok(self
             .client
             .post("https://api.anthropic.com/v1/messages")
             .header("x-api-key", api_key)
             .header("anthropic-version", "2023-06-01")
-            .json(&body);
+            .json(&body))
+    }
+}
 
+#[async_trait]
+impl AiProvider for AnthropicClient {
+    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+        let request = self.build_request(&req, false)?;
         tokio::select! {
             _ = cancel.cancelled() => Err(anyhow!("cancelled")),
             res = request.send() => {
                 let resp: AnthropicResponse = res?.error_for_status()?.json().await?;
+                if let Some(ref reported) = resp.usage {
+                    reported.report(&req.usage);
+                }
                 Ok(resp.content.into_iter().next()
                     .map(|c| c.text.trim().to_string())
                     .unwrap_or_default())
@@ -101,7 +195,50 @@ impl AiProvider for AnthropicClient {
         }
     }
 
+    async fn complete_stream(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        let request = self.build_request(&req, true)?;
+        let response = tokio::select! {
+            _ = cancel.cancelled() => return Err(anyhow!("cancelled")),
+            res = request.send() => res?.error_for_status()?,
+        };
+        let usage = req.usage;
+        let deltas = sse::events(response)
+            .try_filter_map(move |data| future::ready(parse_delta(&data, &usage)));
+        Ok(deltas.boxed())
+    }
+
     fn clone_box(&self) -> Box<dyn AiProvider> {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stream_events() {
+        let usage = UsageTally::default();
+        let delta = r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":" soon"}}"#;
+        assert_eq!(
+            parse_delta(delta, &usage).unwrap().as_deref(),
+            Some(" soon")
+        );
+        let start = r#"{"type":"message_start","message":{"id":"msg_1","usage":{"input_tokens":25,"output_tokens":1}}}"#;
+        assert_eq!(parse_delta(start, &usage).unwrap(), None);
+        let end = r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":4}}"#;
+        assert_eq!(parse_delta(end, &usage).unwrap(), None);
+        assert_eq!(usage.get().input_tokens, 25);
+        assert_eq!(usage.get().output_tokens, 4);
+        let error =
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert!(parse_delta(error, &usage)
+            .unwrap_err()
+            .to_string()
+            .contains("Overloaded"));
+    }
+}
@@ -1,9 +1,10 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use futures::{future, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
-use super::{AiProvider, CompletionRequest, MessageRole};
+use super::{sse, AiProvider, CompletionRequest, MessageRole, TextStream, UsageTally};
 
 #[derive(Clone)]
 pub struct GeminiClient {
@@ -53,7 +54,19 @@ struct GeminiGenerationConfig {
 
 #[derive(Deserialize)]
 struct GeminiResponse {
+    // Absent from the usage-only chunk that ends a stream
+    #[serde(default)]
     candidates: Vec<GeminiCandidate>,
+    #[serde(default, rename = "usageMetadata")]
+    usage_metadata: Option<GeminiUsage>,
+}
+
+#[derive(Deserialize)]
+struct GeminiUsage {
+    #[serde(default, rename = "promptTokenCount")]
+    prompt_token_count: u64,
+    #[serde(default, rename = "candidatesTokenCount")]
+    candidates_token_count: u64,
 }
 
 #[derive(Deserialize)]
@@ -71,9 +84,36 @@ struct GeminiResponsePart {
     text: String,
 }
 
-#[async_trait]
-impl AiProvider for GeminiClient {
-    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+impl GeminiResponse {
+    fn report_usage(&self, usage: &UsageTally) {
+        if let Some(ref reported) = self.usage_metadata {
+            usage.set_input(reported.prompt_token_count);
+            usage.set_output(reported.candidates_token_count);
+        }
+    }
+
+    fn into_text(self) -> Option<String> {
+        self.candidates
+            .into_iter()
+            .next()
+            .and_then(|c| c.content.parts.into_iter().next())
+            .map(|p| p.text)
+    }
+}
+
+/// Text of one `streamGenerateContent` chunk.
+fn parse_delta(data: &str, usage: &UsageTally) -> Result<Option<String>> {
+    let chunk: GeminiResponse = serde_json::from_str(data)?;
+    chunk.report_usage(usage);
+    Ok(chunk.into_text().filter(|t| !t.is_empty()))
+}
+
+impl GeminiClient {
+    fn build_request(
+        &self,
+        req: &CompletionRequest,
+        stream: bool,
+    ) -> Result<reqwest::RequestBuilder> {
         let api_key = self
             .api_key
             .as_deref()
@@ -100,10 +140,7 @@ impl AiProvider for GeminiClient {
         contents.push(GeminiContent {
             role: "user".to_string(),
             parts: vec![GeminiPart {
-                text: format!(
-                    "Complete this message (reply with ONLY the completion): {}",
-                    req.partial_input
-                ),
+                text: req.user_prompt("Complete this message (reply with ONLY the completion)"),
             }],
         });
 
@@ -115,30 +152,73 @@ impl AiProvider for GeminiClient {
             },
             contents,
             generation_config: GeminiGenerationConfig {
-                max_output_tokens: 80,
+                max_output_tokens: req.max_tokens(80),
             },
         };
 
+        let method = if stream {
+            "streamGenerateContent?alt=sse&"
+        } else {
+            "generateContent?"
+        };
         let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-            req.model, api_key
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:{}key={}",
+            req.model, method, api_key
         );
 
-        let request = self.client.post(&url).json(&body);
+        Ok(self.client.post(&url).json(&body))
+    }
+}
 
+#[async_trait]
+impl AiProvider for GeminiClient {
+    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+        let request = self.build_request(&req, false)?;
         tokio::select! {
             _ = cancel.cancelled() => Err(anyhow!("cancelled")),
             res = request.send() => {
                 let resp: GeminiResponse = res?.error_for_status()?.json().await?;
-                Ok(resp.candidates.into_iter().next()
-                    .and_then(|c| c.content.parts.into_iter().next())
-                    .map(|p| p.text.trim().to_string())
-                    .unwrap_or_default())
+                resp.report_usage(&req.usage);
+                Ok(resp.into_text().map(|t| t.trim().to_string()).unwrap_or_default())
             }
         }
     }
 
+    async fn complete_stream(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        let request = self.build_request(&req, true)?;
+        let response = tokio::select! {
+            _ = cancel.cancelled() => return Err(anyhow!("cancelled")),
+            res = request.send() => res?.error_for_status()?,
+        };
+        let usage = req.usage;
+        let deltas = sse::events(response)
+            .try_filter_map(move |data| future::ready(parse_delta(&data, &usage)));
+        Ok(deltas.boxed())
+    }
+
     fn clone_box(&self) -> Box<dyn AiProvider> {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stream_chunks() {
+        let chunk = r#"{"candidates":[{"content":{"parts":[{"text":" at 5"}],"role":"model"}}]}"#;
+        let usage = UsageTally::default();
+        assert_eq!(
+            parse_delta(chunk, &usage).unwrap().as_deref(),
+            Some(" at 5")
+        );
+        let last = r#"{"usageMetadata":{"promptTokenCount":12,"candidatesTokenCount":3}}"#;
+        assert_eq!(parse_delta(last, &usage).unwrap(), None);
+        assert_eq!(usage.get().total(), 15);
+    }
+}
@@ -1,7 +1,13 @@
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::Stream;
 use tokio_util::sync::CancellationToken;
 
+use crate::config::settings::{OllamaConfig, ProviderConfig};
+
 #[derive(Debug, Clone)]
 pub enum MessageRole {
     User,
@@ -15,7 +21,6 @@ pub struct ContextMessage {
 }
 
 impl ContextMessage {
-    #[allow(dead_code)]
     pub fn to_chat_line(&self) -> String {
         match self.role {
             MessageRole::User => format!("[You]: {}", self.content),
@@ -30,17 +35,151 @@ pub struct CompletionRequest {
     pub system: String,
     pub context: Vec<ContextMessage>,
     pub partial_input: String,
+    pub kind: CompletionKind,
+    /// Where the provider reports the tokens this request used.
+    pub usage: UsageTally,
+}
+
+impl CompletionRequest {
+    pub fn new(
+        model: String,
+        system: String,
+        context: Vec<ContextMessage>,
+        partial_input: String,
+        kind: CompletionKind,
+    ) -> Self {
+        Self {
+            model,
+            system,
+            context,
+            partial_input,
+            kind,
+            usage: UsageTally::default(),
+        }
+    }
+}
+
+/// Tokens a provider says a request used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl Usage {
+    pub fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    usage: Usage,
+    backend: Option<(String, String)>,
+}
+
+/// Usage of one request, filled in by the provider as the response arrives.
+/// Clones share the same counts.
+#[derive(Debug, Clone, Default)]
+pub struct UsageTally(Arc<Mutex<Tally>>);
+
+impl UsageTally {
+    pub fn set_input(&self, tokens: u64) {
+        self.0.lock().unwrap().usage.input_tokens = tokens;
+    }
+
+    pub fn set_output(&self, tokens: u64) {
+        self.0.lock().unwrap().usage.output_tokens = tokens;
+    }
+
+    pub fn get(&self) -> Usage {
+        self.0.lock().unwrap().usage
+    }
+
+    /// Record which backend and model the fallback chain sent the request to.
+    pub fn set_backend(&self, name: &str, model: &str) {
+        self.0.lock().unwrap().backend = Some((name.to_string(), model.to_string()));
+    }
+
+    /// The backend and model that answered, when a fallback chain chose one.
+    pub fn backend(&self) -> Option<(String, String)> {
+        self.0.lock().unwrap().backend.clone()
+    }
+}
+
+/// What a request asks for, which decides how providers phrase it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    /// Finish the unfinished message in `partial_input`.
+    Autocomplete,
+    /// `partial_input` is a full instruction (summaries, suggested replies).
+    Prompt,
 }
 
+/// Token budget for [`CompletionKind::Prompt`] requests.
+const PROMPT_MAX_TOKENS: u32 = 256;
+
+impl CompletionRequest {
+    /// The final user turn: `partial_input` after the provider's autocomplete
+    /// `instruction`, or as-is for a prompt.
+    pub fn user_prompt(&self, instruction: &str) -> String {
+        match self.kind {
+            CompletionKind::Autocomplete => format!("{}: {}", instruction, self.partial_input),
+            CompletionKind::Prompt => self.partial_input.clone(),
+        }
+    }
+
+    /// Output token limit, given the provider's limit for autocompletion.
+    pub fn max_tokens(&self, autocomplete: u32) -> u32 {
+        match self.kind {
+            CompletionKind::Autocomplete => autocomplete,
+            CompletionKind::Prompt => autocomplete.max(PROMPT_MAX_TOKENS),
+        }
+    }
+}
+
+/// Text deltas of a completion, in order.
+pub type TextStream = Pin<Box<dyn Stream<Item = Result<String>> + Send>>;
+
 #[async_trait]
 pub trait AiProvider: Send + Sync {
     async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String>;
+
+    /// Stream the completion as it is generated. Providers without streaming
+    /// yield the whole completion as a single delta.
+    async fn complete_stream(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        let text = self.complete(req, cancel).await?;
+        Ok(Box::pin(futures::stream::once(async move { Ok(text) })))
+    }
+
     fn clone_box(&self) -> Box<dyn AiProvider>;
 }
 
 pub mod anthropic;
 pub mod gemini;
+pub mod ollama;
 pub mod openai;
+pub mod sse;
+
+/// The client for the backend named by `backend.provider`.
+pub fn client_for(backend: &ProviderConfig, ollama: &OllamaConfig) -> Box<dyn AiProvider> {
+    match backend.provider.as_str() {
+        "anthropic" => Box::new(anthropic::AnthropicClient::new(backend.api_key.clone())),
+        "gemini" => Box::new(gemini::GeminiClient::new(backend.api_key.clone())),
+        "ollama" => Box::new(ollama::OllamaClient::new(
+            backend.base_url.clone(),
+            ollama.clone(),
+        )),
+        _ => Box::new(openai::OpenAiClient::new(
+            backend.base_url.clone(),
+            backend.api_key.clone(),
+        )),
+    }
+}
 
 #[cfg(test)]
 mod tests {
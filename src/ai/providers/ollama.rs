@@ -0,0 +1,270 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::{future, stream, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use super::{sse, AiProvider, CompletionRequest, MessageRole, TextStream, UsageTally};
+use crate::config::settings::OllamaConfig;
+
+/// Client for Ollama's native API (`/api/generate`, `/api/tags`).
+#[derive(Clone)]
+pub struct OllamaClient {
+    pub base_url: String,
+    options: OllamaConfig,
+    client: reqwest::Client,
+}
+
+impl OllamaClient {
+    pub fn new(base_url: String, options: OllamaConfig) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            options,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GenerateRequest<'a> {
+    model: &'a str,
+    prompt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<&'a str>,
+    raw: bool,
+    stream: bool,
+    keep_alive: &'a str,
+    options: GenerateOptions,
+}
+
+#[derive(Serialize)]
+struct GenerateOptions {
+    num_predict: u32,
+}
+
+/// A `/api/generate` response, or one line of it when streaming.
+#[derive(Deserialize)]
+struct GenerateResponse {
+    #[serde(default)]
+    response: String,
+    #[serde(default)]
+    error: Option<String>,
+    /// Token counts, on the last line only.
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+impl GenerateResponse {
+    fn report_usage(&self, usage: &UsageTally) {
+        if let Some(tokens) = self.prompt_eval_count {
+            usage.set_input(tokens);
+        }
+        if let Some(tokens) = self.eval_count {
+            usage.set_output(tokens);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    models: Vec<ModelTag>,
+}
+
+#[derive(Deserialize)]
+struct ModelTag {
+    name: String,
+}
+
+/// Text of one streamed `/api/generate` line; `None` for empty ones.
+fn parse_chunk(line: &str, usage: &UsageTally) -> Result<Option<String>> {
+    let chunk: GenerateResponse = serde_json::from_str(line)?;
+    if let Some(error) = chunk.error {
+        return Err(anyhow!("Ollama: {}", error));
+    }
+    chunk.report_usage(usage);
+    Ok(Some(chunk.response).filter(|t| !t.is_empty()))
+}
+
+/// The non-empty lines of a newline-delimited JSON body.
+fn json_lines(response: reqwest::Response) -> impl Stream<Item = Result<String>> + Send {
+    let mut buf: Vec<u8> = Vec::new();
+    response.bytes_stream().flat_map(move |chunk| {
+        let items: Vec<Result<String>> = match chunk {
+            Ok(bytes) => sse::split_lines(&mut buf, &bytes)
+                .into_iter()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .map(Ok)
+                .collect(),
+            Err(e) => vec![Err(e.into())],
+        };
+        stream::iter(items)
+    })
+}
+
+impl OllamaClient {
+    fn build_request(&self, req: &CompletionRequest, stream: bool) -> reqwest::RequestBuilder {
+        let mut prompt = String::new();
+        if !req.context.is_empty() {
+            let history: Vec<String> = req
+                .context
+                .iter()
+                .map(|m| {
+                    let label = match m.role {
+                        MessageRole::User => "[You]",
+                        MessageRole::Assistant => "[Them]",
+                    };
+                    format!("{}: {}", label, m.content)
+                })
+                .collect();
+            prompt.push_str(&format!(
+                "Conversation history:\n{}\n\n",
+                history.join("\n")
+            ));
+        }
+        prompt.push_str(
+            &req.user_prompt("Complete only the end of this message I'm typing (a few words max)"),
+        );
+
+        // Raw mode skips the model's template, so the system prompt goes inline
+        let raw = self.options.raw;
+        if raw {
+            prompt = format!("{}\n\n{}\n", req.system, prompt);
+        }
+
+        let body = GenerateRequest {
+            model: &req.model,
+            prompt,
+            system: (!raw).then_some(req.system.as_str()),
+            raw,
+            stream,
+            keep_alive: &self.options.keep_alive,
+            options: GenerateOptions {
+                num_predict: req.max_tokens(self.options.num_predict),
+            },
+        };
+
+        self.client
+            .post(format!("{}/api/generate", self.base_url))
+            .json(&body)
+    }
+
+    /// Names of the models installed locally, as listed by `/api/tags`.
+    pub async fn list_models(&self) -> Result<Vec<String>> {
+        let resp: TagsResponse = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let mut names: Vec<String> = resp.models.into_iter().map(|m| m.name).collect();
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[async_trait]
+impl AiProvider for OllamaClient {
+    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+        let builder = self.build_request(&req, false);
+        tokio::select! {
+            _ = cancel.cancelled() => Err(anyhow!("cancelled")),
+            res = builder.send() => {
+                let resp: GenerateResponse = res?.error_for_status()?.json().await?;
+                resp.report_usage(&req.usage);
+                match resp.error {
+                    Some(error) => Err(anyhow!("Ollama: {}", error)),
+                    None => Ok(resp.response.trim().to_string()),
+                }
+            }
+        }
+    }
+
+    async fn complete_stream(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        let builder = self.build_request(&req, true);
+        let response = tokio::select! {
+            _ = cancel.cancelled() => return Err(anyhow!("cancelled")),
+            res = builder.send() => res?.error_for_status()?,
+        };
+        let usage = req.usage;
+        let deltas = json_lines(response)
+            .try_filter_map(move |line| future::ready(parse_chunk(&line, &usage)));
+        Ok(deltas.boxed())
+    }
+
+    fn clone_box(&self) -> Box<dyn AiProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ai::providers::sse::test_server;
+    use crate::ai::providers::CompletionKind;
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new(
+            "qwen2.5:1.5b-instruct".to_string(),
+            "Be brief.".to_string(),
+            Vec::new(),
+            "see you".to_string(),
+            CompletionKind::Autocomplete,
+        )
+    }
+
+    #[tokio::test]
+    async fn streams_generate_lines() {
+        let url = test_server::serve(
+            vec![
+                "{\"response\":\" tomor\",\"done\":false}\n{\"response\":\"row\",",
+                "\"done\":false}\n{\"response\":\"\",\"done\":true,\"prompt_eval_count\":9,\"eval_count\":2}\n",
+            ],
+            Duration::from_millis(5),
+        )
+        .await;
+        let client = OllamaClient::new(url, OllamaConfig::default());
+        let req = request();
+        let usage = req.usage.clone();
+        let deltas: Vec<String> = client
+            .complete_stream(req, CancellationToken::new())
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(deltas, [" tomor", "row"]);
+        assert_eq!(usage.get().total(), 11);
+    }
+
+    #[tokio::test]
+    async fn lists_models_sorted() {
+        let url = test_server::serve(
+            vec![r#"{"models":[{"name":"qwen2.5:1.5b-instruct","size":1},{"name":"llama3.2:latest"}]}"#],
+            Duration::ZERO,
+        )
+        .await;
+        let client = OllamaClient::new(url, OllamaConfig::default());
+        assert_eq!(
+            client.list_models().await.unwrap(),
+            ["llama3.2:latest", "qwen2.5:1.5b-instruct"]
+        );
+    }
+
+    #[test]
+    fn stream_errors_surface() {
+        let err =
+            parse_chunk(r#"{"error":"model not found"}"#, &UsageTally::default()).unwrap_err();
+        assert!(err.to_string().contains("model not found"));
+    }
+}
@@ -1,9 +1,10 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use futures::{future, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
-use super::{AiProvider, CompletionRequest, MessageRole};
+use super::{sse, AiProvider, CompletionRequest, MessageRole, TextStream, UsageTally};
 
 #[derive(Clone)]
 pub struct OpenAiClient {
@@ -34,11 +35,36 @@ struct ChatRequest {
     messages: Vec<ChatMessage>,
     max_tokens: u32,
     stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+#[derive(Serialize)]
+struct StreamOptions {
+    /// Ask for a final chunk carrying the token usage.
+    include_usage: bool,
+}
+
+#[derive(Deserialize)]
+struct ResponseUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+}
+
+impl ResponseUsage {
+    fn report(&self, usage: &UsageTally) {
+        usage.set_input(self.prompt_tokens);
+        usage.set_output(self.completion_tokens);
+    }
 }
 
 #[derive(Deserialize)]
 struct ChatResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<ResponseUsage>,
 }
 
 #[derive(Deserialize)]
@@ -51,9 +77,42 @@ struct ChoiceMessage {
     content: String,
 }
 
-#[async_trait]
-impl AiProvider for OpenAiClient {
-    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+#[derive(Deserialize)]
+struct StreamChunk {
+    // Empty in the usage chunk that ends the stream
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    #[serde(default)]
+    usage: Option<ResponseUsage>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Text of one `chat.completion.chunk` event; `None` for role-only and final chunks.
+fn parse_delta(data: &str, usage: &UsageTally) -> Result<Option<String>> {
+    let chunk: StreamChunk = serde_json::from_str(data)?;
+    if let Some(ref reported) = chunk.usage {
+        reported.report(usage);
+    }
+    Ok(chunk
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|c| c.delta.content)
+        .filter(|t| !t.is_empty()))
+}
+
+impl OpenAiClient {
+    fn build_request(&self, req: &CompletionRequest, stream: bool) -> reqwest::RequestBuilder {
         let mut messages = vec![ChatMessage {
             role: "system".to_string(),
             content: req.system.clone(),
@@ -85,17 +144,18 @@ impl AiProvider for OpenAiClient {
 
         messages.push(ChatMessage {
             role: "user".to_string(),
-            content: format!(
-                "Complete only the end of this message I'm typing (a few words max): {}",
-                req.partial_input
-            ),
+            content: req
+                .user_prompt("Complete only the end of this message I'm typing (a few words max)"),
         });
 
         let body = ChatRequest {
             model: req.model.clone(),
             messages,
-            max_tokens: 30,
-            stream: false,
+            max_tokens: req.max_tokens(30),
+            stream,
+            stream_options: stream.then_some(StreamOptions {
+                include_usage: true,
+            }),
         };
 
         let mut builder = self
@@ -106,11 +166,21 @@ impl AiProvider for OpenAiClient {
         if let Some(key) = &self.api_key {
             builder = builder.bearer_auth(key);
         }
+        builder
+    }
+}
 
+#[async_trait]
+impl AiProvider for OpenAiClient {
+    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+        let builder = self.build_request(&req, false);
         tokio::select! {
             _ = cancel.cancelled() => Err(anyhow!("cancelled")),
             res = builder.send() => {
                 let resp: ChatResponse = res?.error_for_status()?.json().await?;
+                if let Some(ref reported) = resp.usage {
+                    reported.report(&req.usage);
+                }
                 Ok(resp.choices.into_iter().next()
                     .map(|c| c.message.content.trim().to_string())
                     .unwrap_or_default())
@@ -118,7 +188,76 @@ impl AiProvider for OpenAiClient {
         }
     }
 
+    async fn complete_stream(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        let builder = self.build_request(&req, true);
+        let response = tokio::select! {
+            _ = cancel.cancelled() => return Err(anyhow!("cancelled")),
+            res = builder.send() => res?.error_for_status()?,
+        };
+        let usage = req.usage;
+        let deltas = sse::events(response)
+            .try_take_while(|data| future::ready(Ok(data.as_str() != "[DONE]")))
+            .try_filter_map(move |data| future::ready(parse_delta(&data, &usage)));
+        Ok(deltas.boxed())
+    }
+
     fn clone_box(&self) -> Box<dyn AiProvider> {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ai::providers::sse::test_server;
+    use crate::ai::providers::{CompletionKind, Usage};
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new(
+            "test".to_string(),
+            String::new(),
+            Vec::new(),
+            "see you".to_string(),
+            CompletionKind::Autocomplete,
+        )
+    }
+
+    #[tokio::test]
+    async fn streams_deltas_until_done() {
+        let url = test_server::serve(
+            vec![
+                "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\" tomor",
+                "row\"}}]}\n\ndata: {\"choices\":[{\"delta\":{\"content\":\" then\"}}]}\n\n",
+                "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":40,\"completion_tokens\":3}}\n\n",
+                "data: [DONE]\n\n",
+            ],
+            Duration::from_millis(5),
+        )
+        .await;
+        let client = OpenAiClient::new(url, None);
+        let req = request();
+        let usage = req.usage.clone();
+        let deltas: Vec<String> = client
+            .complete_stream(req, CancellationToken::new())
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(deltas, [" tomorrow", " then"]);
+        assert_eq!(
+            usage.get(),
+            Usage {
+                input_tokens: 40,
+                output_tokens: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use futures::{stream, Stream, StreamExt};
+
+/// Append `chunk` to `buf` and take out every complete line, without its
+/// line ending. Splits on '\n' before decoding so multi-byte characters cut
+/// across chunks survive.
+pub fn split_lines(buf: &mut Vec<u8>, chunk: &[u8]) -> Vec<String> {
+    buf.extend_from_slice(chunk);
+    let mut lines = Vec::new();
+    while let Some(pos) = buf.iter().position(|&b| b == b'\n') {
+        let raw: Vec<u8> = buf.drain(..=pos).collect();
+        let line = String::from_utf8_lossy(&raw);
+        lines.push(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+    lines
+}
+
+/// Incremental `text/event-stream` parser: feed it bytes as they arrive and
+/// get back the `data` of every event completed so far.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buf: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut events = Vec::new();
+        for line in split_lines(&mut self.buf, chunk) {
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+            // `event:`, `id:`, `retry:` and `:` comments carry nothing we use
+        }
+        events
+    }
+}
+
+/// The `data` payloads of the server-sent events in `response`.
+pub fn events(response: reqwest::Response) -> impl Stream<Item = Result<String>> + Send {
+    let mut decoder = SseDecoder::default();
+    response.bytes_stream().flat_map(move |chunk| {
+        let items: Vec<Result<String>> = match chunk {
+            Ok(bytes) => decoder.push(&bytes).into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e.into())],
+        };
+        stream::iter(items)
+    })
+}
+
+/// A local stand-in for a streaming completion endpoint.
+#[cfg(test)]
+pub mod test_server {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one request, answering with `chunks` written `delay` apart as a
+    /// `text/event-stream`. Returns the base URL.
+    pub async fn serve(chunks: Vec<&'static str>, delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    return;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let head =
+                "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n";
+            if socket.write_all(head.as_bytes()).await.is_err() {
+                return;
+            }
+            for chunk in chunks {
+                tokio::time::sleep(delay).await;
+                if socket.write_all(chunk.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+        });
+        format!("http://{}", addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_events_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b": keep-alive\n\ndata: {\"a\"").is_empty());
+        assert_eq!(
+            decoder.push(b":1}\r\n\r\nevent: x\ndata: one\n"),
+            ["{\"a\":1}"]
+        );
+        assert_eq!(decoder.push(b"data: two\n\n"), ["one\ntwo"]);
+
+        // "é" split between two chunks
+        let bytes = "data: café\n\n".as_bytes();
+        assert!(decoder.push(&bytes[..11]).is_empty());
+        assert_eq!(decoder.push(&bytes[11..]), ["café"]);
+    }
+}
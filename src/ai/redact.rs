@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::StreamExt;
+use regex::{Captures, Regex};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::ai::providers::{AiProvider, CompletionRequest, TextStream};
+use crate::tui::event::AppEvent;
+
+/// Longest placeholder a streamed completion may be cut inside of.
+const MAX_PLACEHOLDER_LEN: usize = 16;
+
+#[derive(Clone)]
+struct Rule {
+    label: &'static str,
+    regex: Regex,
+    /// Second look at a match the regex alone is too loose for.
+    accept: fn(&str) -> bool,
+}
+
+/// Masks personal details in text bound for an AI provider: URLs, email
+/// addresses, card-like numbers, phone numbers and `[ai.redact] patterns`.
+#[derive(Clone)]
+pub struct Redactor {
+    rules: Vec<Rule>,
+}
+
+/// Placeholders handed out while masking one request, so the same value
+/// always gets the same placeholder and the completion can be unmasked.
+#[derive(Debug, Default)]
+pub struct Masking {
+    placeholders: HashMap<String, String>,
+    originals: Vec<(String, String)>,
+    counts: HashMap<&'static str, usize>,
+}
+
+fn accept_any(_: &str) -> bool {
+    true
+}
+
+fn digit_count(text: &str) -> usize {
+    text.chars().filter(char::is_ascii_digit).count()
+}
+
+fn looks_like_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split(['-', '.', '/']).collect();
+    let lens: Vec<usize> = parts.iter().map(|p| p.len()).collect();
+    parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()))
+        && matches!(lens[..], [4, 1..=2, 1..=2] | [1..=2, 1..=2, 2 | 4])
+}
+
+fn accept_phone(text: &str) -> bool {
+    (7..=15).contains(&digit_count(text)) && !looks_like_date(text)
+}
+
+impl Redactor {
+    /// The built-in rules followed by `custom` patterns, masked as `PRIVATE`.
+    pub fn new(custom: Vec<Regex>) -> Self {
+        let builtin = |label, pattern: &str, accept| Rule {
+            label,
+            regex: Regex::new(pattern).expect("built-in redaction pattern"),
+            accept,
+        };
+        let mut rules = vec![
+            builtin(
+                "URL",
+                r#"(?i)\b(?:https?://|www\.)[^\s<>\[\]]*[^\s<>\[\].,!?;:'")]"#,
+                accept_any,
+            ),
+            builtin(
+                "EMAIL",
+                r"\b[\w.+-]+@[\w-]+(?:\.[\w-]+)*\.[A-Za-z]{2,}\b",
+                accept_any,
+            ),
+            builtin("CARD", r"\b\d(?:[ -]?\d){12,18}\b", accept_any),
+            builtin(
+                "PHONE",
+                r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{1,4}\)[\s.-]?|\b)\d{2,4}(?:[\s.-]?\d{2,4}){1,4}\b",
+                accept_phone,
+            ),
+        ];
+        rules.extend(custom.into_iter().map(|regex| Rule {
+            label: "PRIVATE",
+            regex,
+            accept: accept_any,
+        }));
+        Self { rules }
+    }
+
+    /// `text` with every match replaced by its placeholder in `masking`.
+    pub fn mask(&self, text: &str, masking: &mut Masking) -> String {
+        let mut text = text.to_string();
+        for rule in &self.rules {
+            text = rule
+                .regex
+                .replace_all(&text, |caps: &Captures| {
+                    let found = &caps[0];
+                    if (rule.accept)(found) {
+                        masking.placeholder(rule.label, found)
+                    } else {
+                        found.to_string()
+                    }
+                })
+                .into_owned();
+        }
+        text
+    }
+
+    /// `req` with its system prompt, context and input masked.
+    pub fn mask_request(
+        &self,
+        mut req: CompletionRequest,
+        masking: &mut Masking,
+    ) -> CompletionRequest {
+        req.system = self.mask(&req.system, masking);
+        for message in &mut req.context {
+            message.content = self.mask(&message.content, masking);
+        }
+        req.partial_input = self.mask(&req.partial_input, masking);
+        req
+    }
+}
+
+impl Masking {
+    fn placeholder(&mut self, label: &'static str, value: &str) -> String {
+        if let Some(placeholder) = self.placeholders.get(value) {
+            return placeholder.clone();
+        }
+        let n = self.counts.entry(label).or_default();
+        *n += 1;
+        let placeholder = format!("[{}_{}]", label, n);
+        self.placeholders
+            .insert(value.to_string(), placeholder.clone());
+        self.originals
+            .push((placeholder.clone(), value.to_string()));
+        placeholder
+    }
+
+    /// Distinct values masked so far.
+    pub fn count(&self) -> usize {
+        self.originals.len()
+    }
+
+    /// `text` with placeholders put back to the values they stand for.
+    pub fn unmask(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (placeholder, value) in &self.originals {
+            if text.contains(placeholder.as_str()) {
+                text = text.replace(placeholder.as_str(), value);
+            }
+        }
+        text
+    }
+
+    /// How much of streamed `text` can be unmasked now: all of it, unless it
+    /// ends inside what may be the start of a placeholder.
+    fn ready_len(&self, text: &str) -> usize {
+        if self.originals.is_empty() {
+            return text.len();
+        }
+        match text.rfind('[') {
+            Some(i) if !text[i..].contains(']') && text.len() - i < MAX_PLACEHOLDER_LEN => i,
+            _ => text.len(),
+        }
+    }
+}
+
+/// A request as it left for the provider, after masking.
+#[derive(Debug, Clone)]
+pub struct SentRequest {
+    pub sent_at: chrono::DateTime<chrono::Local>,
+    pub request: CompletionRequest,
+    /// Distinct values replaced by placeholders.
+    pub masked: usize,
+}
+
+/// Sits between the AI worker and the provider: masks requests when a
+/// [`Redactor`] is set, unmasks the completions, and reports every request
+/// it sends as [`AppEvent::AiRequestSent`].
+pub struct RedactingProvider {
+    inner: Box<dyn AiProvider>,
+    redactor: Option<Arc<Redactor>>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+}
+
+impl RedactingProvider {
+    pub fn new(
+        inner: Box<dyn AiProvider>,
+        redactor: Option<Redactor>,
+        event_tx: mpsc::UnboundedSender<AppEvent>,
+    ) -> Self {
+        Self {
+            inner,
+            redactor: redactor.map(Arc::new),
+            event_tx,
+        }
+    }
+
+    fn prepare(&self, req: CompletionRequest) -> (CompletionRequest, Masking) {
+        let mut masking = Masking::default();
+        let req = match self.redactor {
+            Some(ref redactor) => redactor.mask_request(req, &mut masking),
+            None => req,
+        };
+        let _ = self.event_tx.send(AppEvent::AiRequestSent(SentRequest {
+            sent_at: chrono::Local::now(),
+            request: req.clone(),
+            masked: masking.count(),
+        }));
+        (req, masking)
+    }
+}
+
+/// Unmask a streamed completion, holding back a delta's tail while it may
+/// be a placeholder cut in two.
+fn unmask_stream(stream: TextStream, masking: Masking) -> TextStream {
+    Box::pin(futures::stream::unfold(
+        Some((stream, masking, String::new())),
+        |state| async move {
+            let (mut stream, masking, mut pending) = state?;
+            loop {
+                match stream.next().await {
+                    Some(Ok(delta)) => {
+                        pending.push_str(&delta);
+                        let ready = masking.ready_len(&pending);
+                        if ready > 0 {
+                            let text: String = pending.drain(..ready).collect();
+                            let text = masking.unmask(&text);
+                            return Some((Ok(text), Some((stream, masking, pending))));
+                        }
+                    }
+                    Some(Err(e)) => return Some((Err(e), None)),
+                    None if pending.is_empty() => return None,
+                    None => return Some((Ok(masking.unmask(&pending)), None)),
+                }
+            }
+        },
+    ))
+}
+
+#[async_trait]
+impl AiProvider for RedactingProvider {
+    async fn complete(&self, req: CompletionRequest, cancel: CancellationToken) -> Result<String> {
+        let (req, masking) = self.prepare(req);
+        let text = self.inner.complete(req, cancel).await?;
+        Ok(masking.unmask(&text))
+    }
+
+    async fn complete_stream(
+        &self,
+        req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        let (req, masking) = self.prepare(req);
+        let stream = self.inner.complete_stream(req, cancel).await?;
+        Ok(unmask_stream(stream, masking))
+    }
+
+    fn clone_box(&self) -> Box<dyn AiProvider> {
+        Box::new(Self {
+            inner: self.inner.clone_box(),
+            redactor: self.redactor.clone(),
+            event_tx: self.event_tx.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::providers::{CompletionKind, ContextMessage, MessageRole};
+
+    fn masked(text: &str) -> String {
+        Redactor::new(Vec::new()).mask(text, &mut Masking::default())
+    }
+
+    #[test]
+    fn masks_contact_details() {
+        assert_eq!(
+            masked("mail bob@example.com or call +1 555 123 4567"),
+            "mail [EMAIL_1] or call [PHONE_1]"
+        );
+        assert_eq!(masked("call (555) 123-4567."), "call [PHONE_1].");
+        assert_eq!(
+            masked("see https://example.com/a?b=1, ok"),
+            "see [URL_1], ok"
+        );
+        assert_eq!(masked("card 4111 1111 1111 1111"), "card [CARD_1]");
+    }
+
+    #[test]
+    fn leaves_dates_times_and_short_numbers() {
+        let text = "on 2024-05-01 at 10:30, room 42, 3 of 12";
+        assert_eq!(masked(text), text);
+    }
+
+    #[test]
+    fn same_value_keeps_its_placeholder_and_unmasks() {
+        let redactor = Redactor::new(vec![Regex::new(r"ACME-\d+").unwrap()]);
+        let mut masking = Masking::default();
+        let req = CompletionRequest::new(
+            "m".to_string(),
+            String::new(),
+            vec![ContextMessage {
+                role: MessageRole::Assistant,
+                content: "is ann@corp.io on ACME-42?".to_string(),
+            }],
+            "yes, ann@corp.io".to_string(),
+            CompletionKind::Autocomplete,
+        );
+        let req = redactor.mask_request(req, &mut masking);
+        assert_eq!(req.context[0].content, "is [EMAIL_1] on [PRIVATE_1]?");
+        assert_eq!(req.partial_input, "yes, [EMAIL_1]");
+        assert_eq!(masking.count(), 2);
+        assert_eq!(
+            masking.unmask("[EMAIL_1] owns [PRIVATE_1]"),
+            "ann@corp.io owns ACME-42"
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_unmasks_placeholders_split_across_deltas() {
+        let mut masking = Masking::default();
+        Redactor::new(Vec::new()).mask("bob@example.com", &mut masking);
+        let deltas: Vec<Result<String>> = ["write to [EM", "AIL_1] today", " [sic"]
+            .into_iter()
+            .map(|d| Ok(d.to_string()))
+            .collect();
+        let stream: TextStream = Box::pin(futures::stream::iter(deltas));
+        let out: Vec<String> = unmask_stream(stream, masking)
+            .map(|d| d.unwrap())
+            .collect()
+            .await;
+        assert_eq!(out, ["write to ", "bob@example.com today", " ", "[sic"]);
+    }
+}
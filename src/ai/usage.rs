@@ -0,0 +1,478 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::ai::providers::{
+    AiProvider, CompletionKind, CompletionRequest, TextStream, Usage, UsageTally,
+};
+use crate::config::settings::LimitsConfig;
+use crate::storage::UsageTotals;
+use crate::tui::event::AppEvent;
+
+/// What one request cost, reported as [`AppEvent::AiUsage`] once it is done.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageRecord {
+    pub provider: String,
+    pub model: String,
+    pub usage: Usage,
+    /// Answered from the completion cache without reaching the provider.
+    pub cached: bool,
+}
+
+/// Completions for recent requests, least recently used first out.
+struct CompletionCache {
+    capacity: usize,
+    entries: VecDeque<(u64, String)>,
+}
+
+impl CompletionCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, key: u64) -> Option<String> {
+        let i = self.entries.iter().position(|(k, _)| *k == key)?;
+        let entry = self.entries.remove(i)?;
+        let text = entry.1.clone();
+        self.entries.push_front(entry);
+        Some(text)
+    }
+
+    fn put(&mut self, key: u64, text: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.retain(|(k, _)| *k != key);
+        self.entries.push_front((key, text));
+        self.entries.truncate(self.capacity);
+    }
+}
+
+/// Identifies requests that should get the same completion.
+fn cache_key(req: &CompletionRequest) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    req.model.hash(&mut hasher);
+    req.system.hash(&mut hasher);
+    for message in &req.context {
+        message.to_chat_line().hash(&mut hasher);
+    }
+    req.partial_input.hash(&mut hasher);
+    matches!(req.kind, CompletionKind::Prompt).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Requests in the last minute and today's totals, checked against `[ai.limits]`.
+struct Meter {
+    limits: LimitsConfig,
+    recent: VecDeque<Instant>,
+    day: NaiveDate,
+    requests: u64,
+    tokens: u64,
+    cache: CompletionCache,
+}
+
+impl Meter {
+    fn roll_over(&mut self, today: NaiveDate) {
+        if today != self.day {
+            self.day = today;
+            self.requests = 0;
+            self.tokens = 0;
+        }
+    }
+
+    /// Count a request about to be sent, or say which limit it would break.
+    fn admit(&mut self, now: Instant, today: NaiveDate) -> Result<()> {
+        self.roll_over(today);
+        while self
+            .recent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60))
+        {
+            self.recent.pop_front();
+        }
+        let limits = &self.limits;
+        if limits.requests_per_minute > 0
+            && self.recent.len() >= limits.requests_per_minute as usize
+        {
+            anyhow::bail!(
+                "limit reached: {} requests a minute",
+                limits.requests_per_minute
+            );
+        }
+        if limits.requests_per_day > 0 && self.requests >= limits.requests_per_day.into() {
+            anyhow::bail!("limit reached: {} requests a day", limits.requests_per_day);
+        }
+        if limits.tokens_per_day > 0 && self.tokens >= limits.tokens_per_day {
+            anyhow::bail!("limit reached: {} tokens a day", limits.tokens_per_day);
+        }
+        self.recent.push_back(now);
+        self.requests += 1;
+        Ok(())
+    }
+}
+
+/// Reports a request's usage when dropped, so cancelled streams count too.
+struct Report {
+    record: UsageRecord,
+    tally: UsageTally,
+    meter: Arc<Mutex<Meter>>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+}
+
+impl Drop for Report {
+    fn drop(&mut self) {
+        self.record.usage = self.tally.get();
+        if let Some((provider, model)) = self.tally.backend() {
+            self.record.provider = provider;
+            self.record.model = model;
+        }
+        if let Ok(mut meter) = self.meter.lock() {
+            meter.roll_over(chrono::Local::now().date_naive());
+            meter.tokens += self.record.usage.total();
+        }
+        let _ = self.event_tx.send(AppEvent::AiUsage(self.record.clone()));
+    }
+}
+
+/// Sits in front of the provider: answers repeated requests from a cache,
+/// refuses requests past `[ai.limits]`, and reports what each one used.
+pub struct MeteredProvider {
+    inner: Box<dyn AiProvider>,
+    provider: String,
+    meter: Arc<Mutex<Meter>>,
+    event_tx: mpsc::UnboundedSender<AppEvent>,
+}
+
+impl MeteredProvider {
+    /// `today` is what was already used today, so limits hold across restarts.
+    pub fn new(
+        inner: Box<dyn AiProvider>,
+        provider: String,
+        limits: LimitsConfig,
+        cache_size: usize,
+        today: UsageTotals,
+        event_tx: mpsc::UnboundedSender<AppEvent>,
+    ) -> Self {
+        let meter = Meter {
+            limits,
+            recent: VecDeque::new(),
+            day: chrono::Local::now().date_naive(),
+            requests: today.requests,
+            tokens: today.tokens(),
+            cache: CompletionCache::new(cache_size),
+        };
+        Self {
+            inner,
+            provider,
+            meter: Arc::new(Mutex::new(meter)),
+            event_tx,
+        }
+    }
+
+    fn report(&self, req: &CompletionRequest, cached: bool) -> Report {
+        Report {
+            record: UsageRecord {
+                provider: self.provider.clone(),
+                model: req.model.clone(),
+                usage: Usage::default(),
+                cached,
+            },
+            tally: req.usage.clone(),
+            meter: self.meter.clone(),
+            event_tx: self.event_tx.clone(),
+        }
+    }
+
+    /// The cached completion for `key`, or `None` once the request is
+    /// admitted under the limits.
+    fn lookup(&self, key: u64) -> Result<Option<String>> {
+        let mut meter = self
+            .meter
+            .lock()
+            .map_err(|_| anyhow::anyhow!("AI meter poisoned"))?;
+        if let Some(text) = meter.cache.get(key) {
+            return Ok(Some(text));
+        }
+        meter.admit(Instant::now(), chrono::Local::now().date_naive())?;
+        Ok(None)
+    }
+
+    fn store(meter: &Mutex<Meter>, key: u64, text: String) {
+        if let Ok(mut meter) = meter.lock() {
+            meter.cache.put(key, text);
+        }
+    }
+}
+
+#[async_trait]
+impl AiProvider for MeteredProvider {
+    async fn complete(
+        &self,
+        mut req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<String> {
+        let key = cache_key(&req);
+        req.usage = UsageTally::default();
+        if let Some(text) = self.lookup(key)? {
+            let _report = self.report(&req, true);
+            return Ok(text);
+        }
+        let _report = self.report(&req, false);
+        let text = self.inner.complete(req, cancel).await?;
+        Self::store(&self.meter, key, text.clone());
+        Ok(text)
+    }
+
+    async fn complete_stream(
+        &self,
+        mut req: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Result<TextStream> {
+        let key = cache_key(&req);
+        req.usage = UsageTally::default();
+        if let Some(text) = self.lookup(key)? {
+            let _report = self.report(&req, true);
+            return Ok(Box::pin(futures::stream::iter([Ok(text)])));
+        }
+        let report = self.report(&req, false);
+        let stream = self.inner.complete_stream(req, cancel).await?;
+        // Cache the completion only once the stream has run to its end
+        let meter = self.meter.clone();
+        Ok(Box::pin(futures::stream::unfold(
+            Some((stream, String::new(), report)),
+            move |state| {
+                let meter = meter.clone();
+                async move {
+                    let (mut stream, mut text, report) = state?;
+                    match stream.next().await {
+                        Some(Ok(delta)) => {
+                            text.push_str(&delta);
+                            Some((Ok(delta), Some((stream, text, report))))
+                        }
+                        Some(Err(e)) => Some((Err(e), None)),
+                        None => {
+                            Self::store(&meter, key, text);
+                            None
+                        }
+                    }
+                }
+            },
+        )))
+    }
+
+    fn clone_box(&self) -> Box<dyn AiProvider> {
+        Box::new(Self {
+            inner: self.inner.clone_box(),
+            provider: self.provider.clone(),
+            meter: self.meter.clone(),
+            event_tx: self.event_tx.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Answers with the input reversed, reporting a token per character.
+    struct Echo {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl AiProvider for Echo {
+        async fn complete(&self, req: CompletionRequest, _: CancellationToken) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            req.usage.set_input(req.partial_input.len() as u64);
+            req.usage.set_output(req.partial_input.len() as u64);
+            Ok(req.partial_input.chars().rev().collect())
+        }
+
+        async fn complete_stream(
+            &self,
+            req: CompletionRequest,
+            cancel: CancellationToken,
+        ) -> Result<TextStream> {
+            let text = self.complete(req, cancel).await?;
+            let deltas: Vec<Result<String>> = text.chars().map(|c| Ok(c.to_string())).collect();
+            Ok(Box::pin(futures::stream::iter(deltas)))
+        }
+
+        fn clone_box(&self) -> Box<dyn AiProvider> {
+            Box::new(Echo {
+                calls: self.calls.clone(),
+            })
+        }
+    }
+
+    fn request(input: &str) -> CompletionRequest {
+        CompletionRequest::new(
+            "m".to_string(),
+            String::new(),
+            vec![],
+            input.to_string(),
+            CompletionKind::Prompt,
+        )
+    }
+
+    fn metered(
+        limits: LimitsConfig,
+    ) -> (
+        MeteredProvider,
+        Arc<AtomicUsize>,
+        mpsc::UnboundedReceiver<AppEvent>,
+    ) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let echo = Box::new(Echo {
+            calls: calls.clone(),
+        });
+        let provider = MeteredProvider::new(
+            echo,
+            "echo".to_string(),
+            limits,
+            2,
+            UsageTotals::default(),
+            tx,
+        );
+        (provider, calls, rx)
+    }
+
+    fn records(rx: &mut mpsc::UnboundedReceiver<AppEvent>) -> Vec<UsageRecord> {
+        let mut records = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::AiUsage(record) = event {
+                records.push(record);
+            }
+        }
+        records
+    }
+
+    #[tokio::test]
+    async fn repeated_requests_come_from_the_cache() {
+        let (provider, calls, mut rx) = metered(LimitsConfig::default());
+        let cancel = CancellationToken::new();
+        let first = provider
+            .complete(request("abc"), cancel.clone())
+            .await
+            .unwrap();
+        let streamed: Vec<String> = provider
+            .complete_stream(request("abc"), cancel.clone())
+            .await
+            .unwrap()
+            .map(|d| d.unwrap())
+            .collect()
+            .await;
+        assert_eq!(first, "cba");
+        assert_eq!(streamed.concat(), "cba");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let records = records(&mut rx);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].usage.total(), 6);
+        assert!(!records[0].cached);
+        assert!(records[1].cached);
+        assert_eq!(records[1].usage, Usage::default());
+    }
+
+    #[tokio::test]
+    async fn streamed_completions_are_cached_once_finished() {
+        let (provider, calls, mut rx) = metered(LimitsConfig::default());
+        let cancel = CancellationToken::new();
+        let mut stream = provider
+            .complete_stream(request("xyz"), cancel.clone())
+            .await
+            .unwrap();
+        stream.next().await;
+        drop(stream);
+        assert_eq!(
+            records(&mut rx)[0].usage.total(),
+            6,
+            "cancelled stream still counts"
+        );
+
+        let stream = provider
+            .complete_stream(request("xyz"), cancel.clone())
+            .await
+            .unwrap();
+        assert_eq!(stream.map(|d| d.unwrap()).collect::<String>().await, "zyx");
+        provider.complete(request("xyz"), cancel).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn limits_refuse_requests_but_not_cache_hits() {
+        let limits = LimitsConfig {
+            requests_per_minute: 2,
+            ..LimitsConfig::default()
+        };
+        let (provider, calls, _rx) = metered(limits);
+        let cancel = CancellationToken::new();
+        provider
+            .complete(request("a"), cancel.clone())
+            .await
+            .unwrap();
+        provider
+            .complete(request("b"), cancel.clone())
+            .await
+            .unwrap();
+        let err = provider
+            .complete(request("c"), cancel.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "limit reached: 2 requests a minute");
+        assert_eq!(provider.complete(request("a"), cancel).await.unwrap(), "a");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn daily_limits_reset_on_a_new_day() {
+        let mut meter = Meter {
+            limits: LimitsConfig {
+                requests_per_day: 1,
+                tokens_per_day: 100,
+                ..LimitsConfig::default()
+            },
+            recent: VecDeque::new(),
+            day: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            requests: 0,
+            tokens: 0,
+            cache: CompletionCache::new(0),
+        };
+        let now = Instant::now();
+        let day = meter.day;
+        meter.admit(now, day).unwrap();
+        assert!(meter.admit(now, day).is_err());
+        let next = day.succ_opt().unwrap();
+        meter.admit(now, next).unwrap();
+        meter.tokens = 100;
+        meter.requests = 0;
+        let err = meter.admit(now, next).unwrap_err().to_string();
+        assert_eq!(err, "limit reached: 100 tokens a day");
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        let mut cache = CompletionCache::new(2);
+        cache.put(1, "one".to_string());
+        cache.put(2, "two".to_string());
+        assert_eq!(cache.get(1).as_deref(), Some("one"));
+        cache.put(3, "three".to_string());
+        assert_eq!(cache.get(2), None);
+        assert_eq!(cache.get(1).as_deref(), Some("one"));
+        assert_eq!(cache.get(3).as_deref(), Some("three"));
+    }
+}
@@ -1,8 +1,13 @@
+use futures::StreamExt;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
-use crate::ai::context::{build_context, RawMessage, SYSTEM_PROMPT};
-use crate::ai::providers::{AiProvider, CompletionRequest};
+use crate::ai::context::{
+    build_context, parse_list, parse_smart_replies, translate_draft_request, translate_request,
+    ChatPrompts, RawMessage, CATCH_UP_PROMPT, MAX_CATCH_UP_BULLETS, SMART_REPLY_REQUEST,
+    TRANSLATE_PROMPT,
+};
+use crate::ai::providers::{AiProvider, CompletionKind, CompletionRequest};
 use crate::config::settings::AiConfig;
 use crate::tui::event::AppEvent;
 
@@ -11,13 +16,44 @@ pub struct AiWorker {
     config: AiConfig,
     event_tx: mpsc::UnboundedSender<AppEvent>,
     cancel_token: Option<CancellationToken>,
+    smart_reply_token: Option<CancellationToken>,
+    catch_up_token: Option<CancellationToken>,
+    draft_token: Option<CancellationToken>,
     summary_handles: Vec<tokio::task::JoinHandle<()>>,
     shutdown_token: CancellationToken,
 }
 
+/// Unread messages of one chat to summarise.
+pub struct CatchUpChat {
+    pub chat_id: String,
+    pub chat_name: String,
+    /// Id of the newest message, identifying what the summary covers.
+    pub last_message_id: String,
+    /// `sender: text` lines, oldest first.
+    pub lines: Vec<String>,
+}
+
+/// Bullet-point summary of one chat's unread messages, or why it failed.
+#[derive(Debug)]
+pub struct CatchUpSummary {
+    pub chat_id: String,
+    pub last_message_id: String,
+    pub bullets: Result<Vec<String>, String>,
+}
+
+/// Translation of a message, or why it failed.
+#[derive(Debug)]
+pub struct MessageTranslation {
+    pub chat_id: String,
+    pub message_id: String,
+    pub language: String,
+    pub text: Result<String, String>,
+}
+
 pub struct AiRequest {
     pub partial_input: String,
     pub messages: Vec<RawMessage>,
+    pub prompts: ChatPrompts,
     pub summary: Option<String>,
 }
 
@@ -32,11 +68,19 @@ impl AiWorker {
             config,
             event_tx,
             cancel_token: None,
+            smart_reply_token: None,
+            catch_up_token: None,
+            draft_token: None,
             summary_handles: Vec::new(),
             shutdown_token: CancellationToken::new(),
         }
     }
 
+    /// Use `model` for requests from now on.
+    pub fn set_model(&mut self, model: String) {
+        self.config.model = model;
+    }
+
     pub fn shutdown(&mut self) {
         self.shutdown_token.cancel();
         for handle in self.summary_handles.drain(..) {
@@ -45,6 +89,11 @@ impl AiWorker {
         if let Some(token) = self.cancel_token.take() {
             token.cancel();
         }
+        self.cancel_smart_replies();
+        self.cancel_catch_up();
+        if let Some(token) = self.draft_token.take() {
+            token.cancel();
+        }
     }
 
     /// If messages exceed the threshold, asynchronously generate a summary
@@ -53,6 +102,7 @@ impl AiWorker {
         &mut self,
         chat_id: String,
         messages: Vec<crate::ai::context::RawMessage>,
+        system: String,
         threshold: usize,
         db_tx: tokio::sync::mpsc::UnboundedSender<(String, String)>,
     ) {
@@ -81,12 +131,13 @@ impl AiWorker {
                 older.join("\n")
             );
 
-            let req = crate::ai::providers::CompletionRequest {
+            let req = crate::ai::providers::CompletionRequest::new(
                 model,
-                system: "You summarise conversations concisely.".to_string(),
-                context: vec![],
-                partial_input: prompt,
-            };
+                system,
+                vec![],
+                prompt,
+                CompletionKind::Prompt,
+            );
 
             if cancel.is_cancelled() {
                 return;
@@ -119,26 +170,387 @@ impl AiWorker {
         tokio::spawn(async move {
             let context = build_context(&req.messages, req.summary.as_deref(), last_n);
 
-            let completion_req = CompletionRequest {
+            let completion_req = CompletionRequest::new(
                 model,
-                system: SYSTEM_PROMPT.to_string(),
+                req.prompts.completion,
                 context,
-                partial_input: req.partial_input,
-            };
+                req.partial_input,
+                CompletionKind::Autocomplete,
+            );
 
-            let check_token = token.clone();
-            match provider.complete(completion_req, token).await {
-                Ok(text) if !text.is_empty() => {
-                    let _ = event_tx.send(AppEvent::AiSuggestion(text));
+            let result: anyhow::Result<()> = async {
+                let mut stream = provider
+                    .complete_stream(completion_req, token.clone())
+                    .await?;
+                let mut text = String::new();
+                loop {
+                    let delta = tokio::select! {
+                        _ = token.cancelled() => return Ok(()),
+                        delta = stream.next() => delta,
+                    };
+                    let Some(delta) = delta else {
+                        return Ok(());
+                    };
+                    text.push_str(&delta?);
+                    // Grow the ghost text as each delta arrives
+                    let suggestion = text.trim();
+                    if !suggestion.is_empty() {
+                        let _ = event_tx.send(AppEvent::AiSuggestion(suggestion.to_string()));
+                    }
                 }
-                Err(_) if check_token.is_cancelled() => {
+            }
+            .await;
+            match result {
+                Err(_) if token.is_cancelled() => {
                     // Request was cancelled — silently ignore
                 }
                 Err(e) => {
                     let _ = event_tx.send(AppEvent::AiError(e.to_string()));
                 }
-                _ => {}
+                Ok(()) => {}
+            }
+        });
+    }
+
+    /// Suggest replies to the latest message in `chat_id` once the chat has
+    /// been quiet for the debounce delay, replacing any pending suggestion.
+    pub fn request_smart_replies(&mut self, chat_id: String, req: AiRequest) {
+        self.cancel_smart_replies();
+        let token = CancellationToken::new();
+        self.smart_reply_token = Some(token.clone());
+
+        let provider = self.provider.clone_box();
+        let event_tx = self.event_tx.clone();
+        let model = self.config.model.clone();
+        let last_n = self.config.context_messages;
+        let debounce = std::time::Duration::from_millis(self.config.debounce_ms);
+
+        tokio::spawn(async move {
+            // Messages often arrive in bursts; answer the last one
+            tokio::select! {
+                _ = token.cancelled() => return,
+                _ = tokio::time::sleep(debounce) => {}
+            }
+            let completion_req = CompletionRequest::new(
+                model,
+                req.prompts.smart_reply,
+                build_context(&req.messages, req.summary.as_deref(), last_n),
+                SMART_REPLY_REQUEST.to_string(),
+                CompletionKind::Prompt,
+            );
+            match provider.complete(completion_req, token.clone()).await {
+                Ok(text) => {
+                    let replies = parse_smart_replies(&text);
+                    if !replies.is_empty() && !token.is_cancelled() {
+                        let _ = event_tx.send(AppEvent::AiSmartReplies(chat_id, replies));
+                    }
+                }
+                Err(_) if token.is_cancelled() => {}
+                Err(e) => {
+                    let _ = event_tx.send(AppEvent::AiError(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Summarise each chat's unread messages in turn, sending one
+    /// [`AppEvent::AiCatchUp`] per chat. Replaces any catch-up in progress.
+    pub fn catch_up(&mut self, chats: Vec<CatchUpChat>) {
+        self.cancel_catch_up();
+        let token = CancellationToken::new();
+        self.catch_up_token = Some(token.clone());
+
+        let provider = self.provider.clone_box();
+        let event_tx = self.event_tx.clone();
+        let model = self.config.model.clone();
+
+        tokio::spawn(async move {
+            // One at a time: local models serve requests sequentially anyway
+            for chat in chats {
+                let req = CompletionRequest::new(
+                    model.clone(),
+                    CATCH_UP_PROMPT.to_string(),
+                    vec![],
+                    format!(
+                        "Unread messages in \"{}\":\n\n{}",
+                        chat.chat_name,
+                        chat.lines.join("\n")
+                    ),
+                    CompletionKind::Prompt,
+                );
+                let bullets = match provider.complete(req, token.clone()).await {
+                    Ok(text) => {
+                        let bullets = parse_list(&text, MAX_CATCH_UP_BULLETS);
+                        if bullets.is_empty() {
+                            Err("empty summary".to_string())
+                        } else {
+                            Ok(bullets)
+                        }
+                    }
+                    Err(_) if token.is_cancelled() => return,
+                    Err(e) => Err(e.to_string()),
+                };
+                let summary = CatchUpSummary {
+                    chat_id: chat.chat_id,
+                    last_message_id: chat.last_message_id,
+                    bullets,
+                };
+                if token.is_cancelled() || event_tx.send(AppEvent::AiCatchUp(summary)).is_err() {
+                    return;
+                }
             }
         });
     }
+
+    /// Translate a message into `language`, answering with [`AppEvent::AiTranslation`].
+    pub fn translate_message(
+        &mut self,
+        chat_id: String,
+        message_id: String,
+        text: String,
+        language: String,
+    ) {
+        let provider = self.provider.clone_box();
+        let event_tx = self.event_tx.clone();
+        let req = CompletionRequest::new(
+            self.config.model.clone(),
+            TRANSLATE_PROMPT.to_string(),
+            vec![],
+            translate_request(&text, &language),
+            CompletionKind::Prompt,
+        );
+        let cancel = self.shutdown_token.clone();
+
+        tokio::spawn(async move {
+            let text = match provider.complete(req, cancel.clone()).await {
+                Ok(text) if !text.is_empty() => Ok(text),
+                Ok(_) => Err("empty translation".to_string()),
+                Err(_) if cancel.is_cancelled() => return,
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = event_tx.send(AppEvent::AiTranslation(MessageTranslation {
+                chat_id,
+                message_id,
+                language,
+                text,
+            }));
+        });
+    }
+
+    /// Translate our draft in `chat_id` into the language of `their_messages`,
+    /// answering with [`AppEvent::AiDraftTranslation`]. Replaces any draft
+    /// translation in progress.
+    pub fn translate_draft(&mut self, chat_id: String, draft: String, their_messages: Vec<String>) {
+        if let Some(token) = self.draft_token.take() {
+            token.cancel();
+        }
+        let token = CancellationToken::new();
+        self.draft_token = Some(token.clone());
+
+        let provider = self.provider.clone_box();
+        let event_tx = self.event_tx.clone();
+        let req = CompletionRequest::new(
+            self.config.model.clone(),
+            TRANSLATE_PROMPT.to_string(),
+            vec![],
+            translate_draft_request(&draft, &their_messages),
+            CompletionKind::Prompt,
+        );
+
+        tokio::spawn(async move {
+            let text = match provider.complete(req, token.clone()).await {
+                Ok(text) if !text.is_empty() => Ok(text),
+                Ok(_) => Err("empty translation".to_string()),
+                Err(_) if token.is_cancelled() => return,
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = event_tx.send(AppEvent::AiDraftTranslation(chat_id, text));
+        });
+    }
+
+    pub fn cancel_catch_up(&mut self) {
+        if let Some(token) = self.catch_up_token.take() {
+            token.cancel();
+        }
+    }
+
+    /// Drop a pending smart-reply request, e.g. when the chat changes.
+    pub fn cancel_smart_replies(&mut self) {
+        if let Some(token) = self.smart_reply_token.take() {
+            token.cancel();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ai::providers::openai::OpenAiClient;
+    use crate::ai::providers::sse::test_server;
+
+    fn request() -> AiRequest {
+        AiRequest {
+            partial_input: "see you".to_string(),
+            messages: Vec::new(),
+            prompts: ChatPrompts::default(),
+            summary: None,
+        }
+    }
+
+    async fn next_event(rx: &mut mpsc::UnboundedReceiver<AppEvent>) -> Option<AppEvent> {
+        tokio::time::timeout(Duration::from_millis(500), rx.recv())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn suggestion_grows_with_each_delta() {
+        let url = test_server::serve(
+            vec![
+                "data: {\"choices\":[{\"delta\":{\"content\":\" tomorrow\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\" at 5\"}}]}\n\n",
+                "data: [DONE]\n\n",
+            ],
+            Duration::from_millis(5),
+        )
+        .await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut worker = AiWorker::new(
+            Box::new(OpenAiClient::new(url, None)),
+            AiConfig::default(),
+            tx,
+        );
+        worker.request(request());
+
+        let mut suggestions = Vec::new();
+        while let Some(event) = next_event(&mut rx).await {
+            match event {
+                AppEvent::AiSuggestion(text) => suggestions.push(text),
+                AppEvent::AiError(e) => panic!("unexpected error: {}", e),
+                _ => {}
+            }
+        }
+        assert_eq!(suggestions, ["tomorrow", "tomorrow at 5"]);
+    }
+
+    #[tokio::test]
+    async fn shutdown_stops_a_stream_midway() {
+        let url = test_server::serve(
+            vec![
+                "data: {\"choices\":[{\"delta\":{\"content\":\"first\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\" second\"}}]}\n\n",
+                "data: [DONE]\n\n",
+            ],
+            Duration::from_millis(200),
+        )
+        .await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut worker = AiWorker::new(
+            Box::new(OpenAiClient::new(url, None)),
+            AiConfig::default(),
+            tx,
+        );
+        worker.request(request());
+
+        assert!(matches!(
+            next_event(&mut rx).await,
+            Some(AppEvent::AiSuggestion(text)) if text == "first"
+        ));
+        worker.shutdown();
+        assert!(next_event(&mut rx).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn smart_replies_answer_the_last_of_a_burst() {
+        let url = test_server::serve(
+            vec![r#"{"choices":[{"message":{"content":"1. Sounds good\n2. On my way"}}]}"#],
+            Duration::ZERO,
+        )
+        .await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let config = AiConfig {
+            debounce_ms: 20,
+            ..AiConfig::default()
+        };
+        let mut worker = AiWorker::new(Box::new(OpenAiClient::new(url, None)), config, tx);
+        // The first request is superseded while still debouncing
+        worker.request_smart_replies("a".to_string(), request());
+        worker.request_smart_replies("b".to_string(), request());
+
+        match next_event(&mut rx).await {
+            Some(AppEvent::AiSmartReplies(chat_id, replies)) => {
+                assert_eq!(chat_id, "b");
+                assert_eq!(replies, ["Sounds good", "On my way"]);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(next_event(&mut rx).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn catch_up_sends_bullets_per_chat() {
+        let url = test_server::serve(
+            vec![r#"{"choices":[{"message":{"content":"- Dinner moved to 8\n- Alice asks if you can bring cake"}}]}"#],
+            Duration::ZERO,
+        )
+        .await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut worker = AiWorker::new(
+            Box::new(OpenAiClient::new(url, None)),
+            AiConfig::default(),
+            tx,
+        );
+        worker.catch_up(vec![CatchUpChat {
+            chat_id: "family".to_string(),
+            chat_name: "Family".to_string(),
+            last_message_id: "m9".to_string(),
+            lines: vec!["Alice: dinner at 8 now, can you bring cake?".to_string()],
+        }]);
+
+        match next_event(&mut rx).await {
+            Some(AppEvent::AiCatchUp(summary)) => {
+                assert_eq!(summary.chat_id, "family");
+                assert_eq!(summary.last_message_id, "m9");
+                assert_eq!(
+                    summary.bullets.unwrap(),
+                    ["Dinner moved to 8", "Alice asks if you can bring cake"]
+                );
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn translation_names_its_message() {
+        let url = test_server::serve(
+            vec![r#"{"choices":[{"message":{"content":"Good morning!\n"}}]}"#],
+            Duration::ZERO,
+        )
+        .await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut worker = AiWorker::new(
+            Box::new(OpenAiClient::new(url, None)),
+            AiConfig::default(),
+            tx,
+        );
+        worker.translate_message(
+            "chat-1".to_string(),
+            "m1".to_string(),
+            "Guten Morgen!".to_string(),
+            "English".to_string(),
+        );
+
+        match next_event(&mut rx).await {
+            Some(AppEvent::AiTranslation(translation)) => {
+                assert_eq!(translation.chat_id, "chat-1");
+                assert_eq!(translation.message_id, "m1");
+                assert_eq!(translation.language, "English");
+                assert_eq!(translation.text.unwrap(), "Good morning!");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
 }
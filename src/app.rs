@@ -1,9 +1,13 @@
+use std::collections::{HashMap, HashSet};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use crossterm::{
-    event::{DisableBracketedPaste, EnableBracketedPaste, KeyCode},
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        KeyCode, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{
         disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
@@ -11,30 +15,50 @@ use crossterm::{
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 
-use crate::ai::context::RawMessage;
-use crate::ai::providers::anthropic::AnthropicClient;
-use crate::ai::providers::gemini::GeminiClient;
-use crate::ai::providers::openai::OpenAiClient;
-use crate::ai::worker::{AiRequest, AiWorker};
+use crate::ai::context::{ChatPrompts, RawMessage, MAX_CATCH_UP_MESSAGES};
+use crate::ai::embeddings;
+use crate::ai::fallback::{Backend, FallbackProvider};
+use crate::ai::indexer::{embeddable_text, Indexer};
+use crate::ai::providers::client_for;
+use crate::ai::providers::ollama::OllamaClient;
+use crate::ai::redact::{RedactingProvider, Redactor};
+use crate::ai::usage::MeteredProvider;
+use crate::ai::worker::{AiRequest, AiWorker, CatchUpChat};
 use crate::config::AppConfig;
+use crate::core::away::{AutoResponder, AwayRule};
+use crate::core::folders::ChatFolder;
+use crate::core::markup;
 use crate::core::provider::ProviderEvent;
-use crate::core::types::{AuthStatus, MessageContent, Platform};
+use crate::core::types::{AuthStatus, MessageContent, Platform, UnifiedChat, UnifiedMessage};
 use crate::core::MessageRouter;
 use crate::providers::mock::MockProvider;
 use crate::providers::whatsapp::WhatsAppProvider;
-use crate::storage::{AddressBook, Database, ScheduledMessage};
+use crate::storage::{kind_scope, AddressBook, Database, Persona, ScheduledMessage};
 use tui_textarea::TextArea;
 
 use crate::tui;
 use crate::tui::app_state::{
-    AppState, ChatMenuItem, InputMode, ScheduleListState, SchedulePromptState, SearchState,
-    SettingsKey, SettingsValue, TypingInfo,
+    kind_plural, ActivePanel, AppState, CatchUpEntry, CatchUpState, ChatMenuItem, HelpState,
+    InputMode, PaletteState, ScheduleListState, SchedulePromptState, ScheduleTarget, SearchMode,
+    SearchState, SentRequestsState, SettingsKey, SettingsValue, TypingInfo,
 };
+use crate::tui::editor;
 use crate::tui::event::{AppEvent, EventHandler};
-use crate::tui::keybindings::{map_key, Action};
+use crate::tui::image_preview::{self, GraphicsProtocol, ImagePreviews, PreviewSettings};
+use crate::tui::keybindings::{Action, Keymap};
+use crate::tui::mouse::HitTarget;
+use crate::tui::palette::palette_entries;
 use crate::tui::render;
+use crate::tui::scheduler::{retry_delay, MissedSendPolicy, Scheduler};
 use crate::tui::search::top_fuzzy_matches;
-use crate::tui::time_parse::{format_local_time, parse_schedule_time};
+use crate::tui::slash_commands::{self, ComposerInput, SlashCommand};
+use crate::tui::theme::Theme;
+use crate::tui::time_parse::{
+    format_local_time, parse_recurrence_change, parse_schedule_time, ScheduleTime,
+};
+
+/// Shown when an AI action is asked for in a chat marked "Never send to AI".
+const AI_EXCLUDED: &str = "This chat is never sent to AI (change it in the chat menu)";
 
 pub struct App {
     state: AppState,
@@ -43,23 +67,41 @@ pub struct App {
     address_book: AddressBook,
     config: AppConfig,
     config_path: PathBuf,
+    keymap: Keymap,
     ai_worker: Option<AiWorker>,
     last_keystroke: Option<Instant>,
     db_summary_tx: tokio::sync::mpsc::UnboundedSender<(String, String)>,
     db_summary_rx: tokio::sync::mpsc::UnboundedReceiver<(String, String)>,
-    schedule_status_ticks: u8,
+    flash_status_ticks: u8,
     tick_count: u64,
     telegram_auth_tx:
         Option<tokio::sync::mpsc::UnboundedSender<crate::providers::telegram::AuthInput>>,
     event_tx: tokio::sync::mpsc::UnboundedSender<AppEvent>,
+    scheduler: Option<Scheduler>,
+    auto_responder: AutoResponder,
+    /// Away replies picked while handling provider events: (platform, chat id, text).
+    auto_replies: Vec<(Platform, String, String)>,
+    /// Scheduled messages due before this came due while the app was closed.
+    started_at: chrono::DateTime<chrono::Utc>,
+    /// Catch-up summaries by (chat id, id of the newest message they cover).
+    catch_up_cache: HashMap<(String, String), Vec<String>>,
+    /// Embeds message history for semantic search, when `[ai.embeddings]` is on.
+    indexer: Option<Indexer>,
+    /// A batch of messages is out with the indexer.
+    indexing: bool,
 }
 
 impl App {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: AppConfig,
         db: Database,
         address_book: AddressBook,
         config_path: PathBuf,
+        keymap: Keymap,
+        folders: Vec<ChatFolder>,
+        away_rules: Vec<AwayRule>,
+        redactor: Option<Redactor>,
         event_tx: tokio::sync::mpsc::UnboundedSender<AppEvent>,
     ) -> Self {
         tracing::info!(
@@ -70,28 +112,91 @@ impl App {
             debug = config.ai.debug,
             "AI config loaded"
         );
+        let backends = config.ai.backends();
         let ai_worker = if config.ai.enabled {
-            let provider: Box<dyn crate::ai::providers::AiProvider> =
-                match config.ai.provider.as_str() {
-                    "anthropic" => Box::new(AnthropicClient::new(config.ai.api_key.clone())),
-                    "gemini" => Box::new(GeminiClient::new(config.ai.api_key.clone())),
-                    _ => Box::new(OpenAiClient::new(
-                        config.ai.base_url.clone(),
-                        config.ai.api_key.clone(),
-                    )),
-                };
+            let chain = backends
+                .iter()
+                .map(|backend| {
+                    let redactor = redactor
+                        .clone()
+                        .filter(|_| config.ai.redact.applies_to(backend));
+                    tracing::info!(
+                        backend = %backend.name(),
+                        redacted = redactor.is_some(),
+                        "AI backend configured"
+                    );
+                    Backend {
+                        name: backend.name().to_string(),
+                        model: backend.model.clone(),
+                        timeout: Duration::from_secs(backend.timeout_secs),
+                        provider: Box::new(RedactingProvider::new(
+                            client_for(backend, &config.ai.ollama),
+                            redactor,
+                            event_tx.clone(),
+                        )),
+                    }
+                })
+                .collect();
             tracing::info!("AI worker created — autocomplete enabled");
+            let provider = Box::new(FallbackProvider::new(chain, event_tx.clone()));
+            let today = db
+                .ai_usage_on(chrono::Local::now().date_naive())
+                .unwrap_or_default();
+            let provider = Box::new(MeteredProvider::new(
+                provider,
+                backends[0].name().to_string(),
+                config.ai.limits.clone(),
+                config.ai.cache_size,
+                today,
+                event_tx.clone(),
+            ));
             Some(AiWorker::new(provider, config.ai.clone(), event_tx.clone()))
         } else {
             tracing::info!("AI worker NOT created — ai.enabled = false in config");
             None
         };
 
+        let indexer = config.ai.embeddings.enabled.then(|| {
+            tracing::info!(
+                provider = %config.ai.embeddings.provider,
+                model = %config.ai.embeddings.model,
+                "Embedding indexer created — semantic search enabled"
+            );
+            Indexer::new(
+                embeddings::provider_for(&config.ai.embeddings),
+                config.ai.debounce_ms,
+                event_tx.clone(),
+            )
+        });
+
         let (db_summary_tx, db_summary_rx) =
             tokio::sync::mpsc::unbounded_channel::<(String, String)>();
 
         let mut state = AppState::new();
         state.ai_debug = config.ai.debug;
+        if ai_worker.is_some() {
+            state.ai_enabled = true;
+            state.ai_usage = db
+                .ai_usage_on(chrono::Local::now().date_naive())
+                .unwrap_or_default();
+            state.ai_backend = Some(backends[0].name().to_string());
+        }
+        state.theme = load_theme(&config);
+        state.folders = folders;
+        state.away = config.away.enabled;
+        let away_cooldown = chrono::Duration::hours(config.away.cooldown_hours.into());
+        let mut auto_responder = AutoResponder::new(away_rules, away_cooldown);
+        match db.away_replies_since(chrono::Utc::now() - away_cooldown) {
+            Ok(replies) => {
+                for (chat_id, sender, at) in replies {
+                    auto_responder.remember(chat_id, sender, at);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load recent away replies: {}", e),
+        }
+        state.image_previews = ImagePreviews::new(
+            GraphicsProtocol::from_setting(&config.tui.image_previews).map(PreviewSettings::new),
+        );
 
         Self {
             state,
@@ -100,14 +205,22 @@ impl App {
             address_book,
             config,
             config_path,
+            keymap,
             ai_worker,
             last_keystroke: None,
             db_summary_tx,
             db_summary_rx,
-            schedule_status_ticks: 0,
+            flash_status_ticks: 0,
             tick_count: 0,
             telegram_auth_tx: None,
             event_tx,
+            scheduler: None,
+            auto_responder,
+            auto_replies: Vec::new(),
+            started_at: chrono::Utc::now(),
+            catch_up_cache: HashMap::new(),
+            indexer,
+            indexing: false,
         }
     }
 
@@ -128,6 +241,26 @@ impl App {
             .flatten()
             .map(|v| v == "true")
             .unwrap_or(true);
+        // Chats whose incoming messages are translated as they arrive
+        self.state.auto_translate = self
+            .db
+            .get_preferences_with_prefix("auto_translate:")
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, v)| v == "true")
+            .map(|(chat_id, _)| chat_id)
+            .collect();
+        // Chats never sent to the AI provider or the indexer
+        self.state.ai_excluded = self
+            .db
+            .get_preferences_with_prefix("ai_excluded:")
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, v)| v == "true")
+            .map(|(chat_id, _)| chat_id)
+            .collect();
+        // Catch up on messages stored since the last run
+        self.index_messages();
 
         // Register providers
         if self.config.mock_provider.enabled {
@@ -216,11 +349,17 @@ impl App {
         // Load messages for the initially selected chat
         self.load_selected_chat_messages();
 
+        // Restore saved drafts and put the selected chat's draft back in the composer
+        self.state.drafts = self.db.get_all_drafts().unwrap_or_default();
+        self.state.restore_draft();
+
         // Populate activity cache before first render
         self.refresh_activity_cache();
         self.state.activity_last_refresh_tick = 0;
 
-        // Send any overdue scheduled messages
+        // Send (or hold, per schedule.missed_sends) overdue scheduled messages,
+        // then let the timer task wake us for the rest
+        self.scheduler = Some(Scheduler::start(self.event_tx.clone()));
         self.check_scheduled_messages().await;
 
         // Set initial terminal title
@@ -232,6 +371,9 @@ impl App {
         events.start();
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+        if self.config.tui.mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        }
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
@@ -239,7 +381,12 @@ impl App {
         let original_hook = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |panic_info| {
             let _ = disable_raw_mode();
-            let _ = execute!(io::stdout(), DisableBracketedPaste, LeaveAlternateScreen);
+            let _ = execute!(
+                io::stdout(),
+                DisableMouseCapture,
+                DisableBracketedPaste,
+                LeaveAlternateScreen
+            );
             original_hook(panic_info);
         }));
 
@@ -248,11 +395,22 @@ impl App {
             let event = events.next().await;
             match event {
                 Some(AppEvent::Render) => {
-                    let completed = terminal.draw(|f| render::draw(f, &mut self.state))?;
-                    tui::osc8::inject_osc8_hyperlinks(completed.buffer)?;
+                    self.state.key_hints = self
+                        .keymap
+                        .hints(self.state.input_mode, self.state.enter_sends);
+                    let mut completed = terminal.draw(|f| render::draw(f, &mut self.state))?;
+                    if self.state.image_previews.needs_full_redraw() {
+                        // Sixel/iTerm2 pixels stay on screen until the cells
+                        // under them are repainted
+                        terminal.clear()?;
+                        completed = terminal.draw(|f| render::draw(f, &mut self.state))?;
+                    }
+                    tui::osc8::inject_osc8_hyperlinks(completed.buffer, self.state.theme.url)?;
+                    self.state.image_previews.draw()?;
                 }
                 Some(AppEvent::Tick) => {
                     self.handle_tick();
+                    self.send_auto_replies().await;
                     // Save any pending AI summaries to DB
                     while let Ok((key, value)) = self.db_summary_rx.try_recv() {
                         let _ = self.db.set_preference(&key, &value);
@@ -264,25 +422,10 @@ impl App {
                             self.last_keystroke = None;
                             if self.state.input_mode == InputMode::Editing {
                                 let partial = self.state.input.lines().join("\n");
-                                if !partial.is_empty() {
+                                if !partial.is_empty() && self.selected_chat_ai_allowed() {
+                                    let prompts = self.selected_chat_prompts();
                                     if let Some(worker) = self.ai_worker.as_mut() {
-                                        let messages: Vec<RawMessage> = self
-                                            .state
-                                            .messages
-                                            .iter()
-                                            .filter_map(|m| {
-                                                let text = match &m.content {
-                                                    crate::core::types::MessageContent::Text(t) => {
-                                                        t.clone()
-                                                    }
-                                                    _ => return None,
-                                                };
-                                                Some(RawMessage {
-                                                    is_outgoing: m.is_outgoing,
-                                                    text,
-                                                })
-                                            })
-                                            .collect();
+                                        let messages = raw_messages(&self.state.messages);
                                         let summary =
                                             self.state.selected_chat_id().and_then(|id| {
                                                 self.db
@@ -306,6 +449,7 @@ impl App {
                                         worker.request(AiRequest {
                                             partial_input: partial,
                                             messages,
+                                            prompts,
                                             summary,
                                         });
                                     }
@@ -313,17 +457,36 @@ impl App {
                             }
                         }
                     }
-                    self.check_scheduled_messages().await;
+                    self.request_image_previews();
                 }
                 Some(AppEvent::Key(key)) => {
-                    let action = map_key(key, self.state.input_mode, self.state.enter_sends);
-                    self.handle_action(action).await;
+                    let actions =
+                        self.keymap
+                            .resolve(key, self.state.input_mode, self.state.enter_sends);
+                    for action in actions {
+                        if action == Action::ComposeInEditor {
+                            if let Err(e) = self.compose_in_editor(&mut terminal, &mut events).await
+                            {
+                                tracing::error!("External editor failed: {}", e);
+                                self.state.copy_status = Some(format!("Editor: {}", e));
+                            }
+                        } else {
+                            self.handle_action(action).await;
+                        }
+                        if self.state.should_quit {
+                            break;
+                        }
+                    }
                     if self.state.should_quit {
                         break;
                     }
                 }
+                Some(AppEvent::Mouse(mouse)) => {
+                    self.handle_mouse(mouse).await;
+                }
                 Some(AppEvent::Resize(_, _)) => {
-                    // Terminal handles resize automatically
+                    // Terminal handles resize automatically; graphics must be redrawn
+                    self.state.image_previews.invalidate();
                 }
                 Some(AppEvent::Paste(text)) => {
                     if self.state.input_mode == InputMode::Editing {
@@ -336,12 +499,141 @@ impl App {
                 }
                 Some(AppEvent::AiSuggestion(text)) => {
                     if self.state.input_mode == InputMode::Editing {
-                        tracing::info!(suggestion = %text, "AI autocomplete suggestion received");
+                        tracing::debug!(suggestion = %text, "AI autocomplete suggestion received");
+                        // A streamed suggestion arrives once per delta; log only the latest
+                        if self
+                            .state
+                            .ai_debug_log
+                            .last()
+                            .is_some_and(|l| l.starts_with("[suggestion]"))
+                        {
+                            self.state.ai_debug_log.pop();
+                        }
                         self.state.push_ai_log(format!("[suggestion] ← {:?}", text));
                         self.state.ai_suggestion = Some(text);
                         self.state.ai_status = None;
                     }
                 }
+                Some(AppEvent::AiSmartReplies(chat_id, replies)) => {
+                    if self.state.selected_chat_id() == Some(chat_id.as_str()) {
+                        self.state
+                            .push_ai_log(format!("[smart replies] ← {:?}", replies));
+                        self.state.smart_replies = replies;
+                    }
+                }
+                Some(AppEvent::AiCatchUp(summary)) => {
+                    if let Ok(bullets) = &summary.bullets {
+                        self.catch_up_cache.insert(
+                            (summary.chat_id.clone(), summary.last_message_id),
+                            bullets.clone(),
+                        );
+                    }
+                    if let Some(ref mut catch_up) = self.state.catch_up_state {
+                        catch_up.set_bullets(&summary.chat_id, summary.bullets);
+                    }
+                }
+                Some(AppEvent::AiTranslation(translation)) => match translation.text {
+                    Ok(text) => {
+                        if let Err(e) = self.db.save_translation(
+                            &translation.chat_id,
+                            &translation.message_id,
+                            &translation.language,
+                            &text,
+                        ) {
+                            tracing::error!("Failed to save translation: {}", e);
+                        }
+                        if self.state.selected_chat_id() == Some(translation.chat_id.as_str()) {
+                            self.state.translations.insert(translation.message_id, text);
+                        }
+                    }
+                    Err(e) => {
+                        self.state
+                            .push_ai_log(format!("[translate] ← error: {}", e));
+                        self.flash(format!("Translation failed: {}", e));
+                    }
+                },
+                Some(AppEvent::AiDraftTranslation(chat_id, result)) => match result {
+                    Ok(text) => {
+                        if self.state.selected_chat_id() == Some(chat_id.as_str()) {
+                            self.state.set_input(&text);
+                        }
+                    }
+                    Err(e) => self.flash(format!("Translation failed: {}", e)),
+                },
+                Some(AppEvent::Embedded(result)) => {
+                    self.indexing = false;
+                    match result {
+                        Ok(vectors) => {
+                            let model = &self.config.ai.embeddings.model;
+                            if let Err(e) = self.db.save_embeddings(model, &vectors) {
+                                tracing::error!("Failed to save embeddings: {}", e);
+                            } else {
+                                self.index_messages();
+                            }
+                        }
+                        // Retried when the next message arrives
+                        Err(e) => {
+                            tracing::warn!("Embedding failed: {}", e);
+                            self.state
+                                .push_ai_log(format!("[embeddings] ← error: {}", e));
+                        }
+                    }
+                }
+                Some(AppEvent::QueryEmbedding(query, result)) => {
+                    // Answers to queries typed over since are dropped
+                    let current = self
+                        .state
+                        .search_state
+                        .as_ref()
+                        .is_some_and(|ss| ss.mode == SearchMode::Semantic && ss.query == query);
+                    if current {
+                        let model = &self.config.ai.embeddings.model;
+                        let hits = result
+                            .map_err(anyhow::Error::msg)
+                            .and_then(|vector| self.db.semantic_search(model, &vector, 5));
+                        if let Some(ref mut ss) = self.state.search_state {
+                            ss.searching = false;
+                            ss.selected = 0;
+                            ss.hits = hits.as_ref().cloned().unwrap_or_default();
+                        }
+                        if let Err(e) = hits {
+                            self.flash(format!("Could not search messages: {}", e));
+                        }
+                    }
+                }
+                Some(AppEvent::AiRequestSent(sent)) => {
+                    self.state.record_sent_request(sent);
+                }
+                Some(AppEvent::AiUsage(record)) => {
+                    self.state.push_ai_log(format!(
+                        "[usage] {} {}: {}",
+                        record.provider,
+                        record.model,
+                        if record.cached {
+                            "cached".to_string()
+                        } else {
+                            format!(
+                                "{} in / {} out tokens",
+                                record.usage.input_tokens, record.usage.output_tokens
+                            )
+                        }
+                    ));
+                    let today = chrono::Local::now().date_naive();
+                    if let Err(e) = self.db.record_ai_usage(today, &record) {
+                        tracing::warn!(error = %e, "Failed to record AI usage");
+                    }
+                    match self.db.ai_usage_on(today) {
+                        Ok(usage) => self.state.ai_usage = usage,
+                        Err(e) => tracing::warn!(error = %e, "Failed to read AI usage"),
+                    }
+                }
+                Some(AppEvent::AiBackend(name)) => {
+                    self.state.push_ai_log(format!(
+                        "[backend] → {}",
+                        name.as_deref().unwrap_or("none reachable")
+                    ));
+                    self.state.ai_backend = name;
+                }
                 Some(AppEvent::AiError(e)) => {
                     tracing::info!(error = %e, "AI autocomplete error");
                     self.state.push_ai_log(format!("[error] ← {}", e));
@@ -351,6 +643,25 @@ impl App {
                     tracing::error!(error = %e, "Media open error");
                     self.state.copy_status = Some(e);
                 }
+                Some(AppEvent::ImagePreview(msg_id, result)) => {
+                    self.state.image_previews.finish_load(msg_id, result);
+                }
+                Some(AppEvent::ScheduleDue) => {
+                    self.check_scheduled_messages().await;
+                }
+                Some(AppEvent::AiModels(result)) => match result {
+                    Ok(models) => {
+                        if let Some(ref mut s) = self.state.settings_state {
+                            s.set_ai_models(models);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Could not list Ollama models");
+                        if self.state.settings_state.is_some() {
+                            self.flash(format!("Could not list Ollama models: {}", e));
+                        }
+                    }
+                },
                 Some(AppEvent::Quit) | None => {
                     break;
                 }
@@ -358,13 +669,21 @@ impl App {
         }
 
         // Cleanup
+        self.save_draft();
         if let Some(worker) = &mut self.ai_worker {
             worker.shutdown();
         }
+        if let Some(indexer) = &mut self.indexer {
+            indexer.shutdown();
+        }
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.shutdown();
+        }
         self.router.stop_all().await?;
         disable_raw_mode()?;
         execute!(
             terminal.backend_mut(),
+            DisableMouseCapture,
             DisableBracketedPaste,
             LeaveAlternateScreen
         )?;
@@ -374,6 +693,68 @@ impl App {
         Ok(())
     }
 
+    /// Suspend the TUI, open the composer text (or a reply template quoting the
+    /// last incoming message) in `$VISUAL`/`$EDITOR`, and load the result back
+    /// into the input when the editor exits.
+    async fn compose_in_editor(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        events: &mut EventHandler,
+    ) -> anyhow::Result<()> {
+        use std::io::Write;
+        let input = self.state.input.lines().join("\n");
+        let reply_to = self
+            .state
+            .messages
+            .iter()
+            .rev()
+            .find(|m| !m.is_outgoing)
+            .map(|m| (m.sender.as_str(), m.content.as_text()));
+        let (contents, from_template) = editor::build_template(&input, reply_to);
+        // Readable by us only (0600); removed when `file` is dropped
+        let mut file = tempfile::Builder::new()
+            .prefix("zero-drift-compose-")
+            .suffix(".txt")
+            .tempfile()?;
+        file.write_all(contents.as_bytes())?;
+        let path = file.path().to_path_buf();
+
+        // Hand the TTY over: stop the event reader so it doesn't steal the
+        // editor's keystrokes, then leave raw mode and the alternate screen.
+        events.stop();
+        disable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            DisableMouseCapture,
+            DisableBracketedPaste,
+            LeaveAlternateScreen
+        )?;
+        terminal.show_cursor()?;
+
+        let edit_result = editor::run_editor(&path).await;
+
+        // Restore the TUI even when the editor failed to launch.
+        enable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableBracketedPaste
+        )?;
+        if self.config.tui.mouse {
+            execute!(terminal.backend_mut(), EnableMouseCapture)?;
+        }
+        terminal.clear()?;
+        self.state.image_previews.invalidate();
+        events.start();
+
+        let edited = edit_result.and_then(|_| Ok(std::fs::read_to_string(&path)?));
+        drop(file);
+        let text = editor::finish_edit(&edited?, from_template);
+        self.state.set_input(&text);
+        self.state.ai_suggestion = None;
+        Ok(())
+    }
+
     fn refresh_activity_cache(&mut self) {
         let chat_ids: Vec<String> = self.state.chats.iter().map(|c| c.id.clone()).collect();
         let id_refs: Vec<&str> = chat_ids.iter().map(|s| s.as_str()).collect();
@@ -381,18 +762,224 @@ impl App {
         self.state.activity_cache = cache;
     }
 
+    /// Switch to the chat at `idx` in the chat list.
+    async fn select_chat(&mut self, idx: usize) {
+        self.save_draft();
+        self.clear_smart_replies();
+        self.state.chat_list_state.select(Some(idx));
+        self.state.restore_draft();
+        self.load_selected_chat_messages();
+        let open: HashSet<&str> = self.state.messages.iter().map(|m| m.id.as_str()).collect();
+        self.state
+            .image_previews
+            .retain(|msg_id| open.contains(msg_id));
+        self.capture_new_message_count();
+        self.clear_selected_unread();
+        self.send_read_receipts().await;
+        self.refresh_title();
+    }
+
+    /// Switch the chat list to folder tab `idx`, opening the folder's first
+    /// chat when the current one is not in it.
+    async fn select_folder(&mut self, idx: usize) {
+        let previous = self.state.chat_list_state.selected();
+        if self.state.set_active_folder(idx) {
+            if let Some(first) = self.state.chat_list_state.selected() {
+                // select_chat stashes the draft of the chat we are leaving
+                self.state.chat_list_state.select(previous);
+                self.select_chat(first).await;
+            }
+        }
+    }
+
+    /// Route a click or wheel event through the hit map of the last frame.
+    async fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.state.qr_code.is_some() {
+            return;
+        }
+        let target = self.state.hit_map.at(mouse.column, mouse.row).cloned();
+        let mode = self.state.input_mode;
+        let main_view = matches!(
+            mode,
+            InputMode::Normal | InputMode::Editing | InputMode::MessageSelect
+        );
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let up = mouse.kind == MouseEventKind::ScrollUp;
+                let pick = |prev: Action, next: Action| if up { prev } else { next };
+                let action = match (mode, target) {
+                    (InputMode::Settings, _) => pick(Action::SettingsPrev, Action::SettingsNext),
+                    (InputMode::Searching, _) => pick(Action::SearchPrev, Action::SearchNext),
+                    (InputMode::ChatMenu, _) => pick(Action::ChatMenuPrev, Action::ChatMenuNext),
+                    (_, Some(HitTarget::Folder(_))) if main_view => {
+                        pick(Action::PrevFolder, Action::NextFolder)
+                    }
+                    (_, Some(HitTarget::ChatList | HitTarget::Chat(_))) if main_view => {
+                        pick(Action::PrevChat, Action::NextChat)
+                    }
+                    (
+                        _,
+                        Some(HitTarget::MessageView | HitTarget::Message(_) | HitTarget::Url(_)),
+                    ) if main_view => pick(Action::ScrollUp, Action::ScrollDown),
+                    _ => Action::None,
+                };
+                self.handle_action(action).await;
+            }
+            MouseEventKind::Down(button) => match (mode, target) {
+                (InputMode::Settings, Some(HitTarget::SettingsItem(i))) => {
+                    if let Some(ref mut s) = self.state.settings_state {
+                        s.selected = i;
+                        s.toggle_selected();
+                    }
+                }
+                (InputMode::Searching, Some(HitTarget::SearchResult(pos))) => {
+                    if let Some(ref mut ss) = self.state.search_state {
+                        ss.selected = pos;
+                    }
+                    self.handle_action(Action::SearchConfirm).await;
+                }
+                (InputMode::ChatMenu, Some(HitTarget::ChatMenuItem(i))) => {
+                    if let Some(ref mut menu) = self.state.chat_menu_state {
+                        menu.selected = i;
+                    }
+                    self.handle_action(Action::ChatMenuConfirm).await;
+                }
+                // Clicking outside an overlay dismisses it
+                (_, Some(HitTarget::Overlay)) => {}
+                (InputMode::Settings, _) => self.handle_action(Action::SettingsClose).await,
+                (InputMode::Searching, _) => self.handle_action(Action::SearchClose).await,
+                (InputMode::ChatMenu, _) => self.handle_action(Action::ChatMenuClose).await,
+                (_, Some(target)) if main_view => self.handle_click(target, button).await,
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Click in the chat list, message view or input bar.
+    async fn handle_click(&mut self, target: HitTarget, button: MouseButton) {
+        match target {
+            HitTarget::Chat(idx) => {
+                if self.state.chat_list_state.selected() != Some(idx) {
+                    self.select_chat(idx).await;
+                }
+                if self.state.input_mode != InputMode::Editing {
+                    self.state.active_panel = ActivePanel::ChatList;
+                }
+                if button == MouseButton::Right {
+                    self.state.open_chat_menu();
+                }
+            }
+            HitTarget::Url(url) if button == MouseButton::Left => {
+                let err_tx = self.event_tx.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = tui::media::open_url(&url).await {
+                        let _ = err_tx
+                            .send(AppEvent::MediaError(format!("Failed to open link: {}", e)));
+                    }
+                });
+            }
+            HitTarget::Message(idx) => {
+                self.state.selected_message_idx = Some(idx);
+                self.state.input_mode = InputMode::MessageSelect;
+                self.state.active_panel = ActivePanel::MessageView;
+                let is_image = matches!(
+                    self.state.messages.get(idx).map(|m| &m.content),
+                    Some(MessageContent::Image { .. })
+                );
+                if is_image && button == MouseButton::Left {
+                    self.handle_action(Action::OpenMedia).await;
+                }
+            }
+            HitTarget::SmartReply(i) => {
+                self.handle_action(Action::InsertSmartReply(i)).await;
+            }
+            HitTarget::Input => {
+                if self.state.input_mode == InputMode::MessageSelect {
+                    self.state.exit_message_select();
+                }
+                if self.state.input_mode != InputMode::Editing {
+                    self.handle_action(Action::EnterEditing).await;
+                }
+            }
+            HitTarget::Folder(idx) => {
+                self.select_folder(idx).await;
+                self.state.active_panel = ActivePanel::ChatList;
+            }
+            HitTarget::ChatList => self.state.active_panel = ActivePanel::ChatList,
+            HitTarget::MessageView => self.state.active_panel = ActivePanel::MessageView,
+            _ => {}
+        }
+    }
+
+    /// Start background thumbnail loads for images in the open chat that
+    /// have not been requested yet. Only media the provider can download is
+    /// previewed, and only up to [`image_preview::MAX_PREVIEW_BYTES`].
+    fn request_image_previews(&mut self) {
+        let Some(settings) = self.state.image_previews.settings else {
+            return;
+        };
+        for msg in &self.state.messages {
+            let MessageContent::Image { decrypt_params, .. } = &msg.content else {
+                continue;
+            };
+            let Some(id) = self.state.image_previews.begin_load(&msg.id) else {
+                continue;
+            };
+            let fetch = match decrypt_params {
+                Some(params) if params.file_length > image_preview::MAX_PREVIEW_BYTES => {
+                    Err(format!("too large ({} bytes)", params.file_length))
+                }
+                Some(params) => self
+                    .router
+                    .get_provider(msg.platform)
+                    .and_then(|p| p.media_downloader())
+                    .map(|download| download(params.clone()))
+                    .ok_or_else(|| "no downloader".to_string()),
+                None => Err("no provider media".to_string()),
+            };
+            let fetch = match fetch {
+                Ok(fetch) => fetch,
+                Err(e) => {
+                    self.state
+                        .image_previews
+                        .finish_load(msg.id.clone(), Err(e));
+                    continue;
+                }
+            };
+            let msg_id = msg.id.clone();
+            let tx = self.event_tx.clone();
+            tokio::spawn(async move {
+                let result = match fetch.await {
+                    Ok(bytes) => tokio::task::spawn_blocking(move || {
+                        image_preview::build_thumbnail(&bytes, settings, id)
+                    })
+                    .await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|r| r),
+                    Err(e) => Err(e),
+                };
+                let _ = tx.send(AppEvent::ImagePreview(
+                    msg_id,
+                    result.map_err(|e| e.to_string()),
+                ));
+            });
+        }
+    }
+
     fn handle_tick(&mut self) {
         // Clear transient copy status after one tick so it disappears quickly
         self.state.copy_status = None;
 
-        if self.state.schedule_status.is_some() {
-            self.schedule_status_ticks += 1;
-            if self.schedule_status_ticks >= 8 {
-                self.state.schedule_status = None;
-                self.schedule_status_ticks = 0;
+        if self.state.flash_status.is_some() {
+            self.flash_status_ticks += 1;
+            if self.flash_status_ticks >= 8 {
+                self.state.flash_status = None;
+                self.flash_status_ticks = 0;
             }
         } else {
-            self.schedule_status_ticks = 0;
+            self.flash_status_ticks = 0;
         }
 
         // Advance tick counter and drive typing indicator animation
@@ -425,6 +1012,7 @@ impl App {
                     if let Err(e) = self.db.insert_message(&msg) {
                         tracing::error!("Failed to insert message: {}", e);
                     }
+                    self.index_messages();
 
                     // Increment activity cache for the current hour bucket (slot 23).
                     self.state
@@ -462,6 +1050,58 @@ impl App {
                         .map(|id| id == msg.chat_id)
                         .unwrap_or(false);
 
+                    if self.state.away {
+                        // A chat that isn't loaded could be a group or channel, so it gets no reply
+                        let kind = self
+                            .state
+                            .chats
+                            .iter()
+                            .find(|c| c.id == msg.chat_id)
+                            .map(|c| c.kind.clone());
+                        let reply = kind.and_then(|kind| {
+                            self.auto_responder
+                                .reply_for(&msg, &kind, chrono::Local::now())
+                        });
+                        if let Some(reply) = reply {
+                            if let Err(e) = self.db.record_away_reply(
+                                &msg.chat_id,
+                                &msg.sender,
+                                chrono::Utc::now(),
+                            ) {
+                                tracing::warn!("Failed to record away reply: {}", e);
+                            }
+                            self.auto_replies
+                                .push((msg.platform, msg.chat_id.clone(), reply));
+                        }
+                    }
+
+                    if !msg.is_outgoing
+                        && self.state.auto_translate.contains(&msg.chat_id)
+                        && self.ai_allowed(&msg.chat_id)
+                    {
+                        if let (Some(worker), Some(text)) =
+                            (self.ai_worker.as_mut(), message_text(&msg))
+                        {
+                            worker.translate_message(
+                                msg.chat_id.clone(),
+                                msg.id.clone(),
+                                text.to_string(),
+                                self.config.ai.translate_to.clone(),
+                            );
+                        }
+                    }
+
+                    // New messages bring archived chats back unless they are muted
+                    let unarchive = !msg.is_outgoing
+                        && self
+                            .state
+                            .chats
+                            .iter()
+                            .any(|c| c.id == msg.chat_id && c.is_archived && !c.is_muted);
+                    if unarchive {
+                        self.set_chat_archived(&msg.chat_id, false);
+                    }
+
                     if !is_current_chat && !msg.is_outgoing {
                         if let Some(chat) =
                             self.state.chats.iter_mut().find(|c| c.id == msg.chat_id)
@@ -482,33 +1122,28 @@ impl App {
                         self.state.messages.push(msg.clone());
                         self.state.scroll_offset = 0; // auto-scroll to bottom
 
-                        if let Some(worker) = &mut self.ai_worker {
+                        let prompts = self.selected_chat_prompts();
+                        let allowed = self.ai_allowed(&msg.chat_id);
+                        if let Some(worker) = self.ai_worker.as_mut().filter(|_| allowed) {
                             if let Some(chat_id) = self.state.selected_chat_id() {
-                                let messages: Vec<crate::ai::context::RawMessage> = self
-                                    .state
-                                    .messages
-                                    .iter()
-                                    .filter_map(|m| {
-                                        let text = match &m.content {
-                                            crate::core::types::MessageContent::Text(t) => {
-                                                t.clone()
-                                            }
-                                            _ => return None,
-                                        };
-                                        Some(crate::ai::context::RawMessage {
-                                            is_outgoing: m.is_outgoing,
-                                            text,
-                                        })
-                                    })
-                                    .collect();
+                                let messages = raw_messages(&self.state.messages);
                                 worker.maybe_generate_summary(
                                     chat_id.to_string(),
                                     messages,
+                                    prompts.summary,
                                     self.config.ai.summary_threshold,
                                     self.db_summary_tx.clone(),
                                 );
                             }
                         }
+
+                        if msg.is_outgoing {
+                            self.clear_smart_replies();
+                        } else if let Some(req) = self.smart_reply_request() {
+                            if let Some(worker) = self.ai_worker.as_mut() {
+                                worker.request_smart_replies(msg.chat_id.clone(), req);
+                            }
+                        }
                     }
 
                     // Move chat to top of its group (pinned→top of pinned, unpinned→top of unpinned)
@@ -584,6 +1219,20 @@ impl App {
                         }
                     }
                 }
+                ProviderEvent::ArchiveChanged { chat_id, archived } => {
+                    let changed = self
+                        .state
+                        .chats
+                        .iter()
+                        .any(|c| c.id == chat_id && c.is_archived != archived);
+                    if changed {
+                        self.set_chat_archived(&chat_id, archived);
+                    }
+                }
+                ProviderEvent::FoldersUpdated(platform, folders) => {
+                    tracing::info!(?platform, count = folders.len(), "Chat folders imported");
+                    self.state.set_imported_folders(platform, folders);
+                }
                 ProviderEvent::ChatsUpdated(chats) => {
                     // Persist and merge — but skip expensive DB reads
                     for chat in &chats {
@@ -752,7 +1401,9 @@ impl App {
                 self.state.switch_panel();
             }
             Action::NextChat => {
+                self.save_draft();
                 self.state.select_next_chat();
+                self.state.restore_draft();
                 self.load_selected_chat_messages();
                 self.capture_new_message_count();
                 self.clear_selected_unread();
@@ -760,47 +1411,73 @@ impl App {
                 self.refresh_title();
             }
             Action::PrevChat => {
+                self.save_draft();
                 self.state.select_prev_chat();
+                self.state.restore_draft();
+                self.load_selected_chat_messages();
+                self.capture_new_message_count();
+                self.clear_selected_unread();
+                self.send_read_receipts().await;
+                self.refresh_title();
+            }
+            Action::FirstChat | Action::LastChat => {
+                self.save_draft();
+                if action == Action::FirstChat {
+                    self.state.select_first_chat();
+                } else {
+                    self.state.select_last_chat();
+                }
+                self.state.restore_draft();
                 self.load_selected_chat_messages();
                 self.capture_new_message_count();
                 self.clear_selected_unread();
                 self.send_read_receipts().await;
                 self.refresh_title();
             }
+            Action::NextFolder | Action::PrevFolder => {
+                let count = self.state.folders.len();
+                let idx = if action == Action::NextFolder {
+                    (self.state.active_folder + 1) % count
+                } else {
+                    (self.state.active_folder + count - 1) % count
+                };
+                self.select_folder(idx).await;
+            }
             Action::EnterEditing => {
                 self.state.enter_editing();
             }
+            Action::ExitEditing if self.state.editing_scheduled.is_some() => {
+                self.finish_scheduled_edit(None);
+            }
             Action::ExitEditing => {
                 self.state.exit_editing();
                 self.state.ai_suggestion = None;
                 self.state.ai_status = None;
             }
+            Action::SubmitMessage if self.state.editing_scheduled.is_some() => {
+                let text = self.state.take_input();
+                self.finish_scheduled_edit(Some(text));
+            }
             Action::SubmitMessage => {
                 let input = self.state.take_input();
                 if !input.is_empty() {
-                    if let Some(chat_id) = self.state.selected_chat_id().map(|s| s.to_string()) {
-                        // Determine which provider owns this chat
-                        let platform = self
-                            .state
-                            .chats
-                            .iter()
-                            .find(|c| c.id == chat_id)
-                            .map(|c| c.platform)
-                            .unwrap_or(Platform::Mock);
-
-                        if let Some(provider) = self.router.get_provider_mut(platform) {
-                            match provider
-                                .send_message(&chat_id, MessageContent::Text(input))
-                                .await
+                    match slash_commands::parse_input(&input) {
+                        Ok(ComposerInput::Message(text)) => {
+                            self.clear_smart_replies();
+                            if let Err(e) =
+                                self.send_to_selected_chat(MessageContent::Text(text)).await
                             {
-                                Ok(_) => {
-                                    tracing::debug!("Message sent via {:?}", platform);
-                                }
-                                Err(e) => {
-                                    tracing::error!("Failed to send message: {}", e);
-                                }
+                                tracing::error!("Failed to send message: {}", e);
                             }
                         }
+                        Ok(ComposerInput::Command(command)) => {
+                            self.run_slash_command(command).await;
+                        }
+                        Err(e) => {
+                            // Keep the text so the command can be corrected
+                            self.state.set_input(&input);
+                            self.flash(format!("Could not run command: {}", e));
+                        }
                     }
                 }
             }
@@ -814,6 +1491,9 @@ impl App {
             Action::ClearInput => {
                 self.state.input = TextArea::default();
             }
+            Action::ComposeInEditor => {
+                // Handled in the run loop — it needs to suspend the terminal.
+            }
             Action::ScrollUp => {
                 self.state.scroll_up();
             }
@@ -826,6 +1506,16 @@ impl App {
                     self.state.enter_sends,
                     self.state.show_activity_graph,
                 );
+                let backends = self.config.ai.backends();
+                if let Some(ollama) = backends.iter().find(|b| b.provider == "ollama") {
+                    let client =
+                        OllamaClient::new(ollama.base_url.clone(), self.config.ai.ollama.clone());
+                    let tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        let models = client.list_models().await.map_err(|e| e.to_string());
+                        let _ = tx.send(AppEvent::AiModels(models));
+                    });
+                }
             }
             Action::SettingsNext => {
                 if let Some(ref mut s) = self.state.settings_state {
@@ -850,6 +1540,14 @@ impl App {
                     } else {
                         tracing::info!("Config saved to {}", self.config_path.display());
                     }
+                    // Theme applies live
+                    if self.config.tui.theme != self.state.theme.name {
+                        self.state.theme = load_theme(&self.config);
+                    }
+                    self.state.away = self.config.away.enabled;
+                    if let Some(worker) = self.ai_worker.as_mut() {
+                        worker.set_model(self.config.ai.model.clone());
+                    }
                 }
                 // Save EnterSends to SQLite and apply live (no restart needed)
                 if let Some(ref settings) = self.state.settings_state {
@@ -896,6 +1594,8 @@ impl App {
                 if let Some(idx) = self.state.chat_list_state.selected() {
                     if let Some(chat) = self.state.chats.get(idx) {
                         let name = chat.display_name.as_ref().unwrap_or(&chat.name).clone();
+                        // Park the composer text so the rename field doesn't clobber it
+                        self.save_draft();
                         let mut ta = TextArea::from(vec![name]);
                         ta.move_cursor(tui_textarea::CursorMove::End);
                         self.state.input = ta;
@@ -911,23 +1611,54 @@ impl App {
                     .first()
                     .cloned()
                     .unwrap_or_default();
-                self.state.input = tui_textarea::TextArea::default();
-                if !new_name.is_empty() {
-                    if let Some(idx) = self.state.chat_list_state.selected() {
-                        if let Some(chat) = self.state.chats.get_mut(idx) {
-                            chat.display_name = Some(new_name.clone());
-                            let _ = self.address_book.set_display_name(&chat.id, &new_name);
-                        }
-                    }
-                }
+                self.state.restore_draft();
+                self.rename_selected_chat(&new_name);
                 self.state.input_mode = InputMode::Normal;
             }
             Action::CancelRename => {
-                self.state.input = TextArea::default();
+                self.state.restore_draft();
+                self.state.input_mode = InputMode::Normal;
+            }
+            Action::ConfirmStyleNotes => {
+                let style = self
+                    .state
+                    .input
+                    .lines()
+                    .first()
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default();
+                self.state.restore_draft();
+                self.state.input_mode = InputMode::Normal;
+                if let Some(chat_id) = self.state.selected_chat_id().map(|s| s.to_string()) {
+                    let persona = Persona {
+                        style: (!style.is_empty()).then_some(style),
+                        ..self.db.get_persona(&chat_id).unwrap_or_default()
+                    };
+                    match self.db.set_persona(&chat_id, &persona) {
+                        Ok(()) if persona.style.is_some() => self.flash("AI style notes saved"),
+                        Ok(()) => self.flash("AI style notes cleared"),
+                        Err(e) => self.flash(format!("Could not save style notes: {}", e)),
+                    }
+                }
+            }
+            Action::CancelStyleNotes => {
+                self.state.restore_draft();
                 self.state.input_mode = InputMode::Normal;
             }
             Action::OpenChatMenu => {
                 self.state.open_chat_menu();
+                if self.ai_worker.is_some() {
+                    if let Some(ref mut menu) = self.state.chat_menu_state {
+                        let persona = self.db.get_persona(&menu.chat_id).unwrap_or_default();
+                        let names = self.config.ai.prompts.keys().cloned().collect();
+                        menu.add_persona_items(persona, names);
+                    }
+                }
+                if self.ai_worker.is_some() || self.indexer.is_some() {
+                    if let Some(ref mut menu) = self.state.chat_menu_state {
+                        menu.items.push(ChatMenuItem::ToggleAiExcluded);
+                    }
+                }
             }
             Action::ChatMenuNext => {
                 if let Some(ref mut menu) = self.state.chat_menu_state {
@@ -943,41 +1674,68 @@ impl App {
                 if let Some(ref menu) = self.state.chat_menu_state {
                     let selected_item = menu.items.get(menu.selected).cloned();
                     let chat_id = menu.chat_id.clone();
-                    let new_pinned = !menu.is_pinned;
+                    let (new_pinned, new_muted, new_archived) =
+                        (!menu.is_pinned, !menu.is_muted, !menu.is_archived);
 
                     match selected_item {
                         Some(ChatMenuItem::TogglePin) => {
-                            // Enforce max 10 pinned chats
-                            if new_pinned
-                                && self.state.chats.iter().filter(|c| c.is_pinned).count() >= 10
-                            {
-                                self.state.close_chat_menu();
+                            self.set_chat_pinned(&chat_id, new_pinned);
+                        }
+                        Some(ChatMenuItem::ToggleMute) => {
+                            self.set_chat_muted(&chat_id, new_muted);
+                        }
+                        Some(ChatMenuItem::ToggleArchive) => {
+                            self.archive_chat(&chat_id, new_archived).await;
+                        }
+                        Some(ChatMenuItem::ToggleAutoTranslate) => {
+                            let enabled = !menu.auto_translate;
+                            self.set_auto_translate(&chat_id, enabled);
+                        }
+                        Some(ChatMenuItem::ToggleAiExcluded) => {
+                            let excluded = !menu.ai_excluded;
+                            self.set_ai_excluded(&chat_id, excluded);
+                        }
+                        Some(ChatMenuItem::CyclePrompt) => {
+                            if menu.prompt_names.is_empty() {
+                                self.flash("No templates: add them under [ai.prompts.<name>]");
                                 return;
                             }
-                            let _ = self.db.set_chat_pinned(&chat_id, new_pinned);
-                            if let Some(chat) =
-                                self.state.chats.iter_mut().find(|c| c.id == chat_id)
-                            {
-                                chat.is_pinned = new_pinned;
+                            let persona = Persona {
+                                prompt: menu.next_prompt(),
+                                ..menu.persona.clone()
+                            };
+                            if let Err(e) = self.db.set_persona(&chat_id, &persona) {
+                                tracing::error!("Failed to save persona for {}: {}", chat_id, e);
                             }
-                            self.state
-                                .chats
-                                .sort_by_key(|c| std::cmp::Reverse(c.is_pinned));
-                            let new_idx = self
-                                .state
-                                .chats
-                                .iter()
-                                .position(|c| c.id == chat_id)
-                                .unwrap_or(0);
-                            self.state.chat_list_state.select(Some(new_idx));
+                            // Stay open so the templates can be stepped through
+                            if let Some(ref mut menu) = self.state.chat_menu_state {
+                                menu.persona = persona;
+                            }
+                            return;
                         }
-                        Some(ChatMenuItem::ToggleMute) => {
-                            let new_muted = !menu.is_muted;
-                            let _ = self.db.set_chat_muted(&chat_id, new_muted);
-                            if let Some(chat) =
-                                self.state.chats.iter_mut().find(|c| c.id == chat_id)
-                            {
-                                chat.is_muted = new_muted;
+                        Some(ChatMenuItem::EditStyle) => {
+                            let style = menu.persona.style.clone().unwrap_or_default();
+                            self.state.close_chat_menu();
+                            // Park the composer text like renaming does
+                            self.save_draft();
+                            let mut ta = TextArea::from(vec![style]);
+                            ta.move_cursor(tui_textarea::CursorMove::End);
+                            self.state.input = ta;
+                            self.state.input_mode = InputMode::StyleNotes;
+                            return;
+                        }
+                        Some(ChatMenuItem::ShareWithKind) => {
+                            let scope = kind_scope(&menu.kind);
+                            let kinds = kind_plural(&menu.kind);
+                            match self.db.set_persona(&scope, &menu.persona) {
+                                Ok(()) if menu.persona.is_empty() => {
+                                    self.flash(format!("Cleared the AI persona for {}", kinds))
+                                }
+                                Ok(()) => self.flash(format!(
+                                    "AI persona applies to {} without their own",
+                                    kinds
+                                )),
+                                Err(e) => self.flash(format!("Could not save persona: {}", e)),
                             }
                         }
                         None => {}
@@ -995,6 +1753,24 @@ impl App {
             Action::SearchClose => {
                 self.state.search_state = None;
                 self.state.input_mode = InputMode::Normal;
+                if let Some(indexer) = &mut self.indexer {
+                    indexer.cancel_query();
+                }
+            }
+            Action::SearchToggleMode => {
+                let Some(ref mut ss) = self.state.search_state else {
+                    return;
+                };
+                if ss.mode == SearchMode::Chats && self.indexer.is_none() {
+                    self.flash("Semantic search needs [ai.embeddings] enabled = true");
+                    return;
+                }
+                ss.mode = match ss.mode {
+                    SearchMode::Chats => SearchMode::Semantic,
+                    SearchMode::Semantic => SearchMode::Chats,
+                };
+                ss.selected = 0;
+                self.refresh_search();
             }
             Action::SearchInput(key) => {
                 use crossterm::event::KeyCode;
@@ -1008,39 +1784,68 @@ impl App {
                         }
                         _ => {}
                     }
-                    ss.results = top_fuzzy_matches(&ss.query, &self.state.chats, 5);
-                    ss.selected = ss.selected.min(ss.results.len().saturating_sub(1));
                 }
+                self.refresh_search();
             }
             Action::SearchNext => {
                 if let Some(ref mut ss) = self.state.search_state {
-                    if !ss.results.is_empty() {
-                        ss.selected = (ss.selected + 1) % ss.results.len();
+                    let count = ss.result_count();
+                    if count > 0 {
+                        ss.selected = (ss.selected + 1) % count;
                     }
                 }
             }
             Action::SearchPrev => {
                 if let Some(ref mut ss) = self.state.search_state {
-                    if !ss.results.is_empty() {
-                        ss.selected = ss.selected.checked_sub(1).unwrap_or(ss.results.len() - 1);
+                    let count = ss.result_count();
+                    if count > 0 {
+                        ss.selected = ss.selected.checked_sub(1).unwrap_or(count - 1);
                     }
                 }
             }
-            Action::SearchConfirm => {
-                let chat_idx = self
+            Action::SearchConfirm
+                if self
                     .state
                     .search_state
                     .as_ref()
-                    .and_then(|ss| ss.results.get(ss.selected).copied());
-                if let Some(idx) = chat_idx {
+                    .is_some_and(|ss| ss.mode == SearchMode::Semantic) =>
+            {
+                let hit = self
+                    .state
+                    .search_state
+                    .as_ref()
+                    .and_then(|ss| ss.hits.get(ss.selected).cloned());
+                let Some(hit) = hit else {
+                    return;
+                };
+                let Some(idx) = self.state.chats.iter().position(|c| c.id == hit.chat_id) else {
+                    self.flash("That chat is no longer in the list");
+                    return;
+                };
+                self.state.search_state = None;
+                self.state.input_mode = InputMode::Normal;
+                self.select_chat(idx).await;
+                // Highlight the message when it is among those loaded
+                if let Some(pos) = self
+                    .state
+                    .messages
+                    .iter()
+                    .position(|m| m.id == hit.message_id)
+                {
+                    self.state.enter_message_select();
+                    self.state.selected_message_idx = Some(pos);
+                }
+            }
+            Action::SearchConfirm => {
+                let chat_idx = self
+                    .state
+                    .search_state
+                    .as_ref()
+                    .and_then(|ss| ss.results.get(ss.selected).copied());
+                if let Some(idx) = chat_idx {
                     self.state.search_state = None;
                     self.state.enter_editing();
-                    self.state.chat_list_state.select(Some(idx));
-                    self.load_selected_chat_messages();
-                    self.capture_new_message_count();
-                    self.clear_selected_unread();
-                    self.send_read_receipts().await;
-                    self.refresh_title();
+                    self.select_chat(idx).await;
                 }
             }
             Action::AiSuggestAccept => {
@@ -1050,27 +1855,47 @@ impl App {
                     }
                 }
             }
+            Action::InsertSmartReply(i) => {
+                if let Some(reply) = self.state.smart_replies.get(i).cloned() {
+                    self.clear_smart_replies();
+                    if self.state.input_mode != InputMode::Editing {
+                        self.state.enter_editing();
+                    }
+                    self.state.input.insert_str(&reply);
+                    self.state.ai_suggestion = None;
+                }
+            }
+            Action::TabComplete => {
+                let input = self.state.input.lines().join("\n");
+                if let Some((completed, matches)) = slash_commands::complete(&input) {
+                    if let [only] = matches.as_slice() {
+                        self.flash(format!("{} — {}", only.usage, only.help));
+                    } else {
+                        self.flash(
+                            matches
+                                .iter()
+                                .map(|c| format!("/{}", c.name))
+                                .collect::<Vec<_>>()
+                                .join("  "),
+                        );
+                    }
+                    self.state.set_input(&completed);
+                } else {
+                    Box::pin(self.handle_action(Action::AiSuggestAccept)).await;
+                }
+            }
             Action::AiSuggestRequest => {
                 self.last_keystroke = None;
+                if self.ai_worker.is_some() && !self.selected_chat_ai_allowed() {
+                    self.flash(AI_EXCLUDED);
+                    return;
+                }
                 if self.state.input_mode == InputMode::Editing {
                     let partial = self.state.input.lines().join("\n");
                     if !partial.is_empty() {
+                        let prompts = self.selected_chat_prompts();
                         if let Some(worker) = self.ai_worker.as_mut() {
-                            let messages: Vec<RawMessage> = self
-                                .state
-                                .messages
-                                .iter()
-                                .filter_map(|m| {
-                                    let text = match &m.content {
-                                        crate::core::types::MessageContent::Text(t) => t.clone(),
-                                        _ => return None,
-                                    };
-                                    Some(RawMessage {
-                                        is_outgoing: m.is_outgoing,
-                                        text,
-                                    })
-                                })
-                                .collect();
+                            let messages = raw_messages(&self.state.messages);
                             let summary = self.state.selected_chat_id().and_then(|id| {
                                 self.db
                                     .get_preference(&format!("ai_summary:{}", id))
@@ -1093,6 +1918,7 @@ impl App {
                             worker.request(AiRequest {
                                 partial_input: partial,
                                 messages,
+                                prompts,
                                 summary,
                             });
                         }
@@ -1128,6 +1954,72 @@ impl App {
             Action::MessageSelectExit => {
                 self.state.exit_message_select();
             }
+            Action::TranslateMessage => {
+                let Some(msg) = self
+                    .state
+                    .selected_message_idx
+                    .and_then(|idx| self.state.messages.get(idx))
+                else {
+                    return;
+                };
+                if self.state.translations.contains_key(&msg.id) {
+                    self.flash("Already translated");
+                } else if !self.ai_allowed(&msg.chat_id) {
+                    self.flash(AI_EXCLUDED);
+                } else if let Some(text) = message_text(msg) {
+                    match self.ai_worker.as_mut() {
+                        Some(worker) => {
+                            worker.translate_message(
+                                msg.chat_id.clone(),
+                                msg.id.clone(),
+                                text.to_string(),
+                                self.config.ai.translate_to.clone(),
+                            );
+                            self.flash("Translating…");
+                        }
+                        None => self.flash("Translation needs AI: set [ai] enabled = true"),
+                    }
+                } else {
+                    self.flash("Only text messages can be translated");
+                }
+            }
+            Action::TranslateDraft => {
+                let draft = self.state.input.lines().join("\n");
+                let Some(chat_id) = self.state.selected_chat_id().map(|s| s.to_string()) else {
+                    return;
+                };
+                if draft.trim().is_empty() {
+                    return;
+                }
+                if !self.ai_allowed(&chat_id) {
+                    self.flash(AI_EXCLUDED);
+                    return;
+                }
+                // Their latest messages tell the model which language to use
+                let theirs: Vec<String> = self
+                    .state
+                    .messages
+                    .iter()
+                    .rev()
+                    .filter(|m| !m.is_outgoing)
+                    .filter_map(|m| message_text(m).map(str::to_string))
+                    .take(10)
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev()
+                    .collect();
+                if theirs.is_empty() {
+                    self.flash("No messages from them to match the language of");
+                    return;
+                }
+                match self.ai_worker.as_mut() {
+                    Some(worker) => {
+                        worker.translate_draft(chat_id, draft, theirs);
+                        self.flash("Translating draft…");
+                    }
+                    None => self.flash("Translation needs AI: set [ai] enabled = true"),
+                }
+            }
             Action::OpenMedia => {
                 if let Some(idx) = self.state.selected_message_idx {
                     if let Some(msg) = self.state.messages.get(idx) {
@@ -1209,6 +2101,9 @@ impl App {
                 self.state.exit_message_select();
             }
             // Schedule actions
+            Action::ScheduleMessage if self.state.editing_scheduled.is_some() => {
+                self.flash("Enter saves the edit — change the time with t in the schedule list");
+            }
             Action::ScheduleMessage => {
                 let input = self.state.take_input();
                 if !input.is_empty() {
@@ -1224,6 +2119,8 @@ impl App {
                             Some(SchedulePromptState::new(input, chat_id, platform));
                         self.state.input_mode = InputMode::SchedulePrompt;
                     }
+                } else {
+                    self.flash("Could not schedule — type the message first");
                 }
             }
             Action::ScheduleInput(key) => {
@@ -1241,53 +2138,56 @@ impl App {
             }
             Action::ScheduleConfirm => {
                 if let Some(sp) = self.state.schedule_prompt_state.take() {
-                    if let Some(send_at) = parse_schedule_time(&sp.query) {
-                        let msg = ScheduledMessage {
-                            id: uuid::Uuid::new_v4().to_string(),
-                            chat_id: sp.chat_id,
-                            platform: sp.platform,
-                            content: MessageContent::Text(sp.message_text),
-                            send_at,
-                            status: "pending".to_string(),
-                            created_at: chrono::Utc::now(),
-                        };
-                        if let Err(e) = self.db.insert_scheduled_message(&msg) {
-                            tracing::error!("Failed to schedule message: {}", e);
-                        } else {
-                            self.state.schedule_status =
-                                Some(format!("Scheduled for {}", format_local_time(&send_at)));
-                            self.schedule_status_ticks = 0;
-                            tracing::info!("Scheduled message for {}", format_local_time(&send_at));
+                    match sp.target {
+                        ScheduleTarget::NewMessage => {
+                            if let Some(time) = parse_schedule_time(&sp.query) {
+                                self.schedule_message(
+                                    sp.chat_id,
+                                    sp.platform,
+                                    sp.message_text,
+                                    time,
+                                );
+                            } else {
+                                self.flash(
+                                    "Could not parse time — try 'tomorrow 9am', 'Mar 15 14:30' or 'every monday 9am'",
+                                );
+                            }
+                            self.state.input_mode = InputMode::Editing;
+                        }
+                        ScheduleTarget::Recurrence(id) => {
+                            self.change_recurrence(&id, &sp.query);
+                            self.state.input_mode = InputMode::ScheduleList;
+                        }
+                        ScheduleTarget::SendTime(id) => {
+                            match parse_schedule_time(&sp.query) {
+                                Some(time) => self.change_send_time(&id, time),
+                                None => self.flash(
+                                    "Could not parse time — try 'tomorrow 9am' or 'Mar 15 14:30'",
+                                ),
+                            }
+                            self.state.input_mode = InputMode::ScheduleList;
                         }
-                    } else {
-                        self.state.schedule_status = Some(
-                            "Could not parse time — try 'tomorrow 9am' or 'Mar 15 14:30'"
-                                .to_string(),
-                        );
-                        self.schedule_status_ticks = 0;
-                    }
-                    self.state.input_mode = InputMode::Editing;
-                }
-            }
-            Action::ScheduleCancel => {
-                if let Some(sp) = self.state.schedule_prompt_state.take() {
-                    // Put the message text back into the input
-                    self.state.input = TextArea::default();
-                    for ch in sp.message_text.chars() {
-                        self.state.input.insert_char(ch);
                     }
                 }
-                self.state.input_mode = InputMode::Editing;
             }
-            Action::OpenScheduleList => match self.db.get_pending_scheduled_messages() {
-                Ok(messages) => {
-                    self.state.schedule_list_state = Some(ScheduleListState::new(messages));
+            Action::ScheduleCancel => match self.state.schedule_prompt_state.take() {
+                Some(sp) if sp.target != ScheduleTarget::NewMessage => {
                     self.state.input_mode = InputMode::ScheduleList;
                 }
-                Err(e) => {
-                    tracing::error!("Failed to load scheduled messages: {}", e);
+                sp => {
+                    if let Some(sp) = sp {
+                        // Put the message text back into the input
+                        self.state.input = TextArea::default();
+                        for ch in sp.message_text.chars() {
+                            self.state.input.insert_char(ch);
+                        }
+                    }
+                    self.state.input_mode = InputMode::Editing;
                 }
             },
+            Action::OpenScheduleList => {
+                self.open_schedule_list(None);
+            }
             Action::ScheduleListNext => {
                 if let Some(ref mut sl) = self.state.schedule_list_state {
                     sl.select_next();
@@ -1307,8 +2207,8 @@ impl App {
                                 if sl.selected > 0 && sl.selected >= sl.messages.len() {
                                     sl.selected = sl.messages.len().saturating_sub(1);
                                 }
-                                self.state.schedule_status = Some("Schedule cancelled".to_string());
-                                self.schedule_status_ticks = 0;
+                                self.state.flash_status = Some("Schedule cancelled".to_string());
+                                self.flash_status_ticks = 0;
                             }
                             Err(e) => {
                                 tracing::error!("Failed to cancel scheduled message: {}", e);
@@ -1320,11 +2220,268 @@ impl App {
                         self.state.input_mode = InputMode::Normal;
                     }
                 }
+                self.reschedule_timer();
+            }
+            Action::ScheduleListRepeat => {
+                let selected = self
+                    .state
+                    .schedule_list_state
+                    .as_ref()
+                    .and_then(|sl| sl.messages.get(sl.selected));
+                if let Some(msg) = selected {
+                    self.state.schedule_prompt_state =
+                        Some(SchedulePromptState::for_recurrence(msg));
+                    self.state.input_mode = InputMode::SchedulePrompt;
+                }
+            }
+            Action::ScheduleListEdit => {
+                let selected = self
+                    .state
+                    .schedule_list_state
+                    .as_ref()
+                    .and_then(|sl| sl.messages.get(sl.selected))
+                    .cloned();
+                if let Some(msg) = selected {
+                    match self.state.chats.iter().position(|c| c.id == msg.chat_id) {
+                        Some(idx) => {
+                            self.state.schedule_list_state = None;
+                            if self.state.chat_list_state.selected() != Some(idx) {
+                                self.select_chat(idx).await;
+                            }
+                            // Park the composer text so the edit doesn't clobber it
+                            self.save_draft();
+                            self.state.set_input(msg.content.as_text());
+                            self.state.editing_scheduled = Some(msg.id);
+                            self.state.enter_editing();
+                        }
+                        None => self.flash("Could not edit — the chat is not loaded"),
+                    }
+                }
+            }
+            Action::ScheduleListEditTime => {
+                let selected = self
+                    .state
+                    .schedule_list_state
+                    .as_ref()
+                    .and_then(|sl| sl.messages.get(sl.selected));
+                if let Some(msg) = selected {
+                    self.state.schedule_prompt_state =
+                        Some(SchedulePromptState::for_send_time(msg));
+                    self.state.input_mode = InputMode::SchedulePrompt;
+                }
+            }
+            Action::ScheduleListSendNow => {
+                let selected = self
+                    .state
+                    .schedule_list_state
+                    .as_ref()
+                    .and_then(|sl| sl.messages.get(sl.selected))
+                    .map(|msg| msg.id.clone());
+                if let Some(id) = selected {
+                    match self.db.requeue_scheduled_message(&id, chrono::Utc::now()) {
+                        Ok(()) => {
+                            self.flash("Sending…");
+                            self.reschedule_timer();
+                        }
+                        Err(e) => tracing::error!("Failed to requeue scheduled message: {}", e),
+                    }
+                }
             }
             Action::ScheduleListClose => {
                 self.state.schedule_list_state = None;
                 self.state.input_mode = InputMode::Normal;
             }
+            Action::TogglePin => {
+                if let Some(chat) = self.state.selected_chat() {
+                    let (chat_id, pinned) = (chat.id.clone(), chat.is_pinned);
+                    if self.set_chat_pinned(&chat_id, !pinned) {
+                        self.flash(if pinned {
+                            "Chat unpinned"
+                        } else {
+                            "Chat pinned"
+                        });
+                    } else {
+                        self.flash("Could not pin chat — at most 10 chats can be pinned");
+                    }
+                }
+            }
+            Action::ToggleMute => {
+                if let Some(chat) = self.state.selected_chat() {
+                    let (chat_id, muted) = (chat.id.clone(), chat.is_muted);
+                    self.set_chat_muted(&chat_id, !muted);
+                    self.flash(if muted { "Chat unmuted" } else { "Chat muted" });
+                }
+            }
+            Action::ToggleArchive => {
+                if let Some(chat) = self.state.selected_chat() {
+                    let (chat_id, archived) = (chat.id.clone(), chat.is_archived);
+                    self.archive_chat(&chat_id, !archived).await;
+                    self.flash(if archived {
+                        "Chat unarchived"
+                    } else {
+                        "Chat archived"
+                    });
+                }
+            }
+            Action::ToggleActivityGraph => {
+                let show = !self.state.show_activity_graph;
+                if let Err(e) = self
+                    .db
+                    .set_preference("show_activity_graph", if show { "true" } else { "false" })
+                {
+                    tracing::error!("Failed to persist show_activity_graph preference: {}", e);
+                }
+                self.state.show_activity_graph = show;
+            }
+            Action::ToggleAway => {
+                let away = !self.config.away.enabled;
+                self.config.away.enabled = away;
+                self.state.away = away;
+                if let Err(e) = self.config.save(&self.config_path) {
+                    tracing::error!("Failed to save config: {}", e);
+                }
+                self.flash(if away {
+                    "Away auto-reply on"
+                } else {
+                    "Away auto-reply off"
+                });
+            }
+            Action::ExportChat => {
+                if let Some(chat) = self.state.selected_chat() {
+                    let name = chat.display_name.as_ref().unwrap_or(&chat.name).clone();
+                    let chat_id = chat.id.clone();
+                    let dir = Path::new(&self.config.general.data_dir).join("exports");
+                    match self.db.export_chat(&chat_id, &name, &dir) {
+                        Ok(path) => self.flash(format!("Exported to {}", path.display())),
+                        Err(e) => {
+                            tracing::error!("Failed to export chat {}: {}", chat_id, e);
+                            self.flash(format!("Could not export chat: {}", e));
+                        }
+                    }
+                }
+            }
+            Action::OpenPalette => {
+                self.state.palette_state = Some(PaletteState::new(palette_entries(&self.keymap)));
+                self.state.input_mode = InputMode::Palette;
+            }
+            Action::PaletteInput(key) => {
+                if let Some(ref mut palette) = self.state.palette_state {
+                    match key.code {
+                        KeyCode::Backspace => {
+                            palette.query.pop();
+                        }
+                        KeyCode::Char(c) => {
+                            palette.query.push(c);
+                        }
+                        _ => {}
+                    }
+                    palette.refilter();
+                }
+            }
+            Action::PaletteNext => {
+                if let Some(ref mut palette) = self.state.palette_state {
+                    if !palette.results.is_empty() {
+                        palette.selected = (palette.selected + 1) % palette.results.len();
+                    }
+                }
+            }
+            Action::PalettePrev => {
+                if let Some(ref mut palette) = self.state.palette_state {
+                    if !palette.results.is_empty() {
+                        palette.selected = palette
+                            .selected
+                            .checked_sub(1)
+                            .unwrap_or(palette.results.len() - 1);
+                    }
+                }
+            }
+            Action::PaletteConfirm => {
+                let action = self
+                    .state
+                    .palette_state
+                    .take()
+                    .and_then(|palette| palette.selected_action());
+                self.state.input_mode = InputMode::Normal;
+                if let Some(action) = action {
+                    Box::pin(self.handle_action(action)).await;
+                }
+            }
+            Action::PaletteClose => {
+                self.state.palette_state = None;
+                self.state.input_mode = InputMode::Normal;
+            }
+            Action::OpenHelp => {
+                self.state.help_state = Some(HelpState::new(self.keymap.describe()));
+                self.state.input_mode = InputMode::Help;
+            }
+            Action::HelpScrollDown => {
+                if let Some(ref mut help) = self.state.help_state {
+                    help.scroll_down();
+                }
+            }
+            Action::HelpScrollUp => {
+                if let Some(ref mut help) = self.state.help_state {
+                    help.scroll_up();
+                }
+            }
+            Action::CatchUp => self.open_catch_up(false),
+            Action::CatchUpAll => self.open_catch_up(true),
+            Action::CatchUpNext => {
+                if let Some(ref mut catch_up) = self.state.catch_up_state {
+                    catch_up.select_next();
+                }
+            }
+            Action::CatchUpPrev => {
+                if let Some(ref mut catch_up) = self.state.catch_up_state {
+                    catch_up.select_prev();
+                }
+            }
+            Action::CatchUpOpen => {
+                let chat_id = self
+                    .state
+                    .catch_up_state
+                    .as_ref()
+                    .and_then(|c| c.entries.get(c.selected))
+                    .map(|e| e.chat_id.clone());
+                Box::pin(self.handle_action(Action::CatchUpClose)).await;
+                if let Some(idx) =
+                    chat_id.and_then(|id| self.state.chats.iter().position(|c| c.id == id))
+                {
+                    if self.state.chat_list_state.selected() != Some(idx) {
+                        self.select_chat(idx).await;
+                    }
+                }
+            }
+            Action::OpenSentRequests => {
+                let requests = self.state.sent_requests.iter().rev().cloned().collect();
+                self.state.sent_requests_state = Some(SentRequestsState::new(requests));
+                self.state.input_mode = InputMode::SentRequests;
+            }
+            Action::SentRequestsScrollDown => {
+                if let Some(ref mut sent) = self.state.sent_requests_state {
+                    sent.scroll_down();
+                }
+            }
+            Action::SentRequestsScrollUp => {
+                if let Some(ref mut sent) = self.state.sent_requests_state {
+                    sent.scroll_up();
+                }
+            }
+            Action::SentRequestsClose => {
+                self.state.sent_requests_state = None;
+                self.state.input_mode = InputMode::Normal;
+            }
+            Action::CatchUpClose => {
+                if let Some(worker) = self.ai_worker.as_mut() {
+                    worker.cancel_catch_up();
+                }
+                self.state.catch_up_state = None;
+                self.state.input_mode = InputMode::Normal;
+            }
+            Action::HelpClose => {
+                self.state.help_state = None;
+                self.state.input_mode = InputMode::Normal;
+            }
             Action::TelegramAuthChar(c) => {
                 if let Some(ref mut auth) = self.state.telegram_auth_state {
                     if !c.is_control() {
@@ -1368,40 +2525,784 @@ impl App {
         }
     }
 
+    /// Send due scheduled messages. Failed sends are retried with backoff
+    /// until `schedule.max_attempts`; messages that came due while the app was
+    /// closed follow `schedule.missed_sends`.
     async fn check_scheduled_messages(&mut self) {
         let due = match self.db.get_due_scheduled_messages() {
             Ok(msgs) => msgs,
             Err(e) => {
                 tracing::error!("Failed to query scheduled messages: {}", e);
+                self.retry_schedule_later();
                 return;
             }
         };
 
-        let mut sent_count = 0;
+        let policy = MissedSendPolicy::from_setting(&self.config.schedule.missed_sends);
+        let missed_before = self.started_at - chrono::Duration::minutes(1);
+        let (mut sent_count, mut missed_count, mut failed_count) = (0, 0, 0);
         for msg in due {
+            let now = chrono::Utc::now();
+            // A requeued message has a retry time, so it is never treated as missed
+            let missed = msg.next_attempt_at.is_none() && msg.send_at < missed_before;
+            if missed && policy == MissedSendPolicy::Ask {
+                let _ = self.db.update_scheduled_status(&msg.id, "missed");
+                missed_count += 1;
+                continue;
+            }
+            if missed && policy == MissedSendPolicy::Skip {
+                let next = msg
+                    .recurrence
+                    .as_ref()
+                    .and_then(|r| r.next_after(msg.send_at, now));
+                let _ = match next {
+                    Some(next) => self.db.update_scheduled_send_at(&msg.id, next),
+                    None => self.db.update_scheduled_status(&msg.id, "skipped"),
+                };
+                tracing::info!("Skipped missed scheduled message {}", msg.id);
+                continue;
+            }
+
             let chat_id = msg.chat_id.clone();
-            let content = msg.content.clone();
-            if let Some(provider) = self.router.get_provider_mut(msg.platform) {
-                match provider.send_message(&chat_id, content).await {
-                    Ok(_) => {
-                        let _ = self.db.update_scheduled_status(&msg.id, "sent");
-                        sent_count += 1;
-                        tracing::info!("Sent scheduled message {} to {}", msg.id, chat_id);
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to send scheduled message {}: {}", msg.id, e);
-                        // Leave as pending — will retry next tick
+            let content = match &msg.content {
+                MessageContent::Text(text) => markup::compose_for_platform(text, msg.platform),
+                other => other.clone(),
+            };
+            let result = match self.router.get_provider_mut(msg.platform) {
+                Some(provider) => provider.send_message(&chat_id, content).await.map(|_| ()),
+                None => Err(anyhow::anyhow!("{} is not connected", msg.platform)),
+            };
+            match result {
+                Ok(()) => {
+                    // Recurring messages stay pending at their next occurrence;
+                    // an early "send now" skips the occurrence it replaced
+                    let next = msg
+                        .recurrence
+                        .as_ref()
+                        .and_then(|r| r.next_after(msg.send_at, now.max(msg.send_at)));
+                    let _ = match next {
+                        Some(next) => self.db.update_scheduled_send_at(&msg.id, next),
+                        None => self.db.update_scheduled_status(&msg.id, "sent"),
+                    };
+                    sent_count += 1;
+                    tracing::info!("Sent scheduled message {} to {}", msg.id, chat_id);
+                }
+                Err(e) => {
+                    let attempts = msg.attempts + 1;
+                    let retry_at = (attempts < self.config.schedule.max_attempts)
+                        .then(|| now + retry_delay(attempts));
+                    tracing::error!(
+                        "Failed to send scheduled message {} (attempt {}): {}",
+                        msg.id,
+                        attempts,
+                        e
+                    );
+                    let _ = self.db.record_scheduled_failure(
+                        &msg.id,
+                        attempts,
+                        retry_at,
+                        &e.to_string(),
+                    );
+                    if retry_at.is_none() {
+                        failed_count += 1;
                     }
                 }
             }
         }
-        if sent_count > 0 {
-            self.state.schedule_status = Some(format!(
+
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        if failed_count > 0 {
+            self.flash(format!(
+                "{} scheduled message{} failed — Ctrl+L to review",
+                failed_count,
+                plural(failed_count)
+            ));
+        } else if missed_count > 0 {
+            self.flash(format!(
+                "{} scheduled message{} missed while closed — Ctrl+L to review",
+                missed_count,
+                plural(missed_count)
+            ));
+        } else if sent_count > 0 {
+            self.flash(format!(
                 "Sent {} scheduled message{}",
                 sent_count,
-                if sent_count == 1 { "" } else { "s" }
+                plural(sent_count)
+            ));
+        }
+        self.refresh_schedule_list();
+        self.reschedule_timer();
+    }
+
+    /// Open the scheduled messages overlay, selecting message `select` if given.
+    fn open_schedule_list(&mut self, select: Option<&str>) {
+        match self.db.get_pending_scheduled_messages() {
+            Ok(messages) => {
+                let mut sl = ScheduleListState::new(messages);
+                if let Some(pos) = select.and_then(|id| sl.messages.iter().position(|m| m.id == id))
+                {
+                    sl.selected = pos;
+                }
+                self.state.schedule_list_state = Some(sl);
+                self.state.input_mode = InputMode::ScheduleList;
+            }
+            Err(e) => {
+                tracing::error!("Failed to load scheduled messages: {}", e);
+            }
+        }
+    }
+
+    /// End a composer edit of a scheduled message, saving `text` unless it is
+    /// `None` (cancelled), and return to the schedule list.
+    fn finish_scheduled_edit(&mut self, text: Option<String>) {
+        let Some(id) = self.state.editing_scheduled.take() else {
+            return;
+        };
+        if text.as_ref().is_some_and(|t| t.trim().is_empty()) {
+            self.state.editing_scheduled = Some(id);
+            self.flash("A scheduled message can't be empty — Esc cancels the edit");
+            return;
+        }
+        self.state.take_input();
+        self.state.restore_draft();
+        if let Some(text) = text {
+            let msg = self
+                .db
+                .get_pending_scheduled_messages()
+                .ok()
+                .and_then(|msgs| msgs.into_iter().find(|m| m.id == id));
+            if let Some(mut msg) = msg {
+                msg.content = MessageContent::Text(text);
+                match self.db.update_scheduled_message(&msg) {
+                    Ok(()) => self.flash("Scheduled message updated"),
+                    Err(e) => tracing::error!("Failed to update scheduled message {}: {}", id, e),
+                }
+            } else {
+                self.flash("Could not update — the message was already sent or cancelled");
+            }
+        }
+        self.open_schedule_list(Some(&id));
+    }
+
+    /// Move scheduled message `id` to `time`, keeping its repeat rule unless
+    /// `time` has one. Failed and missed messages go back to pending.
+    fn change_send_time(&mut self, id: &str, time: ScheduleTime) {
+        let Some(sl) = self.state.schedule_list_state.as_mut() else {
+            return;
+        };
+        let Some(msg) = sl.messages.iter_mut().find(|m| m.id == id) else {
+            return;
+        };
+        let mut updated = ScheduledMessage {
+            send_at: time.send_at,
+            status: "pending".to_string(),
+            attempts: 0,
+            next_attempt_at: None,
+            last_error: None,
+            ..msg.clone()
+        };
+        if time.recurrence.is_some() {
+            updated.recurrence = time.recurrence;
+        }
+        if let Err(e) = self.db.update_scheduled_message(&updated) {
+            tracing::error!("Failed to move scheduled message {}: {}", id, e);
+            return;
+        }
+        let when = format_local_time(&updated.send_at);
+        *msg = updated;
+        sl.messages.sort_by_key(|m| m.send_at);
+        sl.selected = sl.messages.iter().position(|m| m.id == id).unwrap_or(0);
+        self.flash(format!("Moved to {}", when));
+        self.reschedule_timer();
+    }
+
+    /// Point the scheduler timer at the earliest pending message.
+    fn reschedule_timer(&self) {
+        let Some(scheduler) = &self.scheduler else {
+            return;
+        };
+        match self.db.next_scheduled_due() {
+            Ok(next) => scheduler.set_next_due(next),
+            Err(e) => {
+                tracing::error!("Failed to query next scheduled message: {}", e);
+                self.retry_schedule_later();
+            }
+        }
+    }
+
+    /// Wake the scheduler again after a retry delay, so a failed database
+    /// read doesn't leave it waiting for a change that never comes.
+    fn retry_schedule_later(&self) {
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.set_next_due(Some(chrono::Utc::now() + retry_delay(1)));
+        }
+    }
+
+    /// Reload an open schedule list after sends changed what it shows.
+    fn refresh_schedule_list(&mut self) {
+        let Some(sl) = self.state.schedule_list_state.as_mut() else {
+            return;
+        };
+        if let Ok(messages) = self.db.get_pending_scheduled_messages() {
+            sl.selected = sl.selected.min(messages.len().saturating_sub(1));
+            sl.messages = messages;
+        }
+    }
+
+    /// Send the away replies queued by [`Self::handle_tick`].
+    async fn send_auto_replies(&mut self) {
+        for (platform, chat_id, text) in std::mem::take(&mut self.auto_replies) {
+            let content = markup::compose_for_platform(&text, platform);
+            let Some(provider) = self.router.get_provider_mut(platform) else {
+                continue;
+            };
+            match provider.send_message(&chat_id, content).await {
+                Ok(_) => tracing::info!("Sent away reply to {}", chat_id),
+                Err(e) => tracing::error!("Failed to send away reply to {}: {}", chat_id, e),
+            }
+        }
+    }
+
+    /// Send `content` to the selected chat through its provider.
+    async fn send_to_selected_chat(&mut self, content: MessageContent) -> anyhow::Result<()> {
+        let Some(chat_id) = self.state.selected_chat_id().map(|s| s.to_string()) else {
+            return Ok(());
+        };
+        self.clear_draft(&chat_id);
+        // Determine which provider owns this chat
+        let platform = self
+            .state
+            .chats
+            .iter()
+            .find(|c| c.id == chat_id)
+            .map(|c| c.platform)
+            .unwrap_or(Platform::Mock);
+
+        // Composer Markdown becomes the platform's native formatting
+        let content = match content {
+            MessageContent::Text(text) => markup::compose_for_platform(&text, platform),
+            other => other,
+        };
+        if let Some(provider) = self.router.get_provider_mut(platform) {
+            provider.send_message(&chat_id, content).await?;
+            tracing::debug!("Message sent via {:?}", platform);
+        }
+        Ok(())
+    }
+
+    async fn run_slash_command(&mut self, command: SlashCommand) {
+        match command {
+            SlashCommand::Me(text) => {
+                let content = MessageContent::Text(format!("_{}_", text));
+                if let Err(e) = self.send_to_selected_chat(content).await {
+                    tracing::error!("Failed to send message: {}", e);
+                }
+            }
+            SlashCommand::Schedule { time, text } => {
+                if let Some(chat) = self.state.selected_chat() {
+                    let (chat_id, platform) = (chat.id.clone(), chat.platform);
+                    self.schedule_message(chat_id, platform, text, time);
+                }
+            }
+            SlashCommand::Mute => Box::pin(self.handle_action(Action::ToggleMute)).await,
+            SlashCommand::Pin => Box::pin(self.handle_action(Action::TogglePin)).await,
+            SlashCommand::Archive => Box::pin(self.handle_action(Action::ToggleArchive)).await,
+            SlashCommand::Export => Box::pin(self.handle_action(Action::ExportChat)).await,
+            SlashCommand::Rename(None) => Box::pin(self.handle_action(Action::RenameChat)).await,
+            SlashCommand::Rename(Some(name)) => {
+                self.rename_selected_chat(&name);
+                self.flash(format!("Renamed to {}", name));
+            }
+            SlashCommand::Search(query) => {
+                let mut search = SearchState::new();
+                search.results = top_fuzzy_matches(&query, &self.state.chats, 5);
+                search.query = query;
+                self.state.search_state = Some(search);
+                self.state.input_mode = InputMode::Searching;
+            }
+            SlashCommand::Attach(path) => {
+                let Some(platform) = self.state.selected_chat().map(|c| c.platform) else {
+                    return;
+                };
+                let supported = self
+                    .router
+                    .get_provider(platform)
+                    .is_some_and(|p| p.supports_attachments());
+                if !path.is_file() {
+                    self.flash(format!(
+                        "Could not attach: {} is not a file",
+                        path.display()
+                    ));
+                } else if !supported {
+                    self.flash(format!(
+                        "Could not attach: sending files is not supported on {}",
+                        platform
+                    ));
+                } else {
+                    let filename = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let content = MessageContent::File {
+                        url: path.display().to_string(),
+                        filename,
+                    };
+                    if let Err(e) = self.send_to_selected_chat(content).await {
+                        tracing::error!("Failed to send file: {}", e);
+                        self.flash(format!("Could not attach: {}", e));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Store a message to be sent to `chat_id` at `send_at`.
+    fn schedule_message(
+        &mut self,
+        chat_id: String,
+        platform: Platform,
+        text: String,
+        time: ScheduleTime,
+    ) {
+        let msg = ScheduledMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            chat_id,
+            platform,
+            content: MessageContent::Text(text),
+            send_at: time.send_at,
+            status: "pending".to_string(),
+            created_at: chrono::Utc::now(),
+            recurrence: time.recurrence,
+            attempts: 0,
+            next_attempt_at: None,
+            last_error: None,
+        };
+        if let Err(e) = self.db.insert_scheduled_message(&msg) {
+            tracing::error!("Failed to schedule message: {}", e);
+        } else {
+            self.clear_draft(&msg.chat_id);
+            let when = format_local_time(&msg.send_at);
+            match &msg.recurrence {
+                Some(r) => self.flash(format!("Scheduled for {}, repeating {}", when, r)),
+                None => self.flash(format!("Scheduled for {}", when)),
+            }
+            tracing::info!("Scheduled message for {}", when);
+            self.reschedule_timer();
+        }
+    }
+
+    /// Apply a repeat rule typed in the schedule list to scheduled message `id`.
+    fn change_recurrence(&mut self, id: &str, input: &str) {
+        let Some(sl) = self.state.schedule_list_state.as_mut() else {
+            return;
+        };
+        let Some(msg) = sl.messages.iter_mut().find(|m| m.id == id) else {
+            return;
+        };
+        let Some(time) = parse_recurrence_change(input, msg.send_at) else {
+            self.flash("Could not parse repeat — try 'every monday', 'daily 9am' or 'once'");
+            return;
+        };
+        if let Err(e) =
+            self.db
+                .update_scheduled_recurrence(id, time.recurrence.as_ref(), time.send_at)
+        {
+            tracing::error!("Failed to update recurrence of {}: {}", id, e);
+            return;
+        }
+        msg.send_at = time.send_at;
+        msg.recurrence = time.recurrence;
+        let status = match &msg.recurrence {
+            Some(r) => format!("Repeats {}", r),
+            None => "Sends once".to_string(),
+        };
+        sl.messages.sort_by_key(|m| m.send_at);
+        self.flash(status);
+        self.reschedule_timer();
+    }
+
+    fn rename_selected_chat(&mut self, new_name: &str) {
+        if new_name.is_empty() {
+            return;
+        }
+        if let Some(idx) = self.state.chat_list_state.selected() {
+            if let Some(chat) = self.state.chats.get_mut(idx) {
+                chat.display_name = Some(new_name.to_string());
+                let _ = self.address_book.set_display_name(&chat.id, new_name);
+            }
+        }
+    }
+
+    /// Pin or unpin a chat, keeping pinned chats at the top of the list.
+    /// Returns false if pinning would exceed the limit of 10 pinned chats.
+    fn set_chat_pinned(&mut self, chat_id: &str, pinned: bool) -> bool {
+        if pinned && self.state.chats.iter().filter(|c| c.is_pinned).count() >= 10 {
+            return false;
+        }
+        let _ = self.db.set_chat_pinned(chat_id, pinned);
+        if let Some(chat) = self.state.chats.iter_mut().find(|c| c.id == chat_id) {
+            chat.is_pinned = pinned;
+        }
+        self.state
+            .chats
+            .sort_by_key(|c| std::cmp::Reverse(c.is_pinned));
+        let new_idx = self
+            .state
+            .chats
+            .iter()
+            .position(|c| c.id == chat_id)
+            .unwrap_or(0);
+        self.state.chat_list_state.select(Some(new_idx));
+        true
+    }
+
+    fn set_chat_archived(&mut self, chat_id: &str, archived: bool) {
+        let _ = self.db.set_chat_archived(chat_id, archived);
+        if let Some(chat) = self.state.chats.iter_mut().find(|c| c.id == chat_id) {
+            chat.is_archived = archived;
+        }
+    }
+
+    /// Archive or unarchive a chat here and in the platform's own archive.
+    async fn archive_chat(&mut self, chat_id: &str, archived: bool) {
+        self.set_chat_archived(chat_id, archived);
+        let platform = self
+            .state
+            .chats
+            .iter()
+            .find(|c| c.id == chat_id)
+            .map(|c| c.platform);
+        if let Some(provider) = platform.and_then(|p| self.router.get_provider(p)) {
+            if let Err(e) = provider.set_archived(chat_id, archived).await {
+                tracing::warn!("Failed to sync archive state for {}: {}", chat_id, e);
+            }
+        }
+    }
+
+    fn set_auto_translate(&mut self, chat_id: &str, enabled: bool) {
+        let key = format!("auto_translate:{}", chat_id);
+        if let Err(e) = self
+            .db
+            .set_preference(&key, if enabled { "true" } else { "false" })
+        {
+            tracing::error!("Failed to save auto-translate for {}: {}", chat_id, e);
+        }
+        if enabled {
+            self.state.auto_translate.insert(chat_id.to_string());
+            self.flash(format!(
+                "Translating new messages into {}",
+                self.config.ai.translate_to
             ));
-            self.schedule_status_ticks = 0;
+        } else {
+            self.state.auto_translate.remove(chat_id);
+            self.flash("Auto-translate off");
+        }
+    }
+
+    fn set_ai_excluded(&mut self, chat_id: &str, excluded: bool) {
+        let key = format!("ai_excluded:{}", chat_id);
+        if let Err(e) = self
+            .db
+            .set_preference(&key, if excluded { "true" } else { "false" })
+        {
+            tracing::error!("Failed to save AI exclusion for {}: {}", chat_id, e);
+        }
+        if excluded {
+            self.state.ai_excluded.insert(chat_id.to_string());
+            if self.state.selected_chat_id() == Some(chat_id) {
+                self.clear_smart_replies();
+                self.state.ai_suggestion = None;
+                self.last_keystroke = None;
+            }
+            self.flash("This chat will not be sent to AI");
+        } else {
+            self.state.ai_excluded.remove(chat_id);
+            self.flash("This chat may be sent to AI");
+            self.index_messages();
+        }
+    }
+
+    fn set_chat_muted(&mut self, chat_id: &str, muted: bool) {
+        let _ = self.db.set_chat_muted(chat_id, muted);
+        if let Some(chat) = self.state.chats.iter_mut().find(|c| c.id == chat_id) {
+            chat.is_muted = muted;
+        }
+    }
+
+    /// Show a transient message in the status bar for a few ticks.
+    /// Messages starting with "Could not" are shown as errors.
+    fn flash(&mut self, msg: impl Into<String>) {
+        self.state.flash_status = Some(msg.into());
+        self.flash_status_ticks = 0;
+    }
+
+    /// Save the composer text as the selected chat's draft and clear the composer.
+    fn save_draft(&mut self) {
+        // The text of a scheduled message being edited is not a draft; leaving
+        // the chat cancels the edit
+        if self.state.editing_scheduled.take().is_some() {
+            self.state.take_input();
+            return;
+        }
+        if let Some((chat_id, text)) = self.state.stash_draft() {
+            if let Err(e) = self.db.set_draft(&chat_id, &text) {
+                tracing::error!("Failed to save draft for {}: {}", chat_id, e);
+            }
+        }
+    }
+
+    /// Drop any saved draft for `chat_id` (e.g. once its text has been sent).
+    fn clear_draft(&mut self, chat_id: &str) {
+        if self.state.drafts.remove(chat_id).is_some() {
+            if let Err(e) = self.db.set_draft(chat_id, "") {
+                tracing::error!("Failed to clear draft for {}: {}", chat_id, e);
+            }
+        }
+    }
+
+    /// Update the search overlay's results for its query: chat names are
+    /// matched at once, a semantic query is embedded once typing pauses.
+    fn refresh_search(&mut self) {
+        let Some(ref mut ss) = self.state.search_state else {
+            return;
+        };
+        match ss.mode {
+            SearchMode::Chats => {
+                ss.results = top_fuzzy_matches(&ss.query, &self.state.chats, 5);
+                ss.selected = ss.selected.min(ss.results.len().saturating_sub(1));
+            }
+            SearchMode::Semantic => {
+                let Some(indexer) = self.indexer.as_mut() else {
+                    return;
+                };
+                if ss.query.trim().is_empty() {
+                    indexer.cancel_query();
+                    ss.hits.clear();
+                    ss.searching = false;
+                } else {
+                    indexer.embed_query(ss.query.clone());
+                    ss.searching = true;
+                }
+            }
+        }
+    }
+
+    /// Hand the indexer the next batch of messages without an embedding.
+    /// Messages with no text are marked done right away.
+    fn index_messages(&mut self) {
+        let Some(ref indexer) = self.indexer else {
+            return;
+        };
+        if self.indexing {
+            return;
+        }
+        let config = &self.config.ai.embeddings;
+        loop {
+            let pending = match self.db.unembedded_messages(
+                &config.model,
+                config.batch_size.max(1),
+                &self.state.ai_excluded,
+            ) {
+                Ok(pending) => pending,
+                Err(e) => {
+                    tracing::error!("Failed to load messages to index: {}", e);
+                    return;
+                }
+            };
+            if pending.is_empty() {
+                return;
+            }
+            let (batch, skipped): (Vec<_>, Vec<_>) = pending
+                .into_iter()
+                .partition(|(_, content)| embeddable_text(content).is_some());
+            if !skipped.is_empty() {
+                let empty: Vec<(String, Vec<f32>)> = skipped
+                    .into_iter()
+                    .map(|(id, _)| (id, Vec::new()))
+                    .collect();
+                if let Err(e) = self.db.save_embeddings(&config.model, &empty) {
+                    tracing::error!("Failed to save embeddings: {}", e);
+                    return;
+                }
+            }
+            if !batch.is_empty() {
+                let batch = batch
+                    .into_iter()
+                    .filter_map(|(id, content)| Some((id, embeddable_text(&content)?.to_string())))
+                    .collect();
+                indexer.index(batch);
+                self.indexing = true;
+                return;
+            }
+        }
+    }
+
+    /// Prompts for a chat from its persona, else its kind's persona, and the
+    /// `[ai.prompts]` template that names.
+    fn chat_prompts(&self, chat_id: &str) -> ChatPrompts {
+        let kind = self
+            .state
+            .chats
+            .iter()
+            .find(|c| c.id == chat_id)
+            .map(|c| c.kind.clone())
+            .unwrap_or_default();
+        let persona = self
+            .db
+            .get_persona(chat_id)
+            .unwrap_or_default()
+            .or(self.db.get_persona(&kind_scope(&kind)).unwrap_or_default());
+        let name = persona.prompt.as_deref().unwrap_or("default");
+        let template = self.config.ai.prompts.get(name);
+        if template.is_none() && persona.prompt.is_some() {
+            tracing::warn!(
+                "No [ai.prompts.{}] template; using the built-in prompts",
+                name
+            );
+        }
+        ChatPrompts::new(template, persona.style.as_deref())
+    }
+
+    /// Whether a chat may be sent to the AI provider or the embedding indexer.
+    fn ai_allowed(&self, chat_id: &str) -> bool {
+        !self.state.ai_excluded.contains(chat_id)
+    }
+
+    fn selected_chat_ai_allowed(&self) -> bool {
+        self.state
+            .selected_chat_id()
+            .is_none_or(|id| self.ai_allowed(id))
+    }
+
+    fn selected_chat_prompts(&self) -> ChatPrompts {
+        self.state
+            .selected_chat_id()
+            .map(|id| self.chat_prompts(id))
+            .unwrap_or_default()
+    }
+
+    /// Context for suggesting replies in the open chat: its text messages
+    /// and stored summary. `None` when AI is off or nothing is open.
+    fn smart_reply_request(&self) -> Option<AiRequest> {
+        self.ai_worker.as_ref()?;
+        let chat_id = self
+            .state
+            .selected_chat_id()
+            .filter(|id| self.ai_allowed(id))?;
+        let messages = raw_messages(&self.state.messages);
+        let summary = self
+            .db
+            .get_preference(&format!("ai_summary:{}", chat_id))
+            .ok()
+            .flatten();
+        Some(AiRequest {
+            partial_input: String::new(),
+            messages,
+            prompts: self.chat_prompts(chat_id),
+            summary,
+        })
+    }
+
+    /// Open the catch-me-up overlay for the selected chat, or every chat with
+    /// unread messages, and summarise what is not already cached.
+    fn open_catch_up(&mut self, all: bool) {
+        if self.ai_worker.is_none() {
+            self.flash("Catch me up needs AI: set [ai] enabled = true");
+            return;
+        }
+        let selected = self.state.chat_list_state.selected();
+        // The open chat's unread count was cleared when it was opened
+        let unread_of = |i: usize, chat: &UnifiedChat| {
+            if Some(i) == selected {
+                self.state.new_message_count.max(chat.unread_count as usize)
+            } else {
+                chat.unread_count as usize
+            }
+        };
+        let chats: Vec<(String, String, usize)> = self
+            .state
+            .chats
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| {
+                if all {
+                    !c.is_muted && self.ai_allowed(&c.id)
+                } else {
+                    Some(*i) == selected
+                }
+            })
+            .map(|(i, c)| {
+                let name = c.display_name.clone().unwrap_or_else(|| c.name.clone());
+                (c.id.clone(), name, unread_of(i, c))
+            })
+            .filter(|(_, _, unread)| *unread > 0)
+            .collect();
+        if chats.is_empty() {
+            self.flash("Nothing unread");
+            return;
+        }
+        if chats
+            .iter()
+            .any(|(chat_id, _, _)| !self.ai_allowed(chat_id))
+        {
+            self.flash(AI_EXCLUDED);
+            return;
+        }
+
+        let mut entries = Vec::new();
+        let mut requests = Vec::new();
+        for (chat_id, chat_name, unread) in chats {
+            let limit = unread.min(MAX_CATCH_UP_MESSAGES) as u32;
+            let messages = match self.db.get_recent_messages_for_chat(&chat_id, limit) {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::error!("Failed to load unread messages of {}: {}", chat_id, e);
+                    continue;
+                }
+            };
+            let Some(last) = messages.last() else {
+                continue;
+            };
+            let key = (chat_id.clone(), last.id.clone());
+            let bullets = self.catch_up_cache.get(&key).cloned().map(Ok);
+            if bullets.is_none() {
+                requests.push(CatchUpChat {
+                    chat_id: chat_id.clone(),
+                    chat_name: chat_name.clone(),
+                    last_message_id: key.1,
+                    lines: messages
+                        .iter()
+                        .map(|m| {
+                            let sender = if m.is_outgoing { "You" } else { &m.sender };
+                            format!("{}: {}", sender, m.content.as_text())
+                        })
+                        .collect(),
+                });
+            }
+            entries.push(CatchUpEntry {
+                chat_id,
+                chat_name,
+                unread,
+                bullets,
+            });
+        }
+        if entries.is_empty() {
+            self.flash("Nothing unread");
+            return;
+        }
+
+        if let Some(worker) = self.ai_worker.as_mut() {
+            worker.catch_up(requests);
+        }
+        self.state.catch_up_state = Some(CatchUpState::new(entries));
+        self.state.input_mode = InputMode::CatchUp;
+    }
+
+    /// Hide the reply chips and drop any request for new ones.
+    fn clear_smart_replies(&mut self) {
+        self.state.smart_replies.clear();
+        if let Some(worker) = self.ai_worker.as_mut() {
+            worker.cancel_smart_replies();
         }
     }
 
@@ -1416,6 +3317,16 @@ impl App {
                     tracing::error!("Failed to load messages: {}", e);
                 }
             }
+            match self
+                .db
+                .get_translations_for_chat(&chat_id, &self.config.ai.translate_to)
+            {
+                Ok(translations) => self.state.translations = translations,
+                Err(e) => {
+                    tracing::error!("Failed to load translations: {}", e);
+                    self.state.translations.clear();
+                }
+            }
         }
     }
 
@@ -1512,38 +3423,46 @@ impl App {
     }
 }
 
+/// The text of a text message; `None` for media and other content.
+fn message_text(msg: &UnifiedMessage) -> Option<&str> {
+    match &msg.content {
+        MessageContent::Text(text) | MessageContent::Formatted { text, .. } => Some(text),
+        _ => None,
+    }
+}
+
+/// The text messages of a chat, as context for the AI.
+fn raw_messages(messages: &[UnifiedMessage]) -> Vec<RawMessage> {
+    messages
+        .iter()
+        .filter_map(|m| {
+            message_text(m).map(|text| RawMessage {
+                is_outgoing: m.is_outgoing,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
 /// Copy text to the system clipboard using the OSC 52 terminal escape sequence.
 /// This works in most modern terminals (kitty, iTerm2, WezTerm, tmux with set-clipboard on, etc.).
 fn copy_to_clipboard(text: &str) {
+    use base64::Engine;
     use std::io::Write;
-    let encoded = base64_encode(text.as_bytes());
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text.as_bytes());
     // OSC 52 ; c ; <base64> ST
     let osc52 = format!("\x1b]52;c;{}\x07", encoded);
     let _ = std::io::stdout().write_all(osc52.as_bytes());
     let _ = std::io::stdout().flush();
 }
 
-/// Minimal base64 encoder (no external crate needed).
-fn base64_encode(input: &[u8]) -> String {
-    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
-    for chunk in input.chunks(3) {
-        let b0 = chunk[0] as u32;
-        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
-        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
-        let n = (b0 << 16) | (b1 << 8) | b2;
-        out.push(ALPHABET[((n >> 18) & 0x3f) as usize] as char);
-        out.push(ALPHABET[((n >> 12) & 0x3f) as usize] as char);
-        if chunk.len() > 1 {
-            out.push(ALPHABET[((n >> 6) & 0x3f) as usize] as char);
-        } else {
-            out.push('=');
-        }
-        if chunk.len() > 2 {
-            out.push(ALPHABET[(n & 0x3f) as usize] as char);
-        } else {
-            out.push('=');
+/// Load the configured theme, falling back to the default dark theme.
+fn load_theme(config: &AppConfig) -> Theme {
+    match Theme::load(&config.tui.theme, Path::new(&config.general.data_dir)) {
+        Ok(theme) => theme,
+        Err(e) => {
+            tracing::warn!("{} — using the default theme", e);
+            Theme::default()
         }
     }
-    out
 }
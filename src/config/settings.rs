@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::ai::redact::Redactor;
+use crate::core::away::AwayRule;
+use crate::core::folders::{self, ChatFolder, FolderRules};
 use crate::core::Result;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +21,37 @@ pub struct AppConfig {
     pub telegram: TelegramConfig,
     #[serde(default)]
     pub ai: AiConfig,
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+    #[serde(default)]
+    pub away: AwayConfig,
+    /// Per-mode key overrides: `[keybindings.normal]` with `"ctrl+l" = "open_schedule_list"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keybindings: BTreeMap<String, BTreeMap<String, String>>,
+    /// Chat list tabs (`[[folders]]`), shown after the built-in ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub folders: Vec<FolderConfig>,
+}
+
+/// A user-defined chat folder. Every rule that is set must match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FolderConfig {
+    pub name: String,
+    /// whatsapp, telegram, slack, mock
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<String>,
+    /// chat, group, channel, newsletter, bot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<String>,
+    /// Only chats with unread messages.
+    #[serde(default)]
+    pub unread: bool,
+    /// `true`: only muted chats; `false`: hide muted chats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted: Option<bool>,
+    /// Regular expression matched against the chat name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_regex: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +70,67 @@ pub struct TuiConfig {
     pub render_rate_ms: u64,
     #[serde(default = "default_chat_list_width")]
     pub chat_list_width_percent: u16,
+    /// Built-in theme (dark, light, high-contrast) or a file in `<data_dir>/themes/`.
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    /// Inline image thumbnails: auto, kitty, iterm2, sixel, halfblocks or off.
+    #[serde(default = "default_image_previews")]
+    pub image_previews: String,
+    /// Capture the mouse for clicking and wheel scrolling. Most terminals
+    /// still select text with Shift held.
+    #[serde(default = "default_true")]
+    pub mouse: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    /// Messages that came due while the app was closed: send (late), skip or ask.
+    #[serde(default = "default_missed_sends")]
+    pub missed_sends: String,
+    /// Send attempts before a scheduled message is marked failed.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AwayConfig {
+    /// Auto-reply to incoming messages. Also toggled from the settings overlay.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Reply used by rules without their own message.
+    #[serde(default = "default_away_message")]
+    pub message: String,
+    /// Answer each sender at most once per this many hours.
+    #[serde(default = "default_away_cooldown")]
+    pub cooldown_hours: u32,
+    /// `[[away.rules]]`, first match wins. Without rules every direct message is answered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<AwayRuleConfig>,
+}
+
+/// An auto-reply rule. Every condition that is set must match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AwayRuleConfig {
+    /// whatsapp, telegram, slack, mock
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<String>,
+    /// chat, group, channel, newsletter, bot. Defaults to `["chat"]` (direct messages).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<String>,
+    /// Regular expression matched against the sender name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_regex: Option<String>,
+    /// Start of the local time window, `HH:MM`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// End of the window, `HH:MM`; earlier than `from` wraps past midnight.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// mon, tue, … sun
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +203,30 @@ fn default_chat_list_width() -> u16 {
     30
 }
 
+fn default_theme() -> String {
+    "dark".to_string()
+}
+
+fn default_image_previews() -> String {
+    "auto".to_string()
+}
+
+fn default_missed_sends() -> String {
+    "send".to_string()
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_away_message() -> String {
+    "I'm away right now and will get back to you soon.".to_string()
+}
+
+fn default_away_cooldown() -> u32 {
+    4
+}
+
 fn default_true() -> bool {
     true
 }
@@ -137,6 +257,33 @@ fn default_summary_threshold() -> usize {
 fn default_debounce_ms() -> u64 {
     500
 }
+fn default_translate_to() -> String {
+    "English".to_string()
+}
+fn default_backend_timeout_secs() -> u64 {
+    10
+}
+fn default_cache_size() -> usize {
+    128
+}
+fn default_embeddings_provider() -> String {
+    "ollama".to_string()
+}
+fn default_embeddings_base_url() -> String {
+    "http://localhost:11434".to_string()
+}
+fn default_embeddings_model() -> String {
+    "nomic-embed-text".to_string()
+}
+fn default_embeddings_batch_size() -> usize {
+    32
+}
+fn default_keep_alive() -> String {
+    "5m".to_string()
+}
+fn default_num_predict() -> u32 {
+    30
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiConfig {
@@ -158,6 +305,200 @@ pub struct AiConfig {
     pub debounce_ms: u64,
     #[serde(default)]
     pub debug: bool,
+    /// Language messages are translated into, as a name the model understands.
+    #[serde(default = "default_translate_to")]
+    pub translate_to: String,
+    #[serde(default)]
+    pub ollama: OllamaConfig,
+    #[serde(default)]
+    pub embeddings: EmbeddingsConfig,
+    /// Named prompt templates (`[ai.prompts.<name>]`) chats can be given as
+    /// their persona. `default` applies to chats without one.
+    #[serde(default)]
+    pub prompts: BTreeMap<String, PromptTemplate>,
+    #[serde(default)]
+    pub redact: RedactConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    /// Completions remembered for identical requests; 0 turns the cache off.
+    #[serde(default = "default_cache_size")]
+    pub cache_size: usize,
+    /// Backends tried in order (`[[ai.providers]]`); when empty, the single
+    /// backend given by `provider`, `base_url` and `api_key`, which are
+    /// otherwise ignored.
+    #[serde(default)]
+    pub providers: Vec<ProviderConfig>,
+}
+
+impl AiConfig {
+    /// The backends to try, in order.
+    pub fn backends(&self) -> Vec<ProviderConfig> {
+        if !self.providers.is_empty() {
+            return self.providers.clone();
+        }
+        vec![ProviderConfig {
+            name: None,
+            provider: self.provider.clone(),
+            base_url: self.base_url.clone(),
+            api_key: self.api_key.clone(),
+            model: None,
+            timeout_secs: default_backend_timeout_secs(),
+        }]
+    }
+}
+
+/// One backend in the `[[ai.providers]]` fallback chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    /// Shown in the status bar and usage; the provider kind when unset.
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_ai_provider")]
+    pub provider: String,
+    #[serde(default = "default_ai_base_url")]
+    pub base_url: String,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model for this backend; `ai.model` when unset.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// How long to wait for an answer before moving on to the next backend.
+    /// The last backend waits as long as it takes.
+    #[serde(default = "default_backend_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl ProviderConfig {
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.provider)
+    }
+
+    /// Whether requests leave this machine: always for the hosted
+    /// providers, otherwise when `base_url` is not a loopback address.
+    pub fn is_remote(&self) -> bool {
+        match self.provider.as_str() {
+            "anthropic" | "gemini" => true,
+            _ => {
+                let url = reqwest::Url::parse(&self.base_url).ok();
+                let host = url.as_ref().and_then(|u| u.host_str()).unwrap_or("");
+                !(host == "localhost"
+                    || host == "[::1]"
+                    || host == "0.0.0.0"
+                    || host.starts_with("127."))
+            }
+        }
+    }
+}
+
+/// Masking of personal details before they reach the AI provider (`[ai.redact]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactConfig {
+    /// Mask requests to remote providers.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Mask requests to local providers too.
+    #[serde(default)]
+    pub local: bool,
+    /// Extra regexes whose matches are masked, e.g. customer or ticket ids.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl RedactConfig {
+    /// Whether requests to `backend` are masked.
+    pub fn applies_to(&self, backend: &ProviderConfig) -> bool {
+        self.enabled && (self.local || backend.is_remote())
+    }
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            local: false,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Caps on AI use (`[ai.limits]`); 0 leaves a limit off. Cached
+/// completions don't count.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LimitsConfig {
+    #[serde(default)]
+    pub requests_per_minute: u32,
+    #[serde(default)]
+    pub requests_per_day: u32,
+    /// Input and output tokens together, as the provider reports them.
+    #[serde(default)]
+    pub tokens_per_day: u64,
+}
+
+/// System prompts replacing the built-in ones; unset ones keep the built-in prompt.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    #[serde(default)]
+    pub completion: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub smart_reply: Option<String>,
+}
+
+/// Local embeddings for semantic search over message history (`[ai.embeddings]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// "ollama" for `/api/embeddings`; anything else uses the OpenAI-compatible `/v1/embeddings`.
+    #[serde(default = "default_embeddings_provider")]
+    pub provider: String,
+    #[serde(default = "default_embeddings_base_url")]
+    pub base_url: String,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default = "default_embeddings_model")]
+    pub model: String,
+    /// Messages embedded per request while indexing.
+    #[serde(default = "default_embeddings_batch_size")]
+    pub batch_size: usize,
+}
+
+impl Default for EmbeddingsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: default_embeddings_provider(),
+            base_url: default_embeddings_base_url(),
+            api_key: None,
+            model: default_embeddings_model(),
+            batch_size: default_embeddings_batch_size(),
+        }
+    }
+}
+
+/// Options only the native Ollama client (`provider = "ollama"`) understands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaConfig {
+    /// How long Ollama keeps the model loaded after a request, e.g. "5m", "1h" or "-1".
+    #[serde(default = "default_keep_alive")]
+    pub keep_alive: String,
+    /// Maximum tokens to generate per completion.
+    #[serde(default = "default_num_predict")]
+    pub num_predict: u32,
+    /// Send the prompt without the model's chat template (for base models).
+    #[serde(default)]
+    pub raw: bool,
+}
+
+impl Default for OllamaConfig {
+    fn default() -> Self {
+        Self {
+            keep_alive: default_keep_alive(),
+            num_predict: default_num_predict(),
+            raw: false,
+        }
+    }
 }
 
 impl Default for AiConfig {
@@ -172,6 +513,14 @@ impl Default for AiConfig {
             summary_threshold: default_summary_threshold(),
             debounce_ms: default_debounce_ms(),
             debug: false,
+            translate_to: default_translate_to(),
+            ollama: OllamaConfig::default(),
+            embeddings: EmbeddingsConfig::default(),
+            prompts: BTreeMap::new(),
+            redact: RedactConfig::default(),
+            limits: LimitsConfig::default(),
+            cache_size: default_cache_size(),
+            providers: Vec::new(),
         }
     }
 }
@@ -186,6 +535,10 @@ impl Default for AppConfig {
             whatsapp: WhatsAppConfig::default(),
             telegram: TelegramConfig::default(),
             ai: AiConfig::default(),
+            schedule: ScheduleConfig::default(),
+            away: AwayConfig::default(),
+            keybindings: BTreeMap::new(),
+            folders: Vec::new(),
         }
     }
 }
@@ -205,6 +558,29 @@ impl Default for TuiConfig {
             tick_rate_ms: default_tick_rate(),
             render_rate_ms: default_render_rate(),
             chat_list_width_percent: default_chat_list_width(),
+            theme: default_theme(),
+            image_previews: default_image_previews(),
+            mouse: true,
+        }
+    }
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            missed_sends: default_missed_sends(),
+            max_attempts: default_max_attempts(),
+        }
+    }
+}
+
+impl Default for AwayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            message: default_away_message(),
+            cooldown_hours: default_away_cooldown(),
+            rules: Vec::new(),
         }
     }
 }
@@ -244,6 +620,147 @@ impl AppConfig {
         std::fs::write(path, content)?;
         Ok(())
     }
+
+    /// Built-in tabs followed by the validated `[[folders]]`. All problems
+    /// are reported together.
+    pub fn chat_folders(&self) -> Result<Vec<ChatFolder>> {
+        let mut errors = Vec::new();
+        let mut result = folders::builtin_folders();
+        for folder in &self.folders {
+            let mut error =
+                |msg: String| errors.push(format!("[[folders]] \"{}\": {}", folder.name, msg));
+            let mut rules = FolderRules {
+                unread_only: folder.unread,
+                muted: folder.muted,
+                ..Default::default()
+            };
+            for name in &folder.platforms {
+                match folders::parse_platform(name) {
+                    Some(p) => rules.platforms.push(p),
+                    None => error(format!("unknown platform \"{}\"", name)),
+                }
+            }
+            for name in &folder.kinds {
+                match folders::parse_kind(name) {
+                    Some(k) => rules.kinds.push(k),
+                    None => error(format!("unknown chat kind \"{}\"", name)),
+                }
+            }
+            if let Some(pattern) = &folder.name_regex {
+                match regex::Regex::new(pattern) {
+                    Ok(re) => rules.name_regex = Some(re),
+                    Err(e) => error(format!("invalid name_regex: {}", e)),
+                }
+            }
+            if folder.name.trim().is_empty() {
+                error("name must not be empty".to_string());
+            }
+            result.push(ChatFolder::new(folder.name.clone(), rules));
+        }
+        if !errors.is_empty() {
+            anyhow::bail!("Invalid folders:\n  {}", errors.join("\n  "));
+        }
+        Ok(result)
+    }
+
+    /// The redactor for AI requests, or `None` when `[ai.redact]` leaves
+    /// every backend's requests as they are. All bad patterns are reported together.
+    pub fn redactor(&self) -> Result<Option<Redactor>> {
+        let redact = &self.ai.redact;
+        if !self.ai.backends().iter().any(|b| redact.applies_to(b)) {
+            return Ok(None);
+        }
+        let mut errors = Vec::new();
+        let mut patterns = Vec::new();
+        for pattern in &redact.patterns {
+            match regex::Regex::new(pattern) {
+                Ok(re) => patterns.push(re),
+                Err(e) => errors.push(format!("\"{}\": {}", pattern, e)),
+            }
+        }
+        if !errors.is_empty() {
+            anyhow::bail!("Invalid [ai.redact] patterns:\n  {}", errors.join("\n  "));
+        }
+        Ok(Some(Redactor::new(patterns)))
+    }
+
+    /// The validated `[[away.rules]]`, or a rule answering every direct
+    /// message when none are configured. All problems are reported together.
+    pub fn away_rules(&self) -> Result<Vec<AwayRule>> {
+        let away = &self.away;
+        if away.rules.is_empty() {
+            return Ok(vec![AwayRule::direct_messages(away.message.clone())]);
+        }
+        let mut errors = Vec::new();
+        let mut result = Vec::new();
+        for (i, config) in away.rules.iter().enumerate() {
+            let mut error =
+                |msg: String| errors.push(format!("[[away.rules]] #{}: {}", i + 1, msg));
+            let mut rule = AwayRule::direct_messages(
+                config
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| away.message.clone()),
+            );
+            rule.platforms = config
+                .platforms
+                .iter()
+                .filter_map(|name| {
+                    let platform = folders::parse_platform(name);
+                    if platform.is_none() {
+                        error(format!("unknown platform \"{}\"", name));
+                    }
+                    platform
+                })
+                .collect();
+            if !config.kinds.is_empty() {
+                rule.kinds = config
+                    .kinds
+                    .iter()
+                    .filter_map(|name| {
+                        let kind = folders::parse_kind(name);
+                        if kind.is_none() {
+                            error(format!("unknown chat kind \"{}\"", name));
+                        }
+                        kind
+                    })
+                    .collect();
+            }
+            if let Some(pattern) = &config.sender_regex {
+                match regex::Regex::new(pattern) {
+                    Ok(re) => rule.sender_regex = Some(re),
+                    Err(e) => error(format!("invalid sender_regex: {}", e)),
+                }
+            }
+            let mut time = |value: &str| {
+                let parsed = chrono::NaiveTime::parse_from_str(value, "%H:%M").ok();
+                if parsed.is_none() {
+                    error(format!("invalid time \"{}\", expected HH:MM", value));
+                }
+                parsed
+            };
+            match (config.from.as_deref(), config.to.as_deref()) {
+                (None, None) => {}
+                (Some(from), Some(to)) => {
+                    if let (Some(from), Some(to)) = (time(from), time(to)) {
+                        rule.window = Some((from, to));
+                    }
+                }
+                _ => error("from and to must be set together".to_string()),
+            }
+            for day in &config.days {
+                match day.parse::<chrono::Weekday>() {
+                    Ok(d) => rule.days.push(d),
+                    Err(_) => error(format!("unknown day \"{}\"", day)),
+                }
+            }
+            result.push(rule);
+        }
+        if !errors.is_empty() {
+            anyhow::bail!("Invalid away rules:\n  {}", errors.join("\n  "));
+        }
+        Ok(result)
+    }
 }
 
 #[cfg(test)]
@@ -286,6 +803,9 @@ context_messages = 10
 summary_threshold = 50
 debounce_ms = 500
 debug = true
+
+[ai.ollama]
+keep_alive = "1h"
 "#;
         let result = toml::from_str::<AppConfig>(toml);
         assert!(result.is_ok(), "parse failed: {:?}", result.err());
@@ -294,5 +814,154 @@ debug = true
         assert_eq!(cfg.ai.base_url, "http://localhost:8080");
         assert_eq!(cfg.ai.model, "qwen2.5-1.5b-instruct-q4_k_m");
         assert!(cfg.ai.debug);
+        assert_eq!(cfg.ai.ollama.keep_alive, "1h");
+        assert_eq!(cfg.ai.ollama.num_predict, 30);
+    }
+
+    #[test]
+    fn test_folders_are_validated() {
+        let cfg: AppConfig = toml::from_str(
+            r#"
+[[folders]]
+name = "Work"
+platforms = ["telegram"]
+kinds = ["group", "channel"]
+muted = false
+name_regex = "(?i)team"
+
+[[folders]]
+name = "Broken"
+kinds = ["forum"]
+name_regex = "("
+"#,
+        )
+        .unwrap();
+        let err = cfg.chat_folders().unwrap_err().to_string();
+        assert!(err.contains("unknown chat kind \"forum\""));
+        assert!(err.contains("invalid name_regex"));
+
+        let mut cfg = cfg;
+        cfg.folders.pop();
+        let folders = cfg.chat_folders().unwrap();
+        assert_eq!(folders.last().unwrap().name, "Work");
+        assert_eq!(folders.len(), 7);
+    }
+
+    #[test]
+    fn test_redaction_applies_to_remote_providers() {
+        let mut cfg = AppConfig::default();
+        assert!(cfg.redactor().unwrap().is_none(), "local ollama");
+        cfg.ai.redact.local = true;
+        assert!(cfg.redactor().unwrap().is_some());
+
+        let mut cfg: AppConfig = toml::from_str(
+            r#"
+[ai]
+provider = "openai"
+base_url = "https://api.openai.com"
+
+[ai.redact]
+patterns = ["ACME-\\d+", "("]
+"#,
+        )
+        .unwrap();
+        assert!(cfg.ai.backends()[0].is_remote());
+        let err = cfg.redactor().err().unwrap().to_string();
+        assert!(err.contains("\"(\""));
+        cfg.ai.redact.patterns.pop();
+        assert!(cfg.redactor().unwrap().is_some());
+        cfg.ai.redact.enabled = false;
+        assert!(cfg.redactor().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_ai_providers_chain() {
+        let cfg: AppConfig = toml::from_str(
+            r#"
+[ai]
+model = "qwen2.5:1.5b"
+
+[[ai.providers]]
+name = "llama"
+provider = "openai"
+base_url = "http://127.0.0.1:8080"
+timeout_secs = 3
+
+[[ai.providers]]
+provider = "anthropic"
+model = "claude-3-5-haiku-latest"
+"#,
+        )
+        .unwrap();
+        let backends = cfg.ai.backends();
+        assert_eq!(backends.len(), 2);
+        assert_eq!(backends[0].name(), "llama");
+        assert_eq!(backends[0].timeout_secs, 3);
+        assert_eq!(backends[1].name(), "anthropic");
+        assert!(!cfg.ai.redact.applies_to(&backends[0]));
+        assert!(cfg.ai.redact.applies_to(&backends[1]));
+        assert!(cfg.redactor().unwrap().is_some());
+
+        let single = AppConfig::default().ai.backends();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].name(), "ollama");
+    }
+
+    #[test]
+    fn test_parse_keybindings() {
+        let toml = r#"
+[keybindings.normal]
+"ctrl+l" = "open_schedule_list"
+"gg" = "first_chat"
+"#;
+        let cfg: AppConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            cfg.keybindings["normal"]["ctrl+l"],
+            "open_schedule_list".to_string()
+        );
+        assert_eq!(cfg.keybindings["normal"].len(), 2);
+    }
+
+    #[test]
+    fn test_away_rules() {
+        let cfg = AppConfig::default();
+        let rules = cfg.away_rules().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].kinds, [crate::core::types::ChatKind::Chat]);
+
+        let toml = r#"
+[away]
+enabled = true
+message = "Off shift"
+
+[[away.rules]]
+platforms = ["telegram"]
+from = "18:00"
+to = "09:00"
+days = ["sat", "Sunday"]
+
+[[away.rules]]
+kinds = ["group"]
+sender_regex = "^Boss"
+message = "Back Monday"
+"#;
+        let cfg: AppConfig = toml::from_str(toml).unwrap();
+        let rules = cfg.away_rules().unwrap();
+        assert_eq!(rules[0].message, "Off shift");
+        assert_eq!(rules[0].days, [chrono::Weekday::Sat, chrono::Weekday::Sun]);
+        assert!(rules[0].window.is_some());
+        assert_eq!(rules[1].message, "Back Monday");
+
+        let toml = r#"
+[[away.rules]]
+platforms = ["irc"]
+from = "25:00"
+days = ["someday"]
+"#;
+        let cfg: AppConfig = toml::from_str(toml).unwrap();
+        let err = cfg.away_rules().unwrap_err().to_string();
+        assert!(err.contains("unknown platform \"irc\""), "{}", err);
+        assert!(err.contains("from and to"), "{}", err);
+        assert!(err.contains("unknown day"), "{}", err);
     }
 }
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, Utc, Weekday};
+use regex::Regex;
+
+use super::types::{ChatKind, Platform, UnifiedMessage};
+
+/// Messages older than this when they arrive are history being synced, not
+/// someone waiting for an answer.
+const MAX_MESSAGE_AGE: Duration = Duration::minutes(10);
+
+/// When to auto-reply and with what. Every set condition must match; empty
+/// lists match anything.
+#[derive(Debug, Clone)]
+pub struct AwayRule {
+    pub platforms: Vec<Platform>,
+    pub kinds: Vec<ChatKind>,
+    /// Matched against the sender name.
+    pub sender_regex: Option<Regex>,
+    /// Local time window; a window whose start is after its end wraps past midnight.
+    pub window: Option<(NaiveTime, NaiveTime)>,
+    pub days: Vec<Weekday>,
+    pub message: String,
+}
+
+impl AwayRule {
+    /// Reply to direct messages at any time.
+    pub fn direct_messages(message: impl Into<String>) -> Self {
+        Self {
+            platforms: Vec::new(),
+            kinds: vec![ChatKind::Chat],
+            sender_regex: None,
+            window: None,
+            days: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn matches(&self, msg: &UnifiedMessage, kind: &ChatKind, now: DateTime<Local>) -> bool {
+        let time = now.time();
+        (self.platforms.is_empty() || self.platforms.contains(&msg.platform))
+            && (self.kinds.is_empty() || self.kinds.contains(kind))
+            && self
+                .sender_regex
+                .as_ref()
+                .is_none_or(|re| re.is_match(&msg.sender))
+            && (self.days.is_empty() || self.days.contains(&now.weekday()))
+            && self.window.is_none_or(|(from, to)| {
+                if from <= to {
+                    from <= time && time < to
+                } else {
+                    time >= from || time < to
+                }
+            })
+    }
+}
+
+/// Picks away replies for incoming messages, answering each sender in a chat
+/// at most once per cooldown.
+#[derive(Debug)]
+pub struct AutoResponder {
+    rules: Vec<AwayRule>,
+    cooldown: Duration,
+    /// (chat id, sender) → when they were last answered.
+    last_reply: HashMap<(String, String), DateTime<Utc>>,
+}
+
+impl AutoResponder {
+    pub fn new(rules: Vec<AwayRule>, cooldown: Duration) -> Self {
+        Self {
+            rules,
+            cooldown,
+            last_reply: HashMap::new(),
+        }
+    }
+
+    /// Count `sender` in `chat_id` as answered at `at`, e.g. before a restart.
+    pub fn remember(&mut self, chat_id: String, sender: String, at: DateTime<Utc>) {
+        self.last_reply.insert((chat_id, sender), at);
+    }
+
+    /// The reply of the first rule matching `msg`, unless the sender was
+    /// answered within the cooldown. A returned reply counts as sent.
+    pub fn reply_for(
+        &mut self,
+        msg: &UnifiedMessage,
+        kind: &ChatKind,
+        now: DateTime<Local>,
+    ) -> Option<String> {
+        let now_utc = now.with_timezone(&Utc);
+        if msg.is_outgoing || now_utc - msg.timestamp > MAX_MESSAGE_AGE {
+            return None;
+        }
+        let rule = self.rules.iter().find(|r| r.matches(msg, kind, now))?;
+        let key = (msg.chat_id.clone(), msg.sender.clone());
+        if self
+            .last_reply
+            .get(&key)
+            .is_some_and(|&at| now_utc - at < self.cooldown)
+        {
+            return None;
+        }
+        self.last_reply.insert(key, now_utc);
+        Some(rule.message.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{MessageContent, MessageStatus};
+    use chrono::TimeZone;
+
+    fn message(sender: &str, at: DateTime<Local>) -> UnifiedMessage {
+        UnifiedMessage {
+            id: "m1".to_string(),
+            chat_id: "chat-1".to_string(),
+            platform: Platform::Telegram,
+            sender: sender.to_string(),
+            content: MessageContent::Text("hi".to_string()),
+            timestamp: at.with_timezone(&Utc),
+            status: MessageStatus::Delivered,
+            is_outgoing: false,
+        }
+    }
+
+    fn local(d: u32, h: u32, m: u32) -> DateTime<Local> {
+        // 2027-03-01 is a Monday
+        Local.with_ymd_and_hms(2027, 3, d, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn answers_each_sender_once_per_cooldown() {
+        let mut responder =
+            AutoResponder::new(vec![AwayRule::direct_messages("Away")], Duration::hours(4));
+        let now = local(1, 12, 0);
+        let alice = message("Alice", now);
+        assert_eq!(
+            responder.reply_for(&alice, &ChatKind::Chat, now).as_deref(),
+            Some("Away")
+        );
+        assert_eq!(responder.reply_for(&alice, &ChatKind::Chat, now), None);
+        let later = now + Duration::hours(5);
+        assert!(responder
+            .reply_for(&message("Alice", later), &ChatKind::Chat, later)
+            .is_some());
+        assert!(responder
+            .reply_for(&message("Bob", now), &ChatKind::Chat, now)
+            .is_some());
+
+        // DMs only by default; synced history and our own messages are ignored
+        assert_eq!(
+            responder.reply_for(&message("Carol", now), &ChatKind::Group, now),
+            None
+        );
+        let old = message("Dave", now - Duration::hours(1));
+        assert_eq!(responder.reply_for(&old, &ChatKind::Chat, now), None);
+        let mut own = message("You", now);
+        own.is_outgoing = true;
+        assert_eq!(responder.reply_for(&own, &ChatKind::Chat, now), None);
+    }
+
+    #[test]
+    fn remembered_replies_count_toward_the_cooldown() {
+        let mut responder =
+            AutoResponder::new(vec![AwayRule::direct_messages("Away")], Duration::hours(4));
+        let now = local(1, 12, 0);
+        responder.remember(
+            "chat-1".to_string(),
+            "Alice".to_string(),
+            (now - Duration::hours(1)).with_timezone(&Utc),
+        );
+        assert_eq!(
+            responder.reply_for(&message("Alice", now), &ChatKind::Chat, now),
+            None
+        );
+    }
+
+    #[test]
+    fn rules_match_window_days_and_sender() {
+        let rule = AwayRule {
+            platforms: vec![Platform::Telegram],
+            sender_regex: Some(Regex::new("(?i)^boss").unwrap()),
+            window: Some((
+                NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            )),
+            days: vec![Weekday::Mon, Weekday::Tue],
+            ..AwayRule::direct_messages("Off shift")
+        };
+        let at = |now: DateTime<Local>, sender: &str| {
+            rule.matches(&message(sender, now), &ChatKind::Chat, now)
+        };
+        assert!(at(local(1, 22, 0), "Boss"));
+        assert!(at(local(2, 8, 59), "boss man"));
+        assert!(!at(local(1, 12, 0), "Boss"));
+        assert!(!at(local(1, 22, 0), "Alice"));
+        // 2027-03-03 is a Wednesday
+        assert!(!at(local(3, 22, 0), "Boss"));
+    }
+}
@@ -0,0 +1,194 @@
+use regex::Regex;
+
+use super::types::{ChatKind, Platform, UnifiedChat};
+
+/// A chat list tab. A chat belongs to the folder when it is explicitly
+/// included, or matches the rules and is not explicitly excluded.
+#[derive(Debug, Clone)]
+pub struct ChatFolder {
+    pub name: String,
+    /// `None`: only the explicitly included chats.
+    pub rules: Option<FolderRules>,
+    /// Chat ids always shown.
+    pub include: Vec<String>,
+    /// Chat ids never shown, even when they match the rules.
+    pub exclude: Vec<String>,
+    /// Platform the folder was imported from; `None` for built-in and config folders.
+    pub source: Option<Platform>,
+    /// The Archived view: shows only archived chats. Every other folder hides them.
+    pub archived: bool,
+}
+
+/// Every set rule must match. Empty lists match anything.
+#[derive(Debug, Clone, Default)]
+pub struct FolderRules {
+    pub platforms: Vec<Platform>,
+    pub kinds: Vec<ChatKind>,
+    pub unread_only: bool,
+    /// `Some(true)`: only muted chats; `Some(false)`: hide muted chats.
+    pub muted: Option<bool>,
+    /// Matched against the display name (custom name if set).
+    pub name_regex: Option<Regex>,
+}
+
+impl FolderRules {
+    pub fn matches(&self, chat: &UnifiedChat) -> bool {
+        let name = chat.display_name.as_deref().unwrap_or(&chat.name);
+        (self.platforms.is_empty() || self.platforms.contains(&chat.platform))
+            && (self.kinds.is_empty() || self.kinds.contains(&chat.kind))
+            && (!self.unread_only || chat.unread_count > 0)
+            && self.muted.is_none_or(|muted| chat.is_muted == muted)
+            && self.name_regex.as_ref().is_none_or(|re| re.is_match(name))
+    }
+}
+
+impl ChatFolder {
+    pub fn new(name: impl Into<String>, rules: FolderRules) -> Self {
+        Self {
+            name: name.into(),
+            rules: Some(rules),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            source: None,
+            archived: false,
+        }
+    }
+
+    pub fn matches(&self, chat: &UnifiedChat) -> bool {
+        if chat.is_archived != self.archived || self.exclude.contains(&chat.id) {
+            return false;
+        }
+        self.include.contains(&chat.id) || self.rules.as_ref().is_some_and(|r| r.matches(chat))
+    }
+}
+
+/// Tabs that always exist, ahead of configured and imported folders.
+pub fn builtin_folders() -> Vec<ChatFolder> {
+    let kinds = |kinds: &[ChatKind]| FolderRules {
+        kinds: kinds.to_vec(),
+        ..Default::default()
+    };
+    vec![
+        ChatFolder::new("All", FolderRules::default()),
+        ChatFolder::new(
+            "Unread",
+            FolderRules {
+                unread_only: true,
+                ..Default::default()
+            },
+        ),
+        ChatFolder::new("DMs", kinds(&[ChatKind::Chat, ChatKind::Bot])),
+        ChatFolder::new("Groups", kinds(&[ChatKind::Group])),
+        ChatFolder::new(
+            "Channels",
+            kinds(&[ChatKind::Channel, ChatKind::Newsletter]),
+        ),
+        ChatFolder {
+            archived: true,
+            ..ChatFolder::new("Archived", FolderRules::default())
+        },
+    ]
+}
+
+/// Parse a platform name as written in config (`whatsapp`, `telegram`, …).
+pub fn parse_platform(name: &str) -> Option<Platform> {
+    match name.to_ascii_lowercase().as_str() {
+        "whatsapp" => Some(Platform::WhatsApp),
+        "telegram" => Some(Platform::Telegram),
+        "slack" => Some(Platform::Slack),
+        "mock" => Some(Platform::Mock),
+        _ => None,
+    }
+}
+
+/// Parse a chat kind as written in config (`chat`, `group`, `channel`, `newsletter`, `bot`).
+pub fn parse_kind(name: &str) -> Option<ChatKind> {
+    let kind = ChatKind::from_str(&name.to_ascii_lowercase());
+    (kind.as_str() == name.to_ascii_lowercase()).then_some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat(id: &str, kind: ChatKind, unread: u32, muted: bool) -> UnifiedChat {
+        UnifiedChat {
+            id: id.to_string(),
+            platform: Platform::Telegram,
+            name: format!("{} chat", id),
+            display_name: None,
+            last_message: None,
+            unread_count: unread,
+            kind,
+            is_pinned: false,
+            is_muted: muted,
+            is_archived: false,
+        }
+    }
+
+    #[test]
+    fn builtin_tabs_filter_by_kind_and_unread() {
+        let folders = builtin_folders();
+        let dm = chat("a", ChatKind::Chat, 2, false);
+        let channel = chat("b", ChatKind::Newsletter, 0, false);
+        let names: Vec<&str> = folders
+            .iter()
+            .filter(|f| f.matches(&dm))
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, ["All", "Unread", "DMs"]);
+        let names: Vec<&str> = folders
+            .iter()
+            .filter(|f| f.matches(&channel))
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, ["All", "Channels"]);
+
+        let mut archived = chat("c", ChatKind::Chat, 1, false);
+        archived.is_archived = true;
+        let names: Vec<&str> = folders
+            .iter()
+            .filter(|f| f.matches(&archived))
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, ["Archived"]);
+    }
+
+    #[test]
+    fn rules_combine_and_explicit_lists_win() {
+        let mut folder = ChatFolder::new(
+            "Work",
+            FolderRules {
+                platforms: vec![Platform::Telegram],
+                muted: Some(false),
+                name_regex: Some(Regex::new("(?i)^team").unwrap()),
+                ..Default::default()
+            },
+        );
+        let mut team = chat("t", ChatKind::Group, 0, false);
+        team.name = "Team ops".to_string();
+        assert!(folder.matches(&team));
+
+        team.is_muted = true;
+        assert!(!folder.matches(&team));
+
+        folder.include.push("t".to_string());
+        assert!(folder.matches(&team));
+        folder.exclude.push("t".to_string());
+        assert!(!folder.matches(&team));
+
+        assert!(!folder.matches(&chat("x", ChatKind::Chat, 0, false)));
+        folder.rules = None;
+        folder.include.push("x".to_string());
+        assert!(folder.matches(&chat("x", ChatKind::Chat, 0, false)));
+    }
+
+    #[test]
+    fn parses_config_names() {
+        assert_eq!(parse_platform("Telegram"), Some(Platform::Telegram));
+        assert_eq!(parse_platform("irc"), None);
+        assert_eq!(parse_kind("group"), Some(ChatKind::Group));
+        assert_eq!(parse_kind("chat"), Some(ChatKind::Chat));
+        assert_eq!(parse_kind("forum"), None);
+    }
+}
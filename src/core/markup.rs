@@ -0,0 +1,510 @@
+//! Inline text formatting shared by the message view and the send path.
+//!
+//! Incoming WhatsApp text carries its formatting as markup (`*bold*`,
+//! `_italic_`, `~strike~`, `` `code` ``, ```` ```block``` ````), Telegram as
+//! entity ranges. Both are parsed into per-character formats. Composer text
+//! uses Markdown (`**bold**`, `*italic*`, `~~strike~~`, ...) and is converted
+//! to each platform's native formatting before sending; on WhatsApp-style
+//! platforms a single `*` keeps meaning bold.
+
+use super::types::{MessageContent, Platform, TextEntity, TextStyle};
+
+/// Set of formatting flags applied to a character.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextFormat(u8);
+
+impl TextFormat {
+    pub const PLAIN: Self = Self(0);
+    pub const BOLD: Self = Self(1);
+    pub const ITALIC: Self = Self(1 << 1);
+    pub const UNDERLINE: Self = Self(1 << 2);
+    pub const STRIKE: Self = Self(1 << 3);
+    pub const CODE: Self = Self(1 << 4);
+    pub const CODE_BLOCK: Self = Self(1 << 5);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn is_plain(self) -> bool {
+        self.0 == 0
+    }
+}
+
+const STYLE_FLAGS: &[(TextStyle, TextFormat)] = &[
+    (TextStyle::Bold, TextFormat::BOLD),
+    (TextStyle::Italic, TextFormat::ITALIC),
+    (TextStyle::Underline, TextFormat::UNDERLINE),
+    (TextStyle::Strikethrough, TextFormat::STRIKE),
+    (TextStyle::Code, TextFormat::CODE),
+    (TextStyle::Pre, TextFormat::CODE_BLOCK),
+];
+
+impl From<TextStyle> for TextFormat {
+    fn from(style: TextStyle) -> Self {
+        STYLE_FLAGS
+            .iter()
+            .find(|(s, _)| *s == style)
+            .map(|(_, f)| *f)
+            .unwrap_or_default()
+    }
+}
+
+/// Text as characters with the formatting applied to each.
+pub type StyledText = Vec<(char, TextFormat)>;
+
+const CODE_FENCE: &str = "```";
+
+/// Inline markers, longest first where they share a prefix.
+const WHATSAPP_MARKERS: &[(&str, TextFormat)] = &[
+    ("*", TextFormat::BOLD),
+    ("_", TextFormat::ITALIC),
+    ("~", TextFormat::STRIKE),
+    ("`", TextFormat::CODE),
+];
+
+const MARKDOWN_MARKERS: &[(&str, TextFormat)] = &[
+    ("**", TextFormat::BOLD),
+    ("~~", TextFormat::STRIKE),
+    ("*", TextFormat::ITALIC),
+    ("_", TextFormat::ITALIC),
+    ("`", TextFormat::CODE),
+];
+
+/// Composer markers for WhatsApp-style platforms: Markdown's `**` and `~~`
+/// plus the native `*bold*` and `~strike~` people already type there.
+const WHATSAPP_COMPOSER_MARKERS: &[(&str, TextFormat)] = &[
+    ("**", TextFormat::BOLD),
+    ("~~", TextFormat::STRIKE),
+    ("*", TextFormat::BOLD),
+    ("_", TextFormat::ITALIC),
+    ("~", TextFormat::STRIKE),
+    ("`", TextFormat::CODE),
+];
+
+/// Parse WhatsApp-style markup (also used by Slack).
+pub fn parse_whatsapp(text: &str) -> StyledText {
+    parse(text, WHATSAPP_MARKERS)
+}
+
+/// Parse the Markdown subset accepted in the composer.
+pub fn parse_markdown(text: &str) -> StyledText {
+    parse(text, MARKDOWN_MARKERS)
+}
+
+pub fn plain(text: &str) -> StyledText {
+    text.chars().map(|c| (c, TextFormat::PLAIN)).collect()
+}
+
+/// Apply entity ranges (byte offsets) to `text`.
+pub fn from_entities(text: &str, entities: &[TextEntity]) -> StyledText {
+    text.char_indices()
+        .map(|(i, c)| {
+            let format = entities
+                .iter()
+                .filter(|e| e.start <= i && i < e.end)
+                .fold(TextFormat::PLAIN, |f, e| f.union(e.style.into()));
+            (c, format)
+        })
+        .collect()
+}
+
+fn parse(text: &str, markers: &[(&str, TextFormat)]) -> StyledText {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = Vec::with_capacity(chars.len());
+    parse_into(&chars, TextFormat::PLAIN, markers, &mut out);
+    out
+}
+
+fn parse_into(
+    chars: &[char],
+    base: TextFormat,
+    markers: &[(&str, TextFormat)],
+    out: &mut StyledText,
+) {
+    let fence_len = CODE_FENCE.len();
+    let mut i = 0;
+    while i < chars.len() {
+        // Code blocks may span lines; their content is literal.
+        if starts_at(chars, i, CODE_FENCE) {
+            if let Some(j) = find_from(chars, i + fence_len, CODE_FENCE) {
+                let mut inner = &chars[i + fence_len..j];
+                if let Some(rest) = inner.strip_prefix(&['\n']) {
+                    inner = rest;
+                }
+                if let Some(rest) = inner.strip_suffix(&['\n']) {
+                    inner = rest;
+                }
+                let format = base.union(TextFormat::CODE_BLOCK);
+                out.extend(inner.iter().map(|&c| (c, format)));
+                i = j + fence_len;
+                continue;
+            }
+        }
+
+        // Underscores and tildes are common in URLs; never treat them as markup.
+        if let Some(len) = url_len(chars, i) {
+            out.extend(chars[i..i + len].iter().map(|&c| (c, base)));
+            i += len;
+            continue;
+        }
+
+        let span = markers.iter().find_map(|&(marker, format)| {
+            let len = marker.chars().count();
+            if starts_at(chars, i, marker) && can_open(chars, i, len) {
+                find_close(chars, i + len, marker).map(|close| (len, format, close))
+            } else {
+                None
+            }
+        });
+        if let Some((len, format, close)) = span {
+            let inner = &chars[i + len..close];
+            if format == TextFormat::CODE {
+                let format = base.union(format);
+                out.extend(inner.iter().map(|&c| (c, format)));
+            } else {
+                parse_into(inner, base.union(format), markers, out);
+            }
+            i = close + len;
+            continue;
+        }
+
+        out.push((chars[i], base));
+        i += 1;
+    }
+}
+
+fn starts_at(chars: &[char], i: usize, pat: &str) -> bool {
+    pat.chars()
+        .enumerate()
+        .all(|(k, p)| chars.get(i + k) == Some(&p))
+}
+
+fn find_from(chars: &[char], from: usize, pat: &str) -> Option<usize> {
+    (from..chars.len()).find(|&k| starts_at(chars, k, pat))
+}
+
+/// An opening marker must not follow a letter or digit (so `snake_case` and
+/// `2*3*4` stay literal) and must be followed by non-whitespace.
+fn can_open(chars: &[char], i: usize, len: usize) -> bool {
+    (i == 0 || !chars[i - 1].is_alphanumeric())
+        && chars.get(i + len).is_some_and(|c| !c.is_whitespace())
+}
+
+/// Find the closing marker on the same line: it must follow non-whitespace,
+/// not be followed by a letter or digit, and enclose at least one character.
+fn find_close(chars: &[char], from: usize, marker: &str) -> Option<usize> {
+    let len = marker.chars().count();
+    for k in from + 1..chars.len() {
+        if chars[k] == '\n' {
+            return None;
+        }
+        if starts_at(chars, k, marker)
+            && !chars[k - 1].is_whitespace()
+            && chars.get(k + len).is_none_or(|c| !c.is_alphanumeric())
+        {
+            return Some(k);
+        }
+    }
+    None
+}
+
+fn url_len(chars: &[char], i: usize) -> Option<usize> {
+    if i > 0 && chars[i - 1].is_alphanumeric() {
+        return None;
+    }
+    if !starts_at(chars, i, "http://") && !starts_at(chars, i, "https://") {
+        return None;
+    }
+    Some(
+        chars[i..]
+            .iter()
+            .position(|c| c.is_whitespace())
+            .unwrap_or(chars.len() - i),
+    )
+}
+
+/// Re-emit styled text as WhatsApp markup. Underline has no WhatsApp
+/// equivalent and is dropped.
+pub fn to_whatsapp(styled: &[(char, TextFormat)]) -> String {
+    const ORDER: &[(TextFormat, &str)] = &[
+        (TextFormat::CODE_BLOCK, CODE_FENCE),
+        (TextFormat::CODE, "`"),
+        (TextFormat::BOLD, "*"),
+        (TextFormat::ITALIC, "_"),
+        (TextFormat::STRIKE, "~"),
+    ];
+    let mut out = String::new();
+    let mut open: Vec<(TextFormat, &str)> = Vec::new();
+    for &(c, format) in styled {
+        // Close the first marker no longer active and everything nested in it
+        if let Some(pos) = open.iter().position(|(f, _)| !format.contains(*f)) {
+            for (_, marker) in open.drain(pos..).rev() {
+                out.push_str(marker);
+            }
+        }
+        for &(flag, marker) in ORDER {
+            if format.contains(flag) && !open.iter().any(|(f, _)| *f == flag) {
+                out.push_str(marker);
+                open.push((flag, marker));
+            }
+        }
+        out.push(c);
+    }
+    for (_, marker) in open.into_iter().rev() {
+        out.push_str(marker);
+    }
+    out
+}
+
+/// Split styled text into plain text plus entity ranges (byte offsets).
+pub fn to_entities(styled: &[(char, TextFormat)]) -> (String, Vec<TextEntity>) {
+    let text: String = styled.iter().map(|(c, _)| c).collect();
+    let offsets: Vec<usize> = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let mut entities = Vec::new();
+    for &(style, flag) in STYLE_FLAGS {
+        let mut start: Option<usize> = None;
+        for (idx, (_, format)) in styled.iter().enumerate() {
+            match (format.contains(flag), start) {
+                (true, None) => start = Some(idx),
+                (false, Some(s)) => {
+                    entities.push(TextEntity {
+                        start: offsets[s],
+                        end: offsets[idx],
+                        style,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            entities.push(TextEntity {
+                start: offsets[s],
+                end: text.len(),
+                style,
+            });
+        }
+    }
+    entities.sort_by_key(|e| (e.start, e.end));
+    (text, entities)
+}
+
+/// Convert composer Markdown into the platform's native formatting. On
+/// WhatsApp, Slack and Mock a single `*` stays bold, as it is natively.
+/// Text without any formatting is sent unchanged.
+pub fn compose_for_platform(text: &str, platform: Platform) -> MessageContent {
+    let styled = match platform {
+        Platform::Telegram => parse_markdown(text),
+        Platform::WhatsApp | Platform::Slack | Platform::Mock => {
+            parse(text, WHATSAPP_COMPOSER_MARKERS)
+        }
+    };
+    if styled.iter().all(|(_, f)| f.is_plain()) {
+        return MessageContent::Text(text.to_string());
+    }
+    match platform {
+        Platform::Telegram => {
+            let (text, entities) = to_entities(&styled);
+            MessageContent::Formatted { text, entities }
+        }
+        Platform::WhatsApp | Platform::Slack | Platform::Mock => {
+            MessageContent::Text(to_whatsapp(&styled))
+        }
+    }
+}
+
+/// Convert a UTF-16 offset/length (as used by Telegram entities) to a byte range.
+pub fn utf16_range_to_bytes(text: &str, offset: usize, length: usize) -> Option<(usize, usize)> {
+    let mut units = 0;
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        if units == offset {
+            start = Some(i);
+        }
+        if units == offset + length {
+            return start.map(|s| (s, i));
+        }
+        units += c.len_utf16();
+    }
+    if units == offset {
+        start = Some(text.len());
+    }
+    if units == offset + length {
+        return start.map(|s| (s, text.len()));
+    }
+    None
+}
+
+/// Convert a byte range to a UTF-16 offset/length.
+pub fn bytes_to_utf16_range(text: &str, start: usize, end: usize) -> (usize, usize) {
+    let offset = text[..start].encode_utf16().count();
+    let length = text[start..end].encode_utf16().count();
+    (offset, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(styled: &[(char, TextFormat)]) -> Vec<(String, TextFormat)> {
+        let mut out: Vec<(String, TextFormat)> = Vec::new();
+        for &(c, f) in styled {
+            match out.last_mut() {
+                Some((s, last)) if *last == f => s.push(c),
+                _ => out.push((c.to_string(), f)),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn parses_whatsapp_markup() {
+        assert_eq!(
+            runs(&parse_whatsapp("a *b* _c_ ~d~ `e`")),
+            vec![
+                ("a ".to_string(), TextFormat::PLAIN),
+                ("b".to_string(), TextFormat::BOLD),
+                (" ".to_string(), TextFormat::PLAIN),
+                ("c".to_string(), TextFormat::ITALIC),
+                (" ".to_string(), TextFormat::PLAIN),
+                ("d".to_string(), TextFormat::STRIKE),
+                (" ".to_string(), TextFormat::PLAIN),
+                ("e".to_string(), TextFormat::CODE),
+            ]
+        );
+        assert_eq!(
+            runs(&parse_whatsapp("*_both_*")),
+            vec![(
+                "both".to_string(),
+                TextFormat::BOLD.union(TextFormat::ITALIC)
+            )]
+        );
+    }
+
+    #[test]
+    fn leaves_non_markup_alone() {
+        for text in [
+            "snake_case_name",
+            "2*3*4",
+            "a * b * c",
+            "https://x.com/a_b_c",
+            "*unclosed",
+            "*across\nlines*",
+        ] {
+            assert!(
+                parse_whatsapp(text).iter().all(|(_, f)| f.is_plain()),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn code_blocks_span_lines_and_are_literal() {
+        let styled = parse_whatsapp("see\n```\nfn *main*() {}\n  x\n```");
+        let text: String = styled.iter().map(|(c, _)| c).collect();
+        assert_eq!(text, "see\nfn *main*() {}\n  x");
+        assert!(styled[4..]
+            .iter()
+            .all(|(_, f)| *f == TextFormat::CODE_BLOCK));
+    }
+
+    #[test]
+    fn entities_apply_by_byte_range() {
+        let styled = from_entities(
+            "héllo world",
+            &[TextEntity {
+                start: 0,
+                end: 6,
+                style: TextStyle::Bold,
+            }],
+        );
+        assert_eq!(
+            runs(&styled),
+            vec![
+                ("héllo".to_string(), TextFormat::BOLD),
+                (" world".to_string(), TextFormat::PLAIN),
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_converts_to_whatsapp() {
+        let to_wa = |s: &str| match compose_for_platform(s, Platform::WhatsApp) {
+            MessageContent::Text(t) => t,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(
+            to_wa("**bold** *bold* _it_ ~~gone~~"),
+            "*bold* *bold* _it_ ~gone~"
+        );
+        assert_eq!(to_wa("**bold _both_**"), "*bold _both_*");
+        assert_eq!(to_wa("```\nlet x = 1;\n```"), "```let x = 1;```");
+    }
+
+    #[test]
+    fn native_whatsapp_markup_is_sent_unchanged() {
+        for platform in [Platform::WhatsApp, Platform::Slack] {
+            for text in ["*bold*", "_it_ and ~gone~", "*_both_*"] {
+                assert!(
+                    matches!(
+                        compose_for_platform(text, platform),
+                        MessageContent::Text(t) if t == text
+                    ),
+                    "{:?} {}",
+                    platform,
+                    text
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn compose_picks_native_format() {
+        assert!(matches!(
+            compose_for_platform("plain *text", Platform::WhatsApp),
+            MessageContent::Text(t) if t == "plain *text"
+        ));
+        match compose_for_platform("say **hi** 👋 `x`", Platform::Telegram) {
+            MessageContent::Formatted { text, entities } => {
+                assert_eq!(text, "say hi 👋 x");
+                assert_eq!(
+                    entities,
+                    vec![
+                        TextEntity {
+                            start: 4,
+                            end: 6,
+                            style: TextStyle::Bold
+                        },
+                        TextEntity {
+                            start: 12,
+                            end: 13,
+                            style: TextStyle::Code
+                        },
+                    ]
+                );
+                assert_eq!(bytes_to_utf16_range(&text, 12, 13), (10, 1));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn utf16_ranges_round_trip() {
+        let text = "👋 hi";
+        assert_eq!(utf16_range_to_bytes(text, 3, 2), Some((5, 7)));
+        assert_eq!(bytes_to_utf16_range(text, 5, 7), (3, 2));
+        assert_eq!(utf16_range_to_bytes(text, 0, 2), Some((0, 4)));
+        assert_eq!(utf16_range_to_bytes(text, 1, 1), None);
+        assert_eq!(utf16_range_to_bytes(text, 3, 9), None);
+    }
+}
@@ -1,5 +1,9 @@
+pub mod away;
 pub mod error;
+pub mod folders;
+pub mod markup;
 pub mod provider;
+pub mod recurrence;
 pub mod router;
 pub mod types;
 
@@ -1,12 +1,21 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use tokio::sync::mpsc;
 
 use super::error::Result;
+use super::folders::ChatFolder;
 use super::types::*;
 
 /// Opaque bytes of a downloaded + decrypted media file.
 pub type MediaBytes = Vec<u8>;
 
+/// Owned media download handle that can run off the UI task, unlike
+/// `download_media` which borrows the provider.
+pub type MediaDownloader =
+    Arc<dyn Fn(MediaDecryptParams) -> BoxFuture<'static, Result<MediaBytes>> + Send + Sync>;
+
 #[derive(Debug, Clone)]
 pub enum ProviderEvent {
     NewMessage(UnifiedMessage),
@@ -42,6 +51,15 @@ pub enum ProviderEvent {
         chat_id: String,
         user_name: String,
     },
+    /// Server-side chat folders (e.g. Telegram dialog filters). Replaces any
+    /// folders previously imported from the same platform.
+    FoldersUpdated(Platform, Vec<ChatFolder>),
+    /// Server-side archive state of a chat, on sync or when changed from
+    /// another device. Sent after the chat itself appeared in `ChatsUpdated`.
+    ArchiveChanged {
+        chat_id: String,
+        archived: bool,
+    },
 }
 
 #[async_trait]
@@ -55,6 +73,11 @@ pub trait MessagingProvider: Send + Sync {
     async fn mark_as_read(&self, _chat_id: &str, _msg_ids: Vec<String>) -> Result<()> {
         Ok(())
     }
+    /// Mirror a local archive/unarchive to the platform's server-side archive.
+    /// The default implementation keeps archiving local to this client.
+    async fn set_archived(&self, _chat_id: &str, _archived: bool) -> Result<()> {
+        Ok(())
+    }
     /// Download and decrypt a media file identified by `params`.
     ///
     /// Returns the raw plaintext bytes of the media.
@@ -65,6 +88,15 @@ pub trait MessagingProvider: Send + Sync {
             "download_media not supported by this provider"
         ))
     }
+    /// Detached equivalent of `download_media`, used for background
+    /// thumbnail loading. `None` when the provider cannot download media.
+    fn media_downloader(&self) -> Option<MediaDownloader> {
+        None
+    }
+    /// Whether `send_message` accepts `MessageContent::File` (a local path in `url`).
+    fn supports_attachments(&self) -> bool {
+        false
+    }
     fn name(&self) -> &str;
     fn platform(&self) -> Platform;
     fn auth_status(&self) -> AuthStatus;
@@ -0,0 +1,280 @@
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// How a scheduled message repeats. The time of day comes from the message's
+/// `send_at`, except for cron rules which carry their own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily,
+    /// Monday to Friday.
+    Weekdays,
+    Weekly(Weekday),
+    /// Day of the month, clamped to the last day in shorter months.
+    Monthly(u32),
+    /// Five-field cron expression: minute hour day-of-month month day-of-week.
+    Cron(String),
+}
+
+impl Recurrence {
+    /// First occurrence strictly after `after`. Daily to monthly rules fire at
+    /// the local time of day of `anchor`; cron rules ignore it.
+    pub fn next_after(&self, anchor: DateTime<Utc>, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_timezone(&Local).date_naive();
+        if let Recurrence::Cron(expr) = self {
+            let cron = CronSpec::parse(expr).ok()?;
+            // Feb 29 rules can be four years out
+            return start
+                .iter_days()
+                .take(4 * 366)
+                .filter(|&date| cron.matches_date(date))
+                .find_map(|date| {
+                    cron.hours.iter().find_map(|&h| {
+                        cron.minutes.iter().find_map(|&m| {
+                            local_to_utc(date, NaiveTime::from_hms_opt(h, m, 0)?)
+                                .filter(|t| *t > after)
+                        })
+                    })
+                });
+        }
+        let time = anchor.with_timezone(&Local).time();
+        start
+            .iter_days()
+            .take(400)
+            .filter(|&date| self.matches_date(date))
+            .find_map(|date| local_to_utc(date, time).filter(|t| *t > after))
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        match self {
+            Recurrence::Daily => true,
+            Recurrence::Weekdays => date.weekday().num_days_from_monday() < 5,
+            Recurrence::Weekly(day) => date.weekday() == *day,
+            Recurrence::Monthly(day) => date.day() == (*day).min(last_day_of_month(date)),
+            Recurrence::Cron(_) => false,
+        }
+    }
+}
+
+/// The phrase form understood by `time_parse::parse_recurrence`.
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "every day"),
+            Recurrence::Weekdays => write!(f, "every weekday"),
+            Recurrence::Weekly(day) => write!(f, "every {}", weekday_name(*day)),
+            Recurrence::Monthly(day) => {
+                let suffix = match (day % 10, day % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                write!(f, "every {}{}", day, suffix)
+            }
+            Recurrence::Cron(expr) => write!(f, "cron {}", expr),
+        }
+    }
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// Local wall-clock time as UTC. Ambiguous times (DST fall-back) take the
+/// earlier instant; times in a DST gap do not exist.
+fn local_to_utc(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    (28..=31)
+        .rev()
+        .find(|&day| date.with_day(day).is_some())
+        .unwrap_or(28)
+}
+
+/// A parsed cron expression; each field lists the values it matches.
+#[derive(Debug)]
+pub struct CronSpec {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days: Vec<u32>,
+    months: Vec<u32>,
+    /// 0 = Sunday.
+    weekdays: Vec<u32>,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSpec {
+    /// Parse `minute hour day-of-month month day-of-week`. Fields accept `*`,
+    /// numbers, ranges (`1-5`), lists (`1,15`) and steps (`*/15`).
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err("expected 5 fields: minute hour day month weekday".to_string());
+        };
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            // Both 0 and 7 mean Sunday
+            weekdays: parse_field(weekday, 0, 7)?
+                .into_iter()
+                .map(|d| d % 7)
+                .collect(),
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    /// Standard cron semantics: when both day fields are restricted, either may match.
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !self.months.contains(&date.month()) {
+            return false;
+        }
+        let day = self.days.contains(&date.day());
+        let weekday = self
+            .weekdays
+            .contains(&date.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (false, true) => day,
+            (true, false) => weekday,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+    let number = |s: &str| {
+        s.parse::<u32>()
+            .map_err(|_| format!("invalid cron field \"{}\"", field))
+    };
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, number(step)?.max(1)),
+            None => (part, 1),
+        };
+        let (lo, hi) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((lo, hi)) => (number(lo)?, number(hi)?),
+            // "5/10" runs from 5 to the end of the range
+            None if step > 1 => (number(range)?, max),
+            None => (number(range)?, number(range)?),
+        };
+        if lo < min || hi > max || lo > hi {
+            return Err(format!(
+                "cron field \"{}\" is outside {}-{}",
+                field, min, max
+            ));
+        }
+        values.extend((lo..=hi).step_by(step as usize));
+    }
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn local(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        let date = NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        local_to_utc(date, NaiveTime::from_hms_opt(h, min, 0).unwrap()).unwrap()
+    }
+
+    fn local_parts(t: DateTime<Utc>) -> (u32, u32, u32, u32) {
+        let t = t.with_timezone(&Local);
+        (t.month(), t.day(), t.hour(), t.minute())
+    }
+
+    #[test]
+    fn next_occurrence_keeps_time_of_day() {
+        // 2027-03-01 is a Monday
+        let sent = local(2027, 3, 1, 9, 0);
+        let weekly = Recurrence::Weekly(Weekday::Mon);
+        assert_eq!(
+            local_parts(weekly.next_after(sent, sent).unwrap()),
+            (3, 8, 9, 0)
+        );
+        let daily = Recurrence::Daily;
+        assert_eq!(
+            local_parts(daily.next_after(sent, sent).unwrap()),
+            (3, 2, 9, 0)
+        );
+        // Friday rolls over to Monday
+        let friday = local(2027, 3, 5, 9, 0);
+        let weekdays = Recurrence::Weekdays;
+        assert_eq!(
+            local_parts(weekdays.next_after(friday, friday).unwrap()),
+            (3, 8, 9, 0)
+        );
+        // Missed occurrences are skipped, not replayed
+        let later = local(2027, 3, 20, 12, 0);
+        assert_eq!(
+            local_parts(weekly.next_after(sent, later).unwrap()),
+            (3, 22, 9, 0)
+        );
+    }
+
+    #[test]
+    fn monthly_clamps_to_short_months() {
+        let sent = local(2027, 1, 31, 8, 30);
+        let monthly = Recurrence::Monthly(31);
+        let next = monthly.next_after(sent, sent).unwrap();
+        assert_eq!(local_parts(next), (2, 28, 8, 30));
+        assert_eq!(
+            local_parts(monthly.next_after(sent, next).unwrap()),
+            (3, 31, 8, 30)
+        );
+    }
+
+    #[test]
+    fn cron_rules() {
+        // Saturday noon → Monday 09:30
+        let saturday = local(2027, 3, 6, 12, 0);
+        let cron = Recurrence::Cron("30 9 * * 1-5".to_string());
+        assert_eq!(
+            local_parts(cron.next_after(saturday, saturday).unwrap()),
+            (3, 8, 9, 30)
+        );
+        let quarter = Recurrence::Cron("*/15 * * * *".to_string());
+        assert_eq!(
+            local_parts(quarter.next_after(saturday, saturday).unwrap()),
+            (3, 6, 12, 15)
+        );
+
+        assert!(CronSpec::parse("0 9 * *").is_err());
+        assert!(CronSpec::parse("0 24 * * *").is_err());
+        assert!(CronSpec::parse("0 9 * * mon").is_err());
+        assert_eq!(CronSpec::parse("0 9 * * 7").unwrap().weekdays, [0]);
+    }
+
+    #[test]
+    fn displays_as_phrase() {
+        assert_eq!(Recurrence::Weekly(Weekday::Mon).to_string(), "every monday");
+        assert_eq!(Recurrence::Monthly(1).to_string(), "every 1st");
+        assert_eq!(Recurrence::Monthly(12).to_string(), "every 12th");
+        assert_eq!(Recurrence::Monthly(22).to_string(), "every 22nd");
+    }
+}
@@ -64,12 +64,37 @@ pub enum MessageContent {
         filename: String,
     },
     System(String),
+    /// Text with formatting ranges, e.g. Telegram message entities.
+    Formatted {
+        text: String,
+        entities: Vec<TextEntity>,
+    },
+}
+
+/// A formatted range of a `MessageContent::Formatted` text.
+/// `start..end` are byte offsets into the text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEntity {
+    pub start: usize,
+    pub end: usize,
+    pub style: TextStyle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextStyle {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    Code,
+    /// Multi-line code block.
+    Pre,
 }
 
 impl MessageContent {
     pub fn as_text(&self) -> &str {
         match self {
-            MessageContent::Text(t) => t,
+            MessageContent::Text(t) | MessageContent::Formatted { text: t, .. } => t,
             MessageContent::Image { caption, .. } => caption.as_deref().unwrap_or("[Image]"),
             MessageContent::File { filename, .. } => filename,
             MessageContent::System(t) => t,
@@ -148,6 +173,7 @@ pub struct UnifiedChat {
     pub kind: ChatKind,
     pub is_pinned: bool,
     pub is_muted: bool,
+    pub is_archived: bool,
 }
 
 #[cfg(test)]
@@ -14,6 +14,7 @@ use crate::app::App;
 use crate::config::AppConfig;
 use crate::storage::{AddressBook, Database};
 use crate::tui::event::EventHandler;
+use crate::tui::keybindings::Keymap;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -53,6 +54,11 @@ async fn main() -> anyhow::Result<()> {
         }
     });
     let config = AppConfig::load(&config_path)?;
+    // Validate [keybindings], [[folders]], [[away.rules]] and [ai.redact] before touching the terminal so errors are readable
+    let keymap = Keymap::from_config(&config.keybindings)?;
+    let folders = config.chat_folders()?;
+    let away_rules = config.away_rules()?;
+    let redactor = config.redactor()?;
 
     // Set up data directory
     let data_dir = PathBuf::from(&config.general.data_dir);
@@ -129,6 +135,10 @@ async fn main() -> anyhow::Result<()> {
         db,
         address_book,
         config_path,
+        keymap,
+        folders,
+        away_rules,
+        redactor,
         event_handler.sender(),
     );
     app.run(event_handler).await?;
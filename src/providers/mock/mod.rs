@@ -89,6 +89,7 @@ impl MockProvider {
                 },
                 is_pinned: false,
                 is_muted: false,
+                is_archived: false,
             })
             .collect()
     }
@@ -251,6 +252,10 @@ impl MessagingProvider for MockProvider {
             .collect())
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     fn name(&self) -> &str {
         "Mock"
     }
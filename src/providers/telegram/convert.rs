@@ -1,3 +1,7 @@
+use grammers_client::tl;
+
+use crate::core::folders::{ChatFolder, FolderRules};
+use crate::core::markup;
 use crate::core::types::*;
 
 // --- PeerCache (populated during get_chats, reused for send/get_messages) ---
@@ -76,7 +80,18 @@ pub fn grammers_message_to_unified(
 ) -> Option<UnifiedMessage> {
     // Text content — distinguish photo vs other media (v1: no URL download yet)
     let content = if !msg.text().is_empty() {
-        MessageContent::Text(msg.text().to_string())
+        let entities = msg
+            .fmt_entities()
+            .map(|entities| tl_entities_to_unified(msg.text(), entities))
+            .unwrap_or_default();
+        if entities.is_empty() {
+            MessageContent::Text(msg.text().to_string())
+        } else {
+            MessageContent::Formatted {
+                text: msg.text().to_string(),
+                entities,
+            }
+        }
     } else if msg.photo().is_some() {
         // TODO(show-picture): Telegram photo download requires grammers client.
         // For now render as [Image] text. A future iteration can add a proper
@@ -106,11 +121,154 @@ pub fn grammers_message_to_unified(
     })
 }
 
+/// Map Telegram formatting entities (UTF-16 ranges) to byte-range `TextEntity`s.
+/// Entity kinds without a terminal rendering (links, mentions, spoilers) are skipped.
+fn tl_entities_to_unified(text: &str, entities: &[tl::enums::MessageEntity]) -> Vec<TextEntity> {
+    use tl::enums::MessageEntity as E;
+    entities
+        .iter()
+        .filter_map(|entity| {
+            let (style, offset, length) = match entity {
+                E::Bold(e) => (TextStyle::Bold, e.offset, e.length),
+                E::Italic(e) => (TextStyle::Italic, e.offset, e.length),
+                E::Underline(e) => (TextStyle::Underline, e.offset, e.length),
+                E::Strike(e) => (TextStyle::Strikethrough, e.offset, e.length),
+                E::Code(e) => (TextStyle::Code, e.offset, e.length),
+                E::Pre(e) => (TextStyle::Pre, e.offset, e.length),
+                _ => return None,
+            };
+            let (start, end) =
+                markup::utf16_range_to_bytes(text, offset as usize, length as usize)?;
+            Some(TextEntity { start, end, style })
+        })
+        .collect()
+}
+
+/// Map our `TextEntity`s to Telegram formatting entities for sending.
+pub fn unified_entities_to_tl(
+    text: &str,
+    entities: &[TextEntity],
+) -> Vec<tl::enums::MessageEntity> {
+    entities
+        .iter()
+        .map(|entity| {
+            let (offset, length) = markup::bytes_to_utf16_range(text, entity.start, entity.end);
+            let (offset, length) = (offset as i32, length as i32);
+            match entity.style {
+                TextStyle::Bold => tl::types::MessageEntityBold { offset, length }.into(),
+                TextStyle::Italic => tl::types::MessageEntityItalic { offset, length }.into(),
+                TextStyle::Underline => tl::types::MessageEntityUnderline { offset, length }.into(),
+                TextStyle::Strikethrough => {
+                    tl::types::MessageEntityStrike { offset, length }.into()
+                }
+                TextStyle::Code => tl::types::MessageEntityCode { offset, length }.into(),
+                TextStyle::Pre => tl::types::MessageEntityPre {
+                    offset,
+                    length,
+                    language: String::new(),
+                }
+                .into(),
+            }
+        })
+        .collect()
+}
+
 // NOTE: grammers types cannot be constructed in unit tests — their
 // constructors are private. The chat-id encode/decode round-trip tests
 // above are the meaningful unit coverage. Integration behaviour is
 // verified manually via the live provider.
 
+/// Our chat id for a peer in an update, as a Bot API dialog id like `get_chats()`.
+pub fn peer_to_chat_id(peer: &tl::enums::Peer) -> String {
+    let id = match peer {
+        tl::enums::Peer::User(u) => u.user_id,
+        tl::enums::Peer::Chat(c) => -c.chat_id,
+        tl::enums::Peer::Channel(c) => -1_000_000_000_000 - c.channel_id,
+    };
+    peer_id_to_chat_id(id)
+}
+
+/// Our chat id for a peer referenced by a dialog filter, using the same
+/// Bot API dialog ids as `get_chats()`. `None` for peers we cannot resolve.
+pub fn input_peer_to_chat_id(peer: &tl::enums::InputPeer) -> Option<String> {
+    let id = match peer {
+        tl::enums::InputPeer::User(u) => u.user_id,
+        tl::enums::InputPeer::Chat(c) => -c.chat_id,
+        tl::enums::InputPeer::Channel(c) => -1_000_000_000_000 - c.channel_id,
+        _ => return None,
+    };
+    Some(peer_id_to_chat_id(id))
+}
+
+/// Convert the account's dialog filters (Telegram folders) to chat folders.
+/// The "All chats" default filter is skipped; our built-in tab covers it.
+pub fn dialog_filters_to_folders(filters: tl::enums::messages::DialogFilters) -> Vec<ChatFolder> {
+    let tl::enums::messages::DialogFilters::Filters(filters) = filters;
+    filters
+        .filters
+        .into_iter()
+        .filter_map(|filter| match filter {
+            tl::enums::DialogFilter::Filter(f) => {
+                let tl::enums::TextWithEntities::Entities(title) = f.title;
+                let mut kinds = Vec::new();
+                if f.contacts || f.non_contacts {
+                    kinds.push(ChatKind::Chat);
+                }
+                if f.groups {
+                    kinds.push(ChatKind::Group);
+                }
+                if f.broadcasts {
+                    kinds.push(ChatKind::Channel);
+                }
+                if f.bots {
+                    kinds.push(ChatKind::Bot);
+                }
+                let rules = (!kinds.is_empty()).then(|| FolderRules {
+                    platforms: vec![Platform::Telegram],
+                    kinds,
+                    unread_only: f.exclude_read,
+                    muted: f.exclude_muted.then_some(false),
+                    name_regex: None,
+                });
+                Some(ChatFolder {
+                    name: title.text,
+                    rules,
+                    include: f
+                        .pinned_peers
+                        .iter()
+                        .chain(&f.include_peers)
+                        .filter_map(input_peer_to_chat_id)
+                        .collect(),
+                    exclude: f
+                        .exclude_peers
+                        .iter()
+                        .filter_map(input_peer_to_chat_id)
+                        .collect(),
+                    source: Some(Platform::Telegram),
+                    archived: false,
+                })
+            }
+            tl::enums::DialogFilter::Chatlist(f) => {
+                let tl::enums::TextWithEntities::Entities(title) = f.title;
+                Some(ChatFolder {
+                    name: title.text,
+                    rules: None,
+                    include: f
+                        .pinned_peers
+                        .iter()
+                        .chain(&f.include_peers)
+                        .filter_map(input_peer_to_chat_id)
+                        .collect(),
+                    exclude: Vec::new(),
+                    source: Some(Platform::Telegram),
+                    archived: false,
+                })
+            }
+            tl::enums::DialogFilter::Default => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,4 +296,19 @@ mod tests {
         assert_eq!(chat_id_to_peer_id("tg-notanumber"), None);
         assert_eq!(chat_id_to_peer_id(""), None);
     }
+
+    #[test]
+    fn test_filter_peers_use_bot_api_ids() {
+        let channel = tl::enums::InputPeer::Channel(tl::types::InputPeerChannel {
+            channel_id: 1234567890,
+            access_hash: 0,
+        });
+        assert_eq!(
+            input_peer_to_chat_id(&channel).as_deref(),
+            Some("tg--1001234567890")
+        );
+        let chat = tl::enums::InputPeer::Chat(tl::types::InputPeerChat { chat_id: 42 });
+        assert_eq!(input_peer_to_chat_id(&chat).as_deref(), Some("tg--42"));
+        assert_eq!(input_peer_to_chat_id(&tl::enums::InputPeer::PeerSelf), None);
+    }
 }
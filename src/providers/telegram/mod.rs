@@ -7,6 +7,7 @@ use std::sync::{Arc, RwLock};
 use async_trait::async_trait;
 use futures::future::BoxFuture;
 use grammers_client::client::UpdatesConfiguration;
+use grammers_client::message::InputMessage;
 use grammers_client::peer::Peer;
 use grammers_client::tl;
 use grammers_client::{Client, SenderPool, SignInError};
@@ -20,7 +21,10 @@ use crate::core::error::Result;
 use crate::core::provider::{MessagingProvider, ProviderEvent};
 use crate::core::types::*;
 
-use convert::{grammers_message_to_unified, peer_id_to_chat_id, ChatNameCache, PeerCache};
+use convert::{
+    dialog_filters_to_folders, grammers_message_to_unified, peer_id_to_chat_id, peer_to_chat_id,
+    unified_entities_to_tl, ChatNameCache, PeerCache,
+};
 
 use grammers_client::client::PasswordToken;
 
@@ -445,6 +449,7 @@ impl TelegramProvider {
         // 8. Fetch initial dialog list and send to TUI.
         let mut dialogs = client.iter_dialogs();
         let mut chats = Vec::new();
+        let mut archive_states = Vec::new();
         let mut count = 0usize;
 
         while let Some(dialog) = dialogs
@@ -467,6 +472,12 @@ impl TelegramProvider {
                 .unwrap_or_else(|| "tg-unknown".to_string());
 
             peer_cache.insert(&chat_id_str, peer_ref);
+            // Folder 1 is Telegram's Archive
+            let archived = matches!(
+                &dialog.raw,
+                tl::enums::Dialog::Dialog(d) if d.folder_id == Some(1)
+            );
+            archive_states.push((chat_id_str.clone(), archived));
 
             let name = peer.name().unwrap_or("Unknown").to_string();
             chat_name_cache.insert(&chat_id_str, &name);
@@ -502,10 +513,29 @@ impl TelegramProvider {
                 kind,
                 is_pinned: false,
                 is_muted: false,
+                is_archived: false,
             });
         }
 
         let _ = tx.send(ProviderEvent::ChatsUpdated(chats));
+        for (chat_id, archived) in archive_states {
+            let _ = tx.send(ProviderEvent::ArchiveChanged { chat_id, archived });
+        }
+
+        // Import the account's folders; not fatal if the request fails.
+        match client
+            .invoke(&tl::functions::messages::GetDialogFilters {})
+            .await
+        {
+            Ok(filters) => {
+                let _ = tx.send(ProviderEvent::FoldersUpdated(
+                    Platform::Telegram,
+                    dialog_filters_to_folders(filters),
+                ));
+            }
+            Err(e) => tracing::warn!("Failed to fetch Telegram folders: {}", e),
+        }
+
         let _ = tx.send(ProviderEvent::SyncCompleted);
         save_session(&session, &session_path);
 
@@ -545,6 +575,15 @@ impl TelegramProvider {
                                     user_name: "someone".to_string(),
                                 });
                             }
+                            // Chats moved in or out of the Archive on another device
+                            tl::enums::Update::FolderPeers(u) => {
+                                for tl::enums::FolderPeer::Peer(fp) in &u.folder_peers {
+                                    let _ = tx.send(ProviderEvent::ArchiveChanged {
+                                        chat_id: peer_to_chat_id(&fp.peer),
+                                        archived: fp.folder_id == 1,
+                                    });
+                                }
+                            }
                             _ => {}
                         }
                         continue;
@@ -693,13 +732,14 @@ impl MessagingProvider for TelegramProvider {
             .get(chat_id)
             .ok_or_else(|| anyhow::anyhow!("Unknown chat_id: {}", chat_id))?;
 
-        let text = match &content {
-            MessageContent::Text(t) => t.clone(),
-            other => other.as_text().to_string(),
+        let message = match &content {
+            MessageContent::Formatted { text, entities } => InputMessage::from(text.as_str())
+                .fmt_entities(unified_entities_to_tl(text, entities)),
+            other => InputMessage::from(other.as_text()),
         };
 
         let sent = client
-            .send_message(peer, text.as_str())
+            .send_message(peer, message)
             .await
             .map_err(|e| anyhow::anyhow!("send_message failed: {}", e))?;
 
@@ -783,6 +823,7 @@ impl MessagingProvider for TelegramProvider {
                 kind,
                 is_pinned: false,
                 is_muted: false,
+                is_archived: false,
             });
         }
 
@@ -848,6 +889,32 @@ impl MessagingProvider for TelegramProvider {
         Ok(())
     }
 
+    async fn set_archived(&self, chat_id: &str, archived: bool) -> Result<()> {
+        let client = self
+            .client
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Telegram client not started"))?;
+
+        let peer = self
+            .peer_cache
+            .get(chat_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown chat_id (not in peer cache): {}", chat_id))?;
+
+        client
+            .invoke(&tl::functions::folders::EditPeerFolders {
+                folder_peers: vec![tl::types::InputFolderPeer {
+                    peer: peer.into(),
+                    folder_id: if archived { 1 } else { 0 },
+                }
+                .into()],
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("EditPeerFolders failed: {}", e))?;
+        Ok(())
+    }
+
     fn name(&self) -> &str {
         "Telegram"
     }
@@ -10,7 +10,7 @@ use whatsapp_rust::bot::Bot;
 use whatsapp_rust::store::SqliteStore;
 use whatsapp_rust::transport::{TokioWebSocketTransportFactory, UreqHttpClient};
 
-use crate::core::provider::{MediaBytes, MessagingProvider, ProviderEvent};
+use crate::core::provider::{MediaBytes, MediaDownloader, MessagingProvider, ProviderEvent};
 use crate::core::types::*;
 use crate::core::Result;
 
@@ -186,26 +186,18 @@ impl MessagingProvider for WhatsAppProvider {
     }
 
     async fn download_media(&self, params: &MediaDecryptParams) -> Result<MediaBytes> {
-        use whatsapp_rust::download::MediaType;
-
         let client = self
             .client
-            .as_ref()
+            .clone()
             .ok_or_else(|| anyhow::anyhow!("WhatsApp client not connected"))?;
+        download_image(client, params.clone()).await
+    }
 
-        let bytes = client
-            .download_from_params(
-                &params.direct_path,
-                &params.media_key,
-                &params.file_sha256,
-                &params.file_enc_sha256,
-                params.file_length,
-                MediaType::Image,
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("WhatsApp media download failed: {}", e))?;
-
-        Ok(bytes)
+    fn media_downloader(&self) -> Option<MediaDownloader> {
+        let client = self.client.clone()?;
+        Some(Arc::new(move |params| {
+            Box::pin(download_image(client.clone(), params))
+        }))
     }
 
     async fn get_chats(&self) -> Result<Vec<UnifiedChat>> {
@@ -229,6 +221,25 @@ impl MessagingProvider for WhatsAppProvider {
     }
 }
 
+async fn download_image(
+    client: Arc<whatsapp_rust::Client>,
+    params: MediaDecryptParams,
+) -> Result<MediaBytes> {
+    use whatsapp_rust::download::MediaType;
+
+    client
+        .download_from_params(
+            &params.direct_path,
+            &params.media_key,
+            &params.file_sha256,
+            &params.file_enc_sha256,
+            params.file_length,
+            MediaType::Image,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("WhatsApp media download failed: {}", e))
+}
+
 /// Handle a WhatsApp event and forward it to our provider event channel.
 fn handle_wa_event(
     event: whatsapp_rust::types::events::Event,
@@ -322,6 +333,7 @@ fn handle_wa_event(
                     kind,
                     is_pinned: false,
                     is_muted: false,
+                    is_archived: false,
                 };
 
                 let _ = tx.send(ProviderEvent::ChatsUpdated(vec![chat]));
@@ -406,6 +418,7 @@ fn handle_wa_event(
                         kind,
                         is_pinned: false,
                         is_muted: false,
+                        is_archived: false,
                     };
 
                     let _ = tx.send(ProviderEvent::ChatsUpdated(vec![chat]));
@@ -427,6 +440,12 @@ fn handle_wa_event(
                 let _ = tx.send(ProviderEvent::SelfRead { chat_id });
             }
         }
+        Event::ArchiveUpdate(update) => {
+            let chat_id = jid_to_chat_id(&update.jid, jid_cache);
+            let archived = update.action.archived.unwrap_or(false);
+            tracing::debug!(archived, "Archive sync from other device: {}", chat_id);
+            let _ = tx.send(ProviderEvent::ArchiveChanged { chat_id, archived });
+        }
         Event::OfflineSyncCompleted(_) => {
             tracing::info!("WhatsApp offline sync completed");
             let _ = tx.send(ProviderEvent::SyncCompleted);
@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+
+use crate::ai::usage::UsageRecord;
+use crate::core::Result;
+use crate::storage::db::Database;
+
+/// AI use summed over a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    /// Requests that reached a provider.
+    pub requests: u64,
+    /// Requests answered from the completion cache.
+    pub cached: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl UsageTotals {
+    pub fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+impl Database {
+    /// Add one request to the counts for its day, provider and model.
+    pub fn record_ai_usage(&self, day: NaiveDate, record: &UsageRecord) -> Result<()> {
+        let (requests, cached) = if record.cached { (0, 1) } else { (1, 0) };
+        self.conn.execute(
+            "INSERT INTO ai_usage (day, provider, model, requests, cached, input_tokens, output_tokens)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(day, provider, model) DO UPDATE SET
+                requests = requests + excluded.requests,
+                cached = cached + excluded.cached,
+                input_tokens = input_tokens + excluded.input_tokens,
+                output_tokens = output_tokens + excluded.output_tokens",
+            rusqlite::params![
+                day.to_string(),
+                record.provider,
+                record.model,
+                requests,
+                cached,
+                record.usage.input_tokens as i64,
+                record.usage.output_tokens as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Usage on `day` across every provider and model.
+    pub fn ai_usage_on(&self, day: NaiveDate) -> Result<UsageTotals> {
+        let totals = self.conn.query_row(
+            "SELECT COALESCE(SUM(requests), 0), COALESCE(SUM(cached), 0),
+                    COALESCE(SUM(input_tokens), 0), COALESCE(SUM(output_tokens), 0)
+             FROM ai_usage WHERE day = ?1",
+            rusqlite::params![day.to_string()],
+            |row| {
+                Ok(UsageTotals {
+                    requests: row.get::<_, i64>(0)? as u64,
+                    cached: row.get::<_, i64>(1)? as u64,
+                    input_tokens: row.get::<_, i64>(2)? as u64,
+                    output_tokens: row.get::<_, i64>(3)? as u64,
+                })
+            },
+        )?;
+        Ok(totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::providers::Usage;
+
+    #[test]
+    fn usage_adds_up_per_day() {
+        let db = Database::open_in_memory().unwrap();
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let record = |model: &str, input, output, cached| UsageRecord {
+            provider: "openai".to_string(),
+            model: model.to_string(),
+            usage: Usage {
+                input_tokens: input,
+                output_tokens: output,
+            },
+            cached,
+        };
+        db.record_ai_usage(day, &record("gpt-4o", 100, 20, false))
+            .unwrap();
+        db.record_ai_usage(day, &record("gpt-4o", 50, 5, false))
+            .unwrap();
+        db.record_ai_usage(day, &record("gpt-4o-mini", 0, 0, true))
+            .unwrap();
+        db.record_ai_usage(day.succ_opt().unwrap(), &record("gpt-4o", 7, 7, false))
+            .unwrap();
+
+        let totals = db.ai_usage_on(day).unwrap();
+        assert_eq!(
+            totals,
+            UsageTotals {
+                requests: 2,
+                cached: 1,
+                input_tokens: 150,
+                output_tokens: 25,
+            }
+        );
+        assert_eq!(totals.tokens(), 175);
+        let empty = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+        assert_eq!(db.ai_usage_on(empty).unwrap(), UsageTotals::default());
+    }
+}
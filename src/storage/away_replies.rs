@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+
+use crate::core::Result;
+use crate::storage::db::Database;
+
+impl Database {
+    /// Remember that `sender` in `chat_id` got an away reply at `at`.
+    pub fn record_away_reply(&self, chat_id: &str, sender: &str, at: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO away_replies (chat_id, sender, replied_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(chat_id, sender) DO UPDATE SET replied_at = excluded.replied_at",
+            rusqlite::params![chat_id, sender, at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Away replies sent at or after `since`, as (chat id, sender, when),
+    /// after forgetting older ones.
+    pub fn away_replies_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(String, String, DateTime<Utc>)>> {
+        let since = since.to_rfc3339();
+        self.conn.execute(
+            "DELETE FROM away_replies WHERE replied_at < ?1",
+            rusqlite::params![since],
+        )?;
+        let mut stmt = self
+            .conn
+            .prepare("SELECT chat_id, sender, replied_at FROM away_replies")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut replies = Vec::new();
+        for row in rows {
+            let (chat_id, sender, at) = row?;
+            if let Ok(at) = DateTime::parse_from_rfc3339(&at) {
+                replies.push((chat_id, sender, at.with_timezone(&Utc)));
+            }
+        }
+        Ok(replies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn keeps_only_recent_replies() {
+        let db = Database::open_in_memory().unwrap();
+        let now = Utc::now();
+        db.record_away_reply("chat-1", "Alice", now - Duration::hours(6))
+            .unwrap();
+        db.record_away_reply("chat-1", "Bob", now - Duration::hours(6))
+            .unwrap();
+        db.record_away_reply("chat-1", "Alice", now).unwrap();
+
+        let replies = db.away_replies_since(now - Duration::hours(4)).unwrap();
+        assert_eq!(replies.len(), 1);
+        assert_eq!(
+            (replies[0].0.as_str(), replies[0].1.as_str()),
+            ("chat-1", "Alice")
+        );
+        assert_eq!(replies[0].2.timestamp(), now.timestamp());
+    }
+}
@@ -30,7 +30,7 @@ impl Database {
     pub fn get_all_chats(&self) -> Result<Vec<UnifiedChat>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, platform, name, last_message, unread_count, kind,
-                    display_name, pinned, muted
+                    display_name, pinned, muted, archived
              FROM chats ORDER BY pinned DESC, updated_at DESC",
         )?;
 
@@ -45,6 +45,7 @@ impl Database {
                 let display_name: Option<String> = row.get(6)?;
                 let pinned: i32 = row.get(7)?;
                 let muted: i32 = row.get(8)?;
+                let archived: i32 = row.get(9)?;
                 Ok((
                     id,
                     platform_str,
@@ -55,6 +56,7 @@ impl Database {
                     display_name,
                     pinned,
                     muted,
+                    archived,
                 ))
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -72,6 +74,7 @@ impl Database {
                     display_name,
                     pinned,
                     muted,
+                    archived,
                 )| {
                     let platform = match platform_str.as_str() {
                         "WhatsApp" => Platform::WhatsApp,
@@ -89,6 +92,7 @@ impl Database {
                         kind: ChatKind::from_str(&kind_str),
                         is_pinned: pinned != 0,
                         is_muted: muted != 0,
+                        is_archived: archived != 0,
                     }
                 },
             )
@@ -105,6 +109,14 @@ impl Database {
         Ok(())
     }
 
+    pub fn set_chat_archived(&self, chat_id: &str, archived: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE chats SET archived = ?1 WHERE id = ?2",
+            rusqlite::params![archived as i32, chat_id],
+        )?;
+        Ok(())
+    }
+
     pub fn set_chat_pinned(&self, chat_id: &str, pinned: bool) -> Result<()> {
         self.conn.execute(
             "UPDATE chats SET pinned = ?1 WHERE id = ?2",
@@ -119,6 +119,26 @@ impl Database {
             ",
         )?;
 
+        // Migration: add recurrence column (JSON-encoded Recurrence, NULL for one-shots)
+        let _ = self.conn.execute(
+            "ALTER TABLE scheduled_messages ADD COLUMN recurrence TEXT",
+            [],
+        );
+
+        // Migration: add retry bookkeeping columns to scheduled_messages
+        let _ = self.conn.execute(
+            "ALTER TABLE scheduled_messages ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        let _ = self.conn.execute(
+            "ALTER TABLE scheduled_messages ADD COLUMN next_attempt_at TEXT",
+            [],
+        );
+        let _ = self.conn.execute(
+            "ALTER TABLE scheduled_messages ADD COLUMN last_error TEXT",
+            [],
+        );
+
         // Migration: add kind column
         let _ = self.conn.execute(
             "ALTER TABLE chats ADD COLUMN kind TEXT NOT NULL DEFAULT 'chat'",
@@ -179,6 +199,13 @@ impl Database {
                 .expect("chats recreate-table migration failed");
         }
 
+        // Migration: add archived column if not exists (after the recreate-table
+        // migration above, which would drop it)
+        let _ = self.conn.execute(
+            "ALTER TABLE chats ADD COLUMN archived INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+
         // Migration: create lid_pn_map table for persisting WhatsApp LID→PN JID mappings.
         // Without this, every restart loses the mapping and the same person appears twice
         // (once as wa-<phone>@s.whatsapp.net, once as wa-<lid>@lid).
@@ -189,6 +216,71 @@ impl Database {
             );",
         )?;
 
+        // Migration: create drafts table for per-chat unsent composer text.
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS drafts (
+                chat_id    TEXT PRIMARY KEY,
+                text       TEXT NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )?;
+
+        // Migration: create translations table caching AI translations of messages.
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS translations (
+                message_id TEXT NOT NULL,
+                chat_id    TEXT NOT NULL,
+                language   TEXT NOT NULL,
+                text       TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (message_id, language)
+            );
+            CREATE INDEX IF NOT EXISTS idx_translations_chat ON translations(chat_id, language);",
+        )?;
+
+        // Migration: create message_embeddings table holding vectors for semantic search.
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS message_embeddings (
+                message_id TEXT NOT NULL,
+                model      TEXT NOT NULL,
+                vector     BLOB NOT NULL,
+                PRIMARY KEY (message_id, model)
+            );",
+        )?;
+
+        // Migration: create ai_personas table for per-chat and per-kind AI prompts.
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS ai_personas (
+                scope  TEXT PRIMARY KEY,
+                prompt TEXT,
+                style  TEXT
+            );",
+        )?;
+
+        // Migration: create ai_usage table for daily AI request and token counts.
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS ai_usage (
+                day           TEXT NOT NULL,
+                provider      TEXT NOT NULL,
+                model         TEXT NOT NULL,
+                requests      INTEGER NOT NULL DEFAULT 0,
+                cached        INTEGER NOT NULL DEFAULT 0,
+                input_tokens  INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, provider, model)
+            );",
+        )?;
+
+        // Migration: create away_replies table so the away cooldown survives restarts.
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS away_replies (
+                chat_id    TEXT NOT NULL,
+                sender     TEXT NOT NULL,
+                replied_at TEXT NOT NULL,
+                PRIMARY KEY (chat_id, sender)
+            );",
+        )?;
+
         // Migration: delete stale @lid chat rows whose LID is now mapped to a PN.
         // This cleans up duplicates created before the lid_pn_map table existed.
         let _ = self.conn.execute(
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::core::Result;
+use crate::storage::db::Database;
+
+impl Database {
+    /// Persist the unsent composer text for a chat.
+    /// An empty (or whitespace-only) draft removes the row instead.
+    pub fn set_draft(&self, chat_id: &str, text: &str) -> Result<()> {
+        if text.trim().is_empty() {
+            self.conn.execute(
+                "DELETE FROM drafts WHERE chat_id = ?1",
+                rusqlite::params![chat_id],
+            )?;
+        } else {
+            self.conn.execute(
+                "INSERT INTO drafts (chat_id, text, updated_at) VALUES (?1, ?2, datetime('now'))
+                 ON CONFLICT(chat_id) DO UPDATE SET
+                   text       = excluded.text,
+                   updated_at = excluded.updated_at",
+                rusqlite::params![chat_id, text],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// All saved drafts, keyed by chat_id.
+    pub fn get_all_drafts(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare("SELECT chat_id, text FROM drafts")?;
+        let rows = stmt
+            .query_map([], |row| {
+                let chat_id: String = row.get(0)?;
+                let text: String = row.get(1)?;
+                Ok((chat_id, text))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::db::Database;
+
+    #[test]
+    fn set_and_load_draft() {
+        let db = Database::open_in_memory().unwrap();
+        db.set_draft("chat-1", "half-written\nsecond line").unwrap();
+        let drafts = db.get_all_drafts().unwrap();
+        assert_eq!(
+            drafts.get("chat-1").map(String::as_str),
+            Some("half-written\nsecond line")
+        );
+    }
+
+    #[test]
+    fn overwrite_keeps_single_row() {
+        let db = Database::open_in_memory().unwrap();
+        db.set_draft("chat-1", "first").unwrap();
+        db.set_draft("chat-1", "second").unwrap();
+        let drafts = db.get_all_drafts().unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts["chat-1"], "second");
+    }
+
+    #[test]
+    fn empty_draft_deletes_row() {
+        let db = Database::open_in_memory().unwrap();
+        db.set_draft("chat-1", "something").unwrap();
+        db.set_draft("chat-1", "   ").unwrap();
+        assert!(db.get_all_drafts().unwrap().is_empty());
+    }
+}
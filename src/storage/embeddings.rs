@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+use crate::ai::embeddings::cosine;
+use crate::core::types::MessageContent;
+use crate::core::Result;
+use crate::storage::db::Database;
+
+/// A stored message ranked by how close its meaning is to a search query.
+#[derive(Debug, Clone)]
+pub struct SemanticHit {
+    pub message_id: String,
+    pub chat_id: String,
+    pub sender: String,
+    pub text: String,
+}
+
+fn to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn from_blob(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+impl Database {
+    /// Messages with no embedding from `model` yet, newest first, leaving
+    /// out those in `excluded_chats`.
+    pub fn unembedded_messages(
+        &self,
+        model: &str,
+        limit: usize,
+        excluded_chats: &HashSet<String>,
+    ) -> Result<Vec<(String, MessageContent)>> {
+        let placeholders: Vec<String> = (0..excluded_chats.len())
+            .map(|i| format!("?{}", i + 3))
+            .collect();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT m.id, m.content FROM messages m
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM message_embeddings e
+                  WHERE e.message_id = m.id AND e.model = ?1
+             )
+             AND m.chat_id NOT IN ({})
+             ORDER BY m.rowid DESC LIMIT ?2",
+            placeholders.join(", ")
+        ))?;
+        let limit = limit as i64;
+        let mut params: Vec<&dyn rusqlite::ToSql> = vec![&model, &limit];
+        params.extend(excluded_chats.iter().map(|id| id as &dyn rusqlite::ToSql));
+        let rows = stmt
+            .query_map(params.as_slice(), |row| {
+                let id: String = row.get(0)?;
+                let content_json: String = row.get(1)?;
+                Ok((id, content_json))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, content_json)| {
+                let content = serde_json::from_str(&content_json)
+                    .unwrap_or(MessageContent::Text(content_json));
+                (id, content)
+            })
+            .collect())
+    }
+
+    /// Store message vectors from `model`. An empty vector marks a message
+    /// with nothing to embed, so the indexer does not pick it up again.
+    pub fn save_embeddings(&self, model: &str, vectors: &[(String, Vec<f32>)]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for (message_id, vector) in vectors {
+            tx.execute(
+                "INSERT OR REPLACE INTO message_embeddings (message_id, model, vector)
+                 VALUES (?1, ?2, ?3)",
+                rusqlite::params![message_id, model, to_blob(vector)],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The `limit` messages whose `model` embeddings are most similar to
+    /// `query`, best first.
+    pub fn semantic_search(
+        &self,
+        model: &str,
+        query: &[f32],
+        limit: usize,
+    ) -> Result<Vec<SemanticHit>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT message_id, vector FROM message_embeddings
+             WHERE model = ?1 AND length(vector) > 0",
+        )?;
+        let mut scored = stmt
+            .query_map(rusqlite::params![model], |row| {
+                let message_id: String = row.get(0)?;
+                let blob: Vec<u8> = row.get(1)?;
+                Ok((message_id, blob))
+            })?
+            .map(|row| row.map(|(id, blob)| (id, cosine(query, &from_blob(&blob)))))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(limit);
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT chat_id, sender, content FROM messages WHERE id = ?1")?;
+        let mut hits = Vec::with_capacity(scored.len());
+        for (message_id, _) in scored {
+            let row = stmt.query_row(rusqlite::params![message_id], |row| {
+                let chat_id: String = row.get(0)?;
+                let sender: String = row.get(1)?;
+                let content_json: String = row.get(2)?;
+                Ok((chat_id, sender, content_json))
+            });
+            // The message may have been deleted since it was embedded
+            let Ok((chat_id, sender, content_json)) = row else {
+                continue;
+            };
+            let content: MessageContent =
+                serde_json::from_str(&content_json).unwrap_or(MessageContent::Text(content_json));
+            hits.push(SemanticHit {
+                message_id,
+                chat_id,
+                sender,
+                text: content.as_text().to_string(),
+            });
+        }
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::core::types::{ChatKind, MessageStatus, Platform, UnifiedChat, UnifiedMessage};
+
+    fn message(id: &str, text: &str) -> UnifiedMessage {
+        UnifiedMessage {
+            id: id.to_string(),
+            chat_id: "chat-1".to_string(),
+            platform: Platform::Mock,
+            sender: "Alice".to_string(),
+            content: MessageContent::Text(text.to_string()),
+            timestamp: Utc::now(),
+            status: MessageStatus::Delivered,
+            is_outgoing: false,
+        }
+    }
+
+    #[test]
+    fn blobs_round_trip() {
+        let vector = [0.25, -1.5, 3.0e-7];
+        assert_eq!(from_blob(&to_blob(&vector)), vector);
+    }
+
+    #[test]
+    fn search_ranks_embedded_messages() {
+        let db = Database::open_in_memory().unwrap();
+        db.upsert_chat(&UnifiedChat {
+            id: "chat-1".to_string(),
+            platform: Platform::Mock,
+            name: "Test Chat".to_string(),
+            display_name: None,
+            last_message: None,
+            unread_count: 0,
+            kind: ChatKind::Chat,
+            is_pinned: false,
+            is_muted: false,
+            is_archived: false,
+        })
+        .unwrap();
+        for (id, text) in [("m1", "dinner at 8"), ("m2", "photo"), ("m3", "lunch")] {
+            db.insert_message(&message(id, text)).unwrap();
+        }
+        let none = HashSet::new();
+        let excluded = HashSet::from(["chat-1".to_string()]);
+        assert!(db
+            .unembedded_messages("test", 10, &excluded)
+            .unwrap()
+            .is_empty());
+        let pending: Vec<String> = db
+            .unembedded_messages("test", 10, &none)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(pending, ["m3", "m2", "m1"]);
+
+        db.save_embeddings(
+            "test",
+            &[
+                ("m1".to_string(), vec![1.0, 0.0]),
+                ("m2".to_string(), vec![]),
+                ("m3".to_string(), vec![0.6, 0.8]),
+            ],
+        )
+        .unwrap();
+        assert!(db
+            .unembedded_messages("test", 10, &none)
+            .unwrap()
+            .is_empty());
+        assert_eq!(db.unembedded_messages("other", 10, &none).unwrap().len(), 3);
+
+        let hits = db.semantic_search("test", &[0.0, 1.0], 5).unwrap();
+        let ids: Vec<&str> = hits.iter().map(|h| h.message_id.as_str()).collect();
+        assert_eq!(ids, ["m3", "m1"]);
+        assert_eq!(hits[0].text, "lunch");
+        assert_eq!(hits[0].sender, "Alice");
+    }
+}
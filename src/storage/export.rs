@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+use crate::core::types::UnifiedMessage;
+use crate::core::Result;
+use crate::storage::db::Database;
+
+impl Database {
+    /// Write a chat's full history to `<dir>/<chat name>-<timestamp>.txt`
+    /// as a plain-text transcript. Returns the path written.
+    pub fn export_chat(&self, chat_id: &str, chat_name: &str, dir: &Path) -> Result<PathBuf> {
+        let messages = self.get_messages_for_chat(chat_id)?;
+        std::fs::create_dir_all(dir)?;
+        let file_name = format!(
+            "{}-{}.txt",
+            sanitize_file_name(chat_name),
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let path = dir.join(file_name);
+        std::fs::write(&path, format_transcript(chat_name, &messages))?;
+        Ok(path)
+    }
+}
+
+fn format_transcript(chat_name: &str, messages: &[UnifiedMessage]) -> String {
+    let mut out = format!("# {}\n# {} messages\n\n", chat_name, messages.len());
+    for msg in messages {
+        let time = msg.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+        let sender = if msg.sender.is_empty() {
+            "(unknown)"
+        } else {
+            msg.sender.as_str()
+        };
+        let mut lines = msg.content.as_text().lines();
+        out.push_str(&format!(
+            "[{}] {}: {}\n",
+            time,
+            sender,
+            lines.next().unwrap_or("")
+        ));
+        // Continuation lines of multi-line messages are indented under the first
+        for line in lines {
+            out.push_str(&format!("    {}\n", line));
+        }
+    }
+    out
+}
+
+/// Replace characters that are awkward or invalid in file names.
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let trimmed = cleaned.trim_matches('_');
+    if trimmed.is_empty() {
+        "chat".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{MessageContent, MessageStatus, Platform};
+    use chrono::Utc;
+
+    fn make_msg(id: &str, sender: &str, text: &str) -> UnifiedMessage {
+        UnifiedMessage {
+            id: id.to_string(),
+            chat_id: "chat-1".to_string(),
+            platform: Platform::Mock,
+            sender: sender.to_string(),
+            content: MessageContent::Text(text.to_string()),
+            timestamp: Utc::now(),
+            status: MessageStatus::Delivered,
+            is_outgoing: false,
+        }
+    }
+
+    #[test]
+    fn transcript_indents_continuation_lines() {
+        let text = format_transcript("Alice", &[make_msg("m1", "Alice", "hi\nthere")]);
+        assert!(text.starts_with("# Alice\n# 1 messages\n"));
+        assert!(text.contains("] Alice: hi\n    there\n"));
+    }
+
+    #[test]
+    fn file_names_are_sanitized() {
+        assert_eq!(sanitize_file_name("Team / Ops: 2024"), "Team___Ops__2024");
+        assert_eq!(sanitize_file_name("***"), "chat");
+    }
+}
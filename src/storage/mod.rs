@@ -1,13 +1,23 @@
 pub mod activity;
 mod addressbook;
+mod ai_usage;
+mod away_replies;
 mod chats;
 pub mod db;
+mod drafts;
+mod embeddings;
+mod export;
 mod lid_map;
 mod messages;
+mod personas;
 mod preferences;
 mod schedule;
 mod sessions;
+mod translations;
 
 pub use addressbook::AddressBook;
+pub use ai_usage::UsageTotals;
 pub use db::Database;
+pub use embeddings::SemanticHit;
+pub use personas::{kind_scope, Persona};
 pub use schedule::ScheduledMessage;
@@ -0,0 +1,106 @@
+use rusqlite::OptionalExtension;
+
+use crate::core::types::ChatKind;
+use crate::core::Result;
+use crate::storage::db::Database;
+
+/// How the AI writes for a chat: an `[ai.prompts]` template by name and free
+/// style notes. Set per chat, or per chat kind under [`kind_scope`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Persona {
+    pub prompt: Option<String>,
+    pub style: Option<String>,
+}
+
+impl Persona {
+    /// This persona with unset fields taken from `fallback`.
+    pub fn or(self, fallback: Persona) -> Persona {
+        Persona {
+            prompt: self.prompt.or(fallback.prompt),
+            style: self.style.or(fallback.style),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prompt.is_none() && self.style.is_none()
+    }
+}
+
+/// Persona scope shared by every chat of `kind`.
+pub fn kind_scope(kind: &ChatKind) -> String {
+    format!("kind:{}", kind.as_str())
+}
+
+impl Database {
+    /// The persona stored for a chat id or [`kind_scope`]; empty when none is.
+    pub fn get_persona(&self, scope: &str) -> Result<Persona> {
+        let persona = self
+            .conn
+            .query_row(
+                "SELECT prompt, style FROM ai_personas WHERE scope = ?1",
+                rusqlite::params![scope],
+                |row| {
+                    Ok(Persona {
+                        prompt: row.get(0)?,
+                        style: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(persona.unwrap_or_default())
+    }
+
+    /// Store the persona for a scope; an empty persona removes it.
+    pub fn set_persona(&self, scope: &str, persona: &Persona) -> Result<()> {
+        if persona.is_empty() {
+            self.conn.execute(
+                "DELETE FROM ai_personas WHERE scope = ?1",
+                rusqlite::params![scope],
+            )?;
+        } else {
+            self.conn.execute(
+                "INSERT INTO ai_personas (scope, prompt, style) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(scope) DO UPDATE SET prompt = excluded.prompt, style = excluded.style",
+                rusqlite::params![scope, persona.prompt, persona.style],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_persona_falls_back_to_its_kind() {
+        let db = Database::open_in_memory().unwrap();
+        let group = kind_scope(&ChatKind::Group);
+        db.set_persona(
+            &group,
+            &Persona {
+                prompt: Some("friends".to_string()),
+                style: Some("lowercase, lots of emoji".to_string()),
+            },
+        )
+        .unwrap();
+        db.set_persona(
+            "chat-1",
+            &Persona {
+                prompt: None,
+                style: Some("formal".to_string()),
+            },
+        )
+        .unwrap();
+
+        let persona = db
+            .get_persona("chat-1")
+            .unwrap()
+            .or(db.get_persona(&group).unwrap());
+        assert_eq!(persona.prompt.as_deref(), Some("friends"));
+        assert_eq!(persona.style.as_deref(), Some("formal"));
+
+        db.set_persona("chat-1", &Persona::default()).unwrap();
+        assert!(db.get_persona("chat-1").unwrap().is_empty());
+    }
+}
@@ -22,4 +22,41 @@ impl Database {
         )?;
         Ok(())
     }
+
+    /// Every preference whose key starts with `prefix`, keyed by the rest of the key.
+    pub fn get_preferences_with_prefix(&self, prefix: &str) -> Result<Vec<(String, String)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT key, value FROM preferences WHERE substr(key, 1, length(?1)) = ?1")?;
+        let rows = stmt
+            .query_map(rusqlite::params![prefix], |row| {
+                let key: String = row.get(0)?;
+                let value: String = row.get(1)?;
+                Ok((key[prefix.len()..].to_string(), value))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::db::Database;
+
+    #[test]
+    fn preferences_by_prefix() {
+        let db = Database::open_in_memory().unwrap();
+        db.set_preference("auto_translate:chat-1", "true").unwrap();
+        db.set_preference("auto_translate:chat-2", "false").unwrap();
+        db.set_preference("auto_translated", "x").unwrap();
+        let mut prefs = db.get_preferences_with_prefix("auto_translate:").unwrap();
+        prefs.sort();
+        assert_eq!(
+            prefs,
+            [
+                ("chat-1".to_string(), "true".to_string()),
+                ("chat-2".to_string(), "false".to_string())
+            ]
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::core::recurrence::Recurrence;
 use crate::core::types::{MessageContent, Platform};
 use crate::core::Result;
 use crate::storage::db::Database;
@@ -10,10 +11,31 @@ pub struct ScheduledMessage {
     pub platform: Platform,
     pub content: MessageContent,
     pub send_at: DateTime<Utc>,
+    /// `pending`, `sent`, `cancelled`, `skipped`, `missed` (came due while the
+    /// app was closed, awaiting review) or `failed` (retries exhausted).
     pub status: String,
     pub created_at: DateTime<Utc>,
+    /// `None` for one-shot messages. Recurring messages stay pending and move
+    /// `send_at` to the next occurrence after each send.
+    pub recurrence: Option<Recurrence>,
+    /// Failed send attempts since the last successful send.
+    pub attempts: u32,
+    /// When the next retry is due; overrides `send_at` while set.
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
 }
 
+impl ScheduledMessage {
+    /// When the message should next be tried.
+    pub fn due_at(&self) -> DateTime<Utc> {
+        self.next_attempt_at.unwrap_or(self.send_at)
+    }
+}
+
+const SELECT_COLUMNS: &str = "SELECT id, chat_id, platform, content, send_at, status, created_at,
+        recurrence, attempts, next_attempt_at, last_error
+     FROM scheduled_messages";
+
 struct ScheduledMessageRow {
     id: String,
     chat_id: String,
@@ -22,6 +44,26 @@ struct ScheduledMessageRow {
     send_at_str: String,
     status: String,
     created_at_str: String,
+    recurrence_json: Option<String>,
+    attempts: u32,
+    next_attempt_at_str: Option<String>,
+    last_error: Option<String>,
+}
+
+fn read_scheduled_row(row: &rusqlite::Row) -> rusqlite::Result<ScheduledMessageRow> {
+    Ok(ScheduledMessageRow {
+        id: row.get(0)?,
+        chat_id: row.get(1)?,
+        platform_str: row.get(2)?,
+        content_json: row.get(3)?,
+        send_at_str: row.get(4)?,
+        status: row.get(5)?,
+        created_at_str: row.get(6)?,
+        recurrence_json: row.get(7)?,
+        attempts: row.get(8)?,
+        next_attempt_at_str: row.get(9)?,
+        last_error: row.get(10)?,
+    })
 }
 
 fn parse_platform(s: &str) -> Platform {
@@ -43,6 +85,13 @@ fn parse_scheduled_row(row: ScheduledMessageRow) -> ScheduledMessage {
     let created_at = DateTime::parse_from_rfc3339(&row.created_at_str)
         .map(|dt| dt.with_timezone(&Utc))
         .unwrap_or_else(|_| Utc::now());
+    let recurrence = row
+        .recurrence_json
+        .and_then(|json| serde_json::from_str(&json).ok());
+    let next_attempt_at = row
+        .next_attempt_at_str
+        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
 
     ScheduledMessage {
         id: row.id,
@@ -52,6 +101,10 @@ fn parse_scheduled_row(row: ScheduledMessageRow) -> ScheduledMessage {
         send_at,
         status: row.status,
         created_at,
+        recurrence,
+        attempts: row.attempts,
+        next_attempt_at,
+        last_error: row.last_error,
     }
 }
 
@@ -61,10 +114,16 @@ impl Database {
         let platform_str = format!("{:?}", msg.platform);
         let send_at_str = msg.send_at.to_rfc3339();
         let created_at_str = msg.created_at.to_rfc3339();
+        let recurrence_json = msg
+            .recurrence
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
 
         self.conn.execute(
-            "INSERT INTO scheduled_messages (id, chat_id, platform, content, send_at, status, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO scheduled_messages
+               (id, chat_id, platform, content, send_at, status, created_at, recurrence)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             rusqlite::params![
                 msg.id,
                 msg.chat_id,
@@ -73,62 +132,109 @@ impl Database {
                 send_at_str,
                 msg.status,
                 created_at_str,
+                recurrence_json,
             ],
         )?;
         Ok(())
     }
 
+    /// Pending messages whose send time, or retry time, has passed.
     pub fn get_due_scheduled_messages(&self) -> Result<Vec<ScheduledMessage>> {
         let now = Utc::now().to_rfc3339();
-        let mut stmt = self.conn.prepare(
-            "SELECT id, chat_id, platform, content, send_at, status, created_at
-             FROM scheduled_messages
-             WHERE status = 'pending' AND send_at <= ?1
-             ORDER BY send_at ASC",
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "{SELECT_COLUMNS}
+             WHERE status = 'pending' AND COALESCE(next_attempt_at, send_at) <= ?1
+             ORDER BY COALESCE(next_attempt_at, send_at) ASC"
+        ))?;
 
         let rows = stmt
-            .query_map(rusqlite::params![now], |row| {
-                Ok(ScheduledMessageRow {
-                    id: row.get(0)?,
-                    chat_id: row.get(1)?,
-                    platform_str: row.get(2)?,
-                    content_json: row.get(3)?,
-                    send_at_str: row.get(4)?,
-                    status: row.get(5)?,
-                    created_at_str: row.get(6)?,
-                })
-            })?
+            .query_map(rusqlite::params![now], read_scheduled_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(rows.into_iter().map(parse_scheduled_row).collect())
     }
 
+    /// Messages not sent yet: pending ones plus missed and failed ones held for review.
     pub fn get_pending_scheduled_messages(&self) -> Result<Vec<ScheduledMessage>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, chat_id, platform, content, send_at, status, created_at
-             FROM scheduled_messages
-             WHERE status = 'pending'
-             ORDER BY send_at ASC",
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "{SELECT_COLUMNS}
+             WHERE status IN ('pending', 'missed', 'failed')
+             ORDER BY send_at ASC"
+        ))?;
 
         let rows = stmt
-            .query_map([], |row| {
-                Ok(ScheduledMessageRow {
-                    id: row.get(0)?,
-                    chat_id: row.get(1)?,
-                    platform_str: row.get(2)?,
-                    content_json: row.get(3)?,
-                    send_at_str: row.get(4)?,
-                    status: row.get(5)?,
-                    created_at_str: row.get(6)?,
-                })
-            })?
+            .query_map([], read_scheduled_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(rows.into_iter().map(parse_scheduled_row).collect())
     }
 
+    /// Earliest time a pending message is due, for arming the scheduler timer.
+    pub fn next_scheduled_due(&self) -> Result<Option<DateTime<Utc>>> {
+        let next: Option<String> = self.conn.query_row(
+            "SELECT MIN(COALESCE(next_attempt_at, send_at))
+             FROM scheduled_messages WHERE status = 'pending'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(next
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)))
+    }
+
+    /// Save an edited message: content, send time, repeat rule, status and retry state.
+    pub fn update_scheduled_message(&self, msg: &ScheduledMessage) -> Result<()> {
+        let content_json = serde_json::to_string(&msg.content)?;
+        let recurrence_json = msg
+            .recurrence
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        self.conn.execute(
+            "UPDATE scheduled_messages
+             SET content = ?1, send_at = ?2, recurrence = ?3, status = ?4,
+                 attempts = ?5, next_attempt_at = ?6, last_error = ?7
+             WHERE id = ?8",
+            rusqlite::params![
+                content_json,
+                msg.send_at.to_rfc3339(),
+                recurrence_json,
+                msg.status,
+                msg.attempts,
+                msg.next_attempt_at.map(|t| t.to_rfc3339()),
+                msg.last_error,
+                msg.id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Move a message to `send_at`, e.g. the next occurrence of a recurring
+    /// one, clearing any retry state.
+    pub fn update_scheduled_send_at(&self, id: &str, send_at: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE scheduled_messages
+             SET send_at = ?1, attempts = 0, next_attempt_at = NULL, last_error = NULL
+             WHERE id = ?2",
+            rusqlite::params![send_at.to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_scheduled_recurrence(
+        &self,
+        id: &str,
+        recurrence: Option<&Recurrence>,
+        send_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let recurrence_json = recurrence.map(serde_json::to_string).transpose()?;
+        self.conn.execute(
+            "UPDATE scheduled_messages SET recurrence = ?1, send_at = ?2 WHERE id = ?3",
+            rusqlite::params![recurrence_json, send_at.to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
     pub fn update_scheduled_status(&self, id: &str, status: &str) -> Result<()> {
         self.conn.execute(
             "UPDATE scheduled_messages SET status = ?1 WHERE id = ?2",
@@ -136,6 +242,47 @@ impl Database {
         )?;
         Ok(())
     }
+
+    /// Record a failed send: retry at `next_attempt_at`, or give up with
+    /// status `failed` when it is `None`.
+    pub fn record_scheduled_failure(
+        &self,
+        id: &str,
+        attempts: u32,
+        next_attempt_at: Option<DateTime<Utc>>,
+        error: &str,
+    ) -> Result<()> {
+        let status = if next_attempt_at.is_some() {
+            "pending"
+        } else {
+            "failed"
+        };
+        self.conn.execute(
+            "UPDATE scheduled_messages
+             SET status = ?1, attempts = ?2, next_attempt_at = ?3, last_error = ?4
+             WHERE id = ?5",
+            rusqlite::params![
+                status,
+                attempts,
+                next_attempt_at.map(|t| t.to_rfc3339()),
+                error,
+                id
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Queue a message to go out at `at` with a fresh set of retries. Works for
+    /// missed and failed messages as well as pending ones.
+    pub fn requeue_scheduled_message(&self, id: &str, at: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE scheduled_messages
+             SET status = 'pending', attempts = 0, next_attempt_at = ?1, last_error = NULL
+             WHERE id = ?2",
+            rusqlite::params![at.to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +303,7 @@ mod tests {
             kind: crate::core::types::ChatKind::Chat,
             is_pinned: false,
             is_muted: false,
+            is_archived: false,
         })
         .unwrap();
         db
@@ -170,6 +318,10 @@ mod tests {
             send_at,
             status: "pending".to_string(),
             created_at: Utc::now(),
+            recurrence: None,
+            attempts: 0,
+            next_attempt_at: None,
+            last_error: None,
         }
     }
 
@@ -224,4 +376,73 @@ mod tests {
         assert_eq!(pending.len(), 1);
         assert_eq!(pending[0].id, "sched-4");
     }
+
+    #[test]
+    fn recurrence_round_trips_and_reschedules() {
+        let db = setup_db();
+        let past = Utc::now() - Duration::minutes(5);
+        let mut msg = make_test_message("sched-5", past);
+        msg.recurrence = Some(Recurrence::Weekly(chrono::Weekday::Mon));
+        db.insert_scheduled_message(&msg).unwrap();
+
+        let due = db.get_due_scheduled_messages().unwrap();
+        assert_eq!(due[0].recurrence, msg.recurrence);
+
+        let next = Utc::now() + Duration::days(3);
+        db.update_scheduled_send_at("sched-5", next).unwrap();
+        assert!(db.get_due_scheduled_messages().unwrap().is_empty());
+
+        db.update_scheduled_recurrence("sched-5", None, past)
+            .unwrap();
+        let due = db.get_due_scheduled_messages().unwrap();
+        assert_eq!(due[0].recurrence, None);
+    }
+
+    #[test]
+    fn edits_are_saved() {
+        let db = setup_db();
+        let future = Utc::now() + Duration::hours(1);
+        let mut msg = make_test_message("sched-7", future);
+        db.insert_scheduled_message(&msg).unwrap();
+
+        msg.content = MessageContent::Text("Edited".to_string());
+        msg.send_at = Utc::now() - Duration::minutes(1);
+        msg.recurrence = Some(Recurrence::Daily);
+        db.update_scheduled_message(&msg).unwrap();
+
+        let due = db.get_due_scheduled_messages().unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].content.as_text(), "Edited");
+        assert_eq!(due[0].recurrence, Some(Recurrence::Daily));
+    }
+
+    #[test]
+    fn failures_back_off_then_fail() {
+        let db = setup_db();
+        let past = Utc::now() - Duration::minutes(5);
+        db.insert_scheduled_message(&make_test_message("sched-6", past))
+            .unwrap();
+
+        let retry_at = Utc::now() + Duration::seconds(30);
+        db.record_scheduled_failure("sched-6", 1, Some(retry_at), "offline")
+            .unwrap();
+        assert!(db.get_due_scheduled_messages().unwrap().is_empty());
+        let next = db.next_scheduled_due().unwrap().unwrap();
+        assert_eq!(next.timestamp(), retry_at.timestamp());
+
+        db.record_scheduled_failure("sched-6", 5, None, "offline")
+            .unwrap();
+        assert_eq!(db.next_scheduled_due().unwrap(), None);
+        let listed = db.get_pending_scheduled_messages().unwrap();
+        assert_eq!(listed[0].status, "failed");
+        assert_eq!(listed[0].attempts, 5);
+        assert_eq!(listed[0].last_error.as_deref(), Some("offline"));
+
+        db.requeue_scheduled_message("sched-6", Utc::now()).unwrap();
+        let due = db.get_due_scheduled_messages().unwrap();
+        assert_eq!(due[0].attempts, 0);
+        assert_eq!(due[0].last_error, None);
+        // The original send time is kept as the recurrence anchor
+        assert_eq!(due[0].send_at.timestamp(), past.timestamp());
+    }
 }
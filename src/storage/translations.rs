@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::core::Result;
+use crate::storage::db::Database;
+
+impl Database {
+    /// Cache the translation of a message into `language`.
+    pub fn save_translation(
+        &self,
+        chat_id: &str,
+        message_id: &str,
+        language: &str,
+        text: &str,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO translations (message_id, chat_id, language, text)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(message_id, language) DO UPDATE SET
+               text       = excluded.text,
+               created_at = datetime('now')",
+            rusqlite::params![message_id, chat_id, language, text],
+        )?;
+        Ok(())
+    }
+
+    /// Cached translations into `language` of a chat's messages, keyed by message id.
+    pub fn get_translations_for_chat(
+        &self,
+        chat_id: &str,
+        language: &str,
+    ) -> Result<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT message_id, text FROM translations WHERE chat_id = ?1 AND language = ?2",
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![chat_id, language], |row| {
+                let message_id: String = row.get(0)?;
+                let text: String = row.get(1)?;
+                Ok((message_id, text))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::db::Database;
+
+    #[test]
+    fn translations_are_cached_per_language() {
+        let db = Database::open_in_memory().unwrap();
+        db.save_translation("chat-1", "m1", "English", "Good morning")
+            .unwrap();
+        db.save_translation("chat-1", "m1", "German", "Guten Morgen")
+            .unwrap();
+        db.save_translation("chat-1", "m1", "English", "Morning!")
+            .unwrap();
+        db.save_translation("chat-2", "m2", "English", "Hello")
+            .unwrap();
+
+        let english = db.get_translations_for_chat("chat-1", "English").unwrap();
+        assert_eq!(english.len(), 1);
+        assert_eq!(english["m1"], "Morning!");
+        assert_eq!(
+            db.get_translations_for_chat("chat-1", "German").unwrap()["m1"],
+            "Guten Morgen"
+        );
+    }
+}
@@ -1,12 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 use std::time::Instant;
 
 use ratatui::widgets::ListState;
-use tui_textarea::TextArea;
+use tui_textarea::{CursorMove, TextArea};
 
+use crate::ai::redact::SentRequest;
 use crate::config::AppConfig;
-use crate::core::types::{Platform, UnifiedChat, UnifiedMessage};
-use crate::storage::ScheduledMessage;
+use crate::core::folders::{builtin_folders, ChatFolder};
+use crate::core::types::{ChatKind, Platform, UnifiedChat, UnifiedMessage};
+use crate::storage::{Persona, ScheduledMessage, SemanticHit, UsageTotals};
+use crate::tui::image_preview::ImagePreviews;
+use crate::tui::keybindings::{Action, HelpSection};
+use crate::tui::mouse::HitMap;
+use crate::tui::palette::{filter_entries, PaletteEntry};
+use crate::tui::theme::{available_themes, Theme};
 
 /// Tracks a contact who is currently typing in a chat.
 #[derive(Debug, Clone)]
@@ -16,7 +24,7 @@ pub struct TypingInfo {
     pub expires_at: Instant,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InputMode {
     Normal,
     Editing,
@@ -28,6 +36,11 @@ pub enum InputMode {
     SchedulePrompt,
     ScheduleList,
     TelegramAuth,
+    Help,
+    Palette,
+    CatchUp,
+    StyleNotes,
+    SentRequests,
 }
 
 // --- Settings overlay types ---
@@ -39,6 +52,9 @@ pub enum SettingsKey {
     LogLevel,
     EnterSends,
     ActivityGraph,
+    Theme,
+    Away,
+    AiModel,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -74,6 +90,16 @@ impl SettingsState {
             .position(|l| l == &config.general.log_level)
             .unwrap_or(2);
 
+        let mut theme_choices = available_themes(Path::new(&config.general.data_dir));
+        let theme_idx = match theme_choices.iter().position(|t| t == &config.tui.theme) {
+            Some(i) => i,
+            None => {
+                // Keep a configured theme selectable even if its file is missing
+                theme_choices.push(config.tui.theme.clone());
+                theme_choices.len() - 1
+            }
+        };
+
         Self {
             items: vec![
                 SettingsItem {
@@ -101,6 +127,21 @@ impl SettingsState {
                     label: "Activity Graph".to_string(),
                     value: SettingsValue::Bool(true),
                 },
+                SettingsItem {
+                    key: SettingsKey::Theme,
+                    label: "Theme".to_string(),
+                    value: SettingsValue::Choice(theme_choices, theme_idx),
+                },
+                SettingsItem {
+                    key: SettingsKey::Away,
+                    label: "Away Auto-reply".to_string(),
+                    value: SettingsValue::Bool(config.away.enabled),
+                },
+                SettingsItem {
+                    key: SettingsKey::AiModel,
+                    label: "AI Model".to_string(),
+                    value: SettingsValue::Choice(vec![config.ai.model.clone()], 0),
+                },
             ],
             selected: 0,
             dirty: false,
@@ -131,6 +172,28 @@ impl SettingsState {
         }
     }
 
+    /// Offer `models` for the AI model, keeping the current pick selected.
+    pub fn set_ai_models(&mut self, mut models: Vec<String>) {
+        let Some(item) = self
+            .items
+            .iter_mut()
+            .find(|i| i.key == SettingsKey::AiModel)
+        else {
+            return;
+        };
+        if let SettingsValue::Choice(choices, idx) = &item.value {
+            let current = choices[*idx].clone();
+            let idx = match models.iter().position(|m| *m == current) {
+                Some(i) => i,
+                None => {
+                    models.push(current);
+                    models.len() - 1
+                }
+            };
+            item.value = SettingsValue::Choice(models, idx);
+        }
+    }
+
     pub fn apply_to_config(&self, config: &mut AppConfig) {
         for item in &self.items {
             match (&item.key, &item.value) {
@@ -143,6 +206,15 @@ impl SettingsState {
                 (SettingsKey::LogLevel, SettingsValue::Choice(choices, idx)) => {
                     config.general.log_level = choices[*idx].clone();
                 }
+                (SettingsKey::Theme, SettingsValue::Choice(choices, idx)) => {
+                    config.tui.theme = choices[*idx].clone();
+                }
+                (SettingsKey::Away, SettingsValue::Bool(v)) => {
+                    config.away.enabled = *v;
+                }
+                (SettingsKey::AiModel, SettingsValue::Choice(choices, idx)) => {
+                    config.ai.model = choices[*idx].clone();
+                }
                 (SettingsKey::EnterSends, _) => {
                     // stored in SQLite preferences, not TOML config
                 }
@@ -153,29 +225,85 @@ impl SettingsState {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum ChatMenuItem {
     TogglePin,
     ToggleMute,
+    ToggleArchive,
+    ToggleAutoTranslate,
+    CyclePrompt,   // next `[ai.prompts]` template for this chat
+    EditStyle,     // edit the chat's AI style notes
+    ShareWithKind, // apply this chat's persona to every chat of its kind
+    ToggleAiExcluded,
 }
 
 impl ChatMenuItem {
-    pub fn label(&self, is_pinned: bool, is_muted: bool) -> &'static str {
-        match self {
+    pub fn label(&self, menu: &ChatMenuState) -> String {
+        let label = match self {
             ChatMenuItem::TogglePin => {
-                if is_pinned {
+                if menu.is_pinned {
                     "Unpin"
                 } else {
                     "Pin"
                 }
             }
             ChatMenuItem::ToggleMute => {
-                if is_muted {
+                if menu.is_muted {
                     "Unmute"
                 } else {
                     "Mute"
                 }
             }
-        }
+            ChatMenuItem::ToggleArchive => {
+                if menu.is_archived {
+                    "Unarchive"
+                } else {
+                    "Archive"
+                }
+            }
+            ChatMenuItem::ToggleAutoTranslate => {
+                if menu.auto_translate {
+                    "Stop auto-translating"
+                } else {
+                    "Auto-translate incoming"
+                }
+            }
+            ChatMenuItem::CyclePrompt => {
+                return format!(
+                    "AI prompt: {}",
+                    menu.persona.prompt.as_deref().unwrap_or("none")
+                );
+            }
+            ChatMenuItem::EditStyle => {
+                if menu.persona.style.is_some() {
+                    "Edit AI style notes"
+                } else {
+                    "Add AI style notes"
+                }
+            }
+            ChatMenuItem::ShareWithKind => {
+                return format!("Use AI persona for all {}", kind_plural(&menu.kind));
+            }
+            ChatMenuItem::ToggleAiExcluded => {
+                if menu.ai_excluded {
+                    "Allow sending to AI"
+                } else {
+                    "Never send to AI"
+                }
+            }
+        };
+        label.to_string()
+    }
+}
+
+/// How a chat kind is named in menus, e.g. "all groups".
+pub fn kind_plural(kind: &ChatKind) -> &'static str {
+    match kind {
+        ChatKind::Chat => "DMs",
+        ChatKind::Group => "groups",
+        ChatKind::Channel => "channels",
+        ChatKind::Newsletter => "newsletters",
+        ChatKind::Bot => "bots",
     }
 }
 
@@ -184,22 +312,67 @@ pub struct ChatMenuState {
     pub chat_name: String,
     pub is_pinned: bool,
     pub is_muted: bool,
+    pub is_archived: bool,
+    pub auto_translate: bool,
+    pub ai_excluded: bool,
+    pub kind: ChatKind,
+    /// The chat's own AI persona, not including its kind's.
+    pub persona: Persona,
+    /// `[ai.prompts]` template names `CyclePrompt` steps through.
+    pub prompt_names: Vec<String>,
     pub selected: usize,
     pub items: Vec<ChatMenuItem>,
 }
 
 impl ChatMenuState {
-    pub fn new(chat_id: String, chat_name: String, is_pinned: bool, is_muted: bool) -> Self {
+    pub fn new(chat: &UnifiedChat, auto_translate: bool, ai_excluded: bool) -> Self {
         Self {
-            chat_id,
-            chat_name,
-            is_pinned,
-            is_muted,
+            chat_id: chat.id.clone(),
+            chat_name: chat
+                .display_name
+                .clone()
+                .unwrap_or_else(|| chat.name.clone()),
+            is_pinned: chat.is_pinned,
+            is_muted: chat.is_muted,
+            is_archived: chat.is_archived,
+            auto_translate,
+            ai_excluded,
+            kind: chat.kind.clone(),
+            persona: Persona::default(),
+            prompt_names: Vec::new(),
             selected: 0,
-            items: vec![ChatMenuItem::TogglePin, ChatMenuItem::ToggleMute],
+            items: vec![
+                ChatMenuItem::TogglePin,
+                ChatMenuItem::ToggleMute,
+                ChatMenuItem::ToggleArchive,
+                ChatMenuItem::ToggleAutoTranslate,
+            ],
         }
     }
 
+    /// Add the AI persona entries, for when AI is enabled.
+    pub fn add_persona_items(&mut self, persona: Persona, prompt_names: Vec<String>) {
+        self.persona = persona;
+        self.prompt_names = prompt_names;
+        self.items.extend([
+            ChatMenuItem::CyclePrompt,
+            ChatMenuItem::EditStyle,
+            ChatMenuItem::ShareWithKind,
+        ]);
+    }
+
+    /// The template after the current one, wrapping round to none.
+    pub fn next_prompt(&self) -> Option<String> {
+        let next = match &self.persona.prompt {
+            None => 0,
+            Some(name) => match self.prompt_names.iter().position(|n| n == name) {
+                Some(i) => i + 1,
+                None => 0,
+            },
+        };
+        self.prompt_names.get(next).cloned()
+    }
+
     pub fn select_next(&mut self) {
         if self.selected < self.items.len() - 1 {
             self.selected += 1;
@@ -213,11 +386,25 @@ impl ChatMenuState {
     }
 }
 
+/// What the search overlay looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Chat names, fuzzy-matched as you type.
+    Chats,
+    /// Message history, ranked by embedding similarity to the query.
+    Semantic,
+}
+
 #[derive(Debug, Clone)]
 pub struct SearchState {
     pub query: String,
     pub results: Vec<usize>, // indices into AppState::chats (top 5)
     pub selected: usize,     // currently highlighted result index
+    pub mode: SearchMode,
+    /// Messages matching the query in semantic mode, best first.
+    pub hits: Vec<SemanticHit>,
+    /// A semantic query is being embedded.
+    pub searching: bool,
 }
 
 impl SearchState {
@@ -226,8 +413,60 @@ impl SearchState {
             query: String::new(),
             results: vec![],
             selected: 0,
+            mode: SearchMode::Chats,
+            hits: vec![],
+            searching: false,
+        }
+    }
+
+    /// Number of results listed in the current mode.
+    pub fn result_count(&self) -> usize {
+        match self.mode {
+            SearchMode::Chats => self.results.len(),
+            SearchMode::Semantic => self.hits.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteState {
+    pub query: String,
+    pub entries: Vec<PaletteEntry>,
+    pub results: Vec<usize>, // indices into `entries`, best match first
+    pub selected: usize,
+}
+
+impl PaletteState {
+    pub fn new(entries: Vec<PaletteEntry>) -> Self {
+        let results = filter_entries("", &entries);
+        Self {
+            query: String::new(),
+            entries,
+            results,
+            selected: 0,
         }
     }
+
+    pub fn refilter(&mut self) {
+        self.results = filter_entries(&self.query, &self.entries);
+        self.selected = self.selected.min(self.results.len().saturating_sub(1));
+    }
+
+    pub fn selected_action(&self) -> Option<Action> {
+        let idx = *self.results.get(self.selected)?;
+        Some(self.entries[idx].action.clone())
+    }
+}
+
+/// What the schedule prompt's input is for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleTarget {
+    /// Send time of a new message.
+    NewMessage,
+    /// Repeat rule of the scheduled message with this id.
+    Recurrence(String),
+    /// Send time of the scheduled message with this id.
+    SendTime(String),
 }
 
 #[derive(Debug, Clone)]
@@ -236,6 +475,7 @@ pub struct SchedulePromptState {
     pub message_text: String, // the message to be scheduled
     pub chat_id: String,
     pub platform: Platform,
+    pub target: ScheduleTarget,
 }
 
 impl SchedulePromptState {
@@ -245,6 +485,31 @@ impl SchedulePromptState {
             message_text,
             chat_id,
             platform,
+            target: ScheduleTarget::NewMessage,
+        }
+    }
+
+    /// Edit the repeat rule of `msg`, starting from its current one.
+    pub fn for_recurrence(msg: &ScheduledMessage) -> Self {
+        Self {
+            query: msg
+                .recurrence
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or_default(),
+            message_text: msg.content.as_text().to_string(),
+            chat_id: msg.chat_id.clone(),
+            platform: msg.platform,
+            target: ScheduleTarget::Recurrence(msg.id.clone()),
+        }
+    }
+
+    /// Move `msg` to a new send time.
+    pub fn for_send_time(msg: &ScheduledMessage) -> Self {
+        Self {
+            target: ScheduleTarget::SendTime(msg.id.clone()),
+            query: String::new(),
+            ..Self::for_recurrence(msg)
         }
     }
 }
@@ -276,6 +541,103 @@ impl ScheduleListState {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct HelpState {
+    pub sections: Vec<HelpSection>,
+    pub scroll: u16,
+}
+
+impl HelpState {
+    pub fn new(sections: Vec<HelpSection>) -> Self {
+        Self {
+            sections,
+            scroll: 0,
+        }
+    }
+
+    pub fn scroll_down(&mut self) {
+        let lines: usize = self.sections.iter().map(|s| s.entries.len() + 2).sum();
+        if (self.scroll as usize) + 1 < lines {
+            self.scroll += 1;
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+/// Requests kept for the "Sent to AI" overlay.
+const MAX_SENT_REQUESTS: usize = 10;
+
+/// The "Sent to AI" overlay: recent AI requests exactly as they were sent.
+#[derive(Debug, Clone)]
+pub struct SentRequestsState {
+    /// Newest first.
+    pub requests: Vec<SentRequest>,
+    pub scroll: u16,
+}
+
+impl SentRequestsState {
+    pub fn new(requests: Vec<SentRequest>) -> Self {
+        Self {
+            requests,
+            scroll: 0,
+        }
+    }
+
+    /// Scroll down a row; rendering clamps this to the last page, which
+    /// depends on how the requests wrap.
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+/// One chat in the catch-me-up overlay.
+#[derive(Debug, Clone)]
+pub struct CatchUpEntry {
+    pub chat_id: String,
+    pub chat_name: String,
+    pub unread: usize,
+    /// `None` while the summary is being written.
+    pub bullets: Option<Result<Vec<String>, String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CatchUpState {
+    pub entries: Vec<CatchUpEntry>,
+    pub selected: usize,
+}
+
+impl CatchUpState {
+    pub fn new(entries: Vec<CatchUpEntry>) -> Self {
+        Self {
+            entries,
+            selected: 0,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn set_bullets(&mut self, chat_id: &str, bullets: Result<Vec<String>, String>) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.chat_id == chat_id) {
+            entry.bullets = Some(bullets);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TelegramAuthStage {
     Phone,
@@ -335,6 +697,9 @@ pub struct AppState {
     pub chats: Vec<UnifiedChat>,
     pub messages: Vec<UnifiedMessage>,
     pub chat_list_state: ListState,
+    /// Chat list tabs: built-ins, then `[[folders]]`, then imported ones.
+    pub folders: Vec<ChatFolder>,
+    pub active_folder: usize,
     pub active_panel: ActivePanel,
     pub input_mode: InputMode,
     pub input: TextArea<'static>,
@@ -343,15 +708,27 @@ pub struct AppState {
     pub qr_code: Option<String>,
     pub whatsapp_connected: bool,
     pub mock_enabled: bool,
+    /// Away auto-reply is on; shows the AWAY badge.
+    pub away: bool,
     pub settings_state: Option<SettingsState>,
     pub chat_menu_state: Option<ChatMenuState>,
     pub search_state: Option<SearchState>,
     pub telegram_auth_state: Option<TelegramAuthState>,
     pub ai_suggestion: Option<String>,
+    /// Suggested replies to the latest incoming message in the open chat.
+    pub smart_replies: Vec<String>,
     pub ai_status: Option<String>,
     pub ai_debug: bool,
     pub ai_debug_log: Vec<String>,
+    /// Whether an AI worker is running; the status bar shows the backend if so.
+    pub ai_enabled: bool,
+    /// Today's AI requests and tokens, shown in the status bar while AI is on.
+    pub ai_usage: UsageTotals,
+    /// The AI backend answering requests; `None` while none is reachable.
+    pub ai_backend: Option<String>,
     pub enter_sends: bool,
+    /// Status bar key hints for the current mode, from the active keymap.
+    pub key_hints: String,
     pub show_activity_graph: bool,
     /// Number of unread messages at the tail of `messages` when a chat was opened.
     pub new_message_count: usize,
@@ -361,7 +738,17 @@ pub struct AppState {
     pub selected_message_idx: Option<usize>,
     pub schedule_prompt_state: Option<SchedulePromptState>,
     pub schedule_list_state: Option<ScheduleListState>,
-    pub schedule_status: Option<String>, // flash message for scheduling feedback
+    /// Id of the scheduled message whose text is open in the composer.
+    pub editing_scheduled: Option<String>,
+    pub flash_status: Option<String>, // short-lived feedback (scheduling, export, …)
+    pub palette_state: Option<PaletteState>,
+    pub help_state: Option<HelpState>,
+    pub catch_up_state: Option<CatchUpState>,
+    pub theme: Theme,
+    /// Inline thumbnails for image messages, keyed by message id.
+    pub image_previews: ImagePreviews,
+    /// Click targets of the last rendered frame.
+    pub hit_map: HitMap,
     /// Per-chat typing indicators: chat_id → who is typing and when it expires.
     pub typing_states: HashMap<String, TypingInfo>,
     /// Running-light phase: 0=first dot lit, 1=middle, 2=last. Cycles every 2 ticks (~500ms/step).
@@ -371,6 +758,17 @@ pub struct AppState {
     pub activity_cache: std::collections::HashMap<String, [u32; 24]>,
     /// `tick_count` value when the last full SQL activity refresh ran.
     pub activity_last_refresh_tick: u64,
+    /// Unsent composer text per chat: chat_id → draft. Mirrors the `drafts` table.
+    pub drafts: HashMap<String, String>,
+    /// Cached translations of the open chat's messages, keyed by message id.
+    pub translations: HashMap<String, String>,
+    /// Chats whose incoming messages are translated as they arrive.
+    pub auto_translate: HashSet<String>,
+    /// Chats never sent to the AI provider or the embedding indexer.
+    pub ai_excluded: HashSet<String>,
+    /// The latest requests sent to the AI provider, newest last.
+    pub sent_requests: VecDeque<SentRequest>,
+    pub sent_requests_state: Option<SentRequestsState>,
 }
 
 impl AppState {
@@ -382,6 +780,8 @@ impl AppState {
             chats: Vec::new(),
             messages: Vec::new(),
             chat_list_state,
+            folders: builtin_folders(),
+            active_folder: 0,
             active_panel: ActivePanel::ChatList,
             input_mode: InputMode::Normal,
             input: TextArea::default(),
@@ -390,72 +790,153 @@ impl AppState {
             qr_code: None,
             whatsapp_connected: false,
             mock_enabled: false,
+            away: false,
             settings_state: None,
             chat_menu_state: None,
             search_state: None,
             telegram_auth_state: None,
             ai_suggestion: None,
+            smart_replies: Vec::new(),
             ai_status: None,
             ai_debug: false,
             ai_debug_log: Vec::new(),
+            ai_enabled: false,
+            ai_usage: UsageTotals::default(),
+            ai_backend: None,
             enter_sends: true,
+            key_hints: String::new(),
             show_activity_graph: true,
             new_message_count: 0,
             copy_status: None,
             selected_message_idx: None,
             schedule_prompt_state: None,
             schedule_list_state: None,
-            schedule_status: None,
+            editing_scheduled: None,
+            flash_status: None,
+            palette_state: None,
+            help_state: None,
+            catch_up_state: None,
+            theme: Theme::default(),
+            image_previews: ImagePreviews::default(),
+            hit_map: HitMap::default(),
             typing_states: HashMap::new(),
             blink_phase: 0,
             activity_cache: std::collections::HashMap::new(),
             activity_last_refresh_tick: 0,
+            drafts: HashMap::new(),
+            translations: HashMap::new(),
+            auto_translate: HashSet::new(),
+            ai_excluded: HashSet::new(),
+            sent_requests: VecDeque::new(),
+            sent_requests_state: None,
         }
     }
 
-    pub fn selected_chat_id(&self) -> Option<&str> {
+    pub fn selected_chat(&self) -> Option<&UnifiedChat> {
         self.chat_list_state
             .selected()
             .and_then(|i| self.chats.get(i))
-            .map(|c| c.id.as_str())
+    }
+
+    pub fn selected_chat_id(&self) -> Option<&str> {
+        self.selected_chat().map(|c| c.id.as_str())
+    }
+
+    /// Indices into `chats` shown under the active folder tab. The selected
+    /// chat stays listed even when it stops matching (e.g. read in "Unread").
+    pub fn visible_chats(&self) -> Vec<usize> {
+        let selected = self.chat_list_state.selected();
+        let folder = self.folders.get(self.active_folder);
+        self.chats
+            .iter()
+            .enumerate()
+            .filter(|&(i, chat)| Some(i) == selected || folder.is_none_or(|f| f.matches(chat)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Position of the selected chat within `visible`.
+    fn selected_position(&self, visible: &[usize]) -> Option<usize> {
+        let selected = self.chat_list_state.selected()?;
+        visible.iter().position(|&i| i == selected)
     }
 
     pub fn select_next_chat(&mut self) {
-        if self.chats.is_empty() {
+        let visible = self.visible_chats();
+        if visible.is_empty() {
             return;
         }
-        let i = match self.chat_list_state.selected() {
-            Some(i) => {
-                if i >= self.chats.len() - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
-            None => 0,
+        let pos = match self.selected_position(&visible) {
+            Some(pos) if pos + 1 < visible.len() => pos + 1,
+            _ => 0,
         };
-        self.chat_list_state.select(Some(i));
+        self.chat_list_state.select(Some(visible[pos]));
         self.scroll_offset = 0;
     }
 
     pub fn select_prev_chat(&mut self) {
-        if self.chats.is_empty() {
+        let visible = self.visible_chats();
+        if visible.is_empty() {
             return;
         }
-        let i = match self.chat_list_state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    0 // stay at top, no wrap
-                } else {
-                    i - 1
-                }
-            }
-            None => 0,
-        };
-        self.chat_list_state.select(Some(i));
+        // Stay at top, no wrap
+        let pos = self
+            .selected_position(&visible)
+            .map_or(0, |pos| pos.saturating_sub(1));
+        self.chat_list_state.select(Some(visible[pos]));
         self.scroll_offset = 0;
     }
 
+    pub fn select_first_chat(&mut self) {
+        if let Some(&first) = self.visible_chats().first() {
+            self.chat_list_state.select(Some(first));
+            self.scroll_offset = 0;
+        }
+    }
+
+    pub fn select_last_chat(&mut self) {
+        if let Some(&last) = self.visible_chats().last() {
+            self.chat_list_state.select(Some(last));
+            self.scroll_offset = 0;
+        }
+    }
+
+    /// Switch to the folder tab at `idx`. Returns true when the selection
+    /// moved to another chat because the current one is not in the folder.
+    pub fn set_active_folder(&mut self, idx: usize) -> bool {
+        if idx >= self.folders.len() {
+            return false;
+        }
+        self.active_folder = idx;
+        *self.chat_list_state.offset_mut() = 0;
+        let folder = &self.folders[idx];
+        if self.selected_chat().is_some_and(|c| folder.matches(c)) {
+            return false;
+        }
+        match self.chats.iter().position(|c| folder.matches(c)) {
+            Some(first) => {
+                self.chat_list_state.select(Some(first));
+                self.scroll_offset = 0;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace the folders imported from `platform`, keeping the active tab
+    /// when it still exists.
+    pub fn set_imported_folders(&mut self, platform: Platform, folders: Vec<ChatFolder>) {
+        let active_name = self.folders.get(self.active_folder).map(|f| f.name.clone());
+        self.folders.retain(|f| f.source != Some(platform));
+        self.folders.extend(folders.into_iter().map(|mut f| {
+            f.source = Some(platform);
+            f
+        }));
+        self.active_folder = active_name
+            .and_then(|name| self.folders.iter().position(|f| f.name == name))
+            .unwrap_or(0);
+    }
+
     pub fn switch_panel(&mut self) {
         self.active_panel = match self.active_panel {
             ActivePanel::ChatList => ActivePanel::MessageView,
@@ -478,6 +959,43 @@ impl AppState {
         text
     }
 
+    /// Move the composer text into `drafts` under the selected chat and clear the input.
+    /// Returns `(chat_id, text)` so the caller can persist it; an empty text means the
+    /// draft was removed.
+    pub fn stash_draft(&mut self) -> Option<(String, String)> {
+        let chat_id = self.selected_chat_id()?.to_string();
+        let text = self.take_input();
+        if text.trim().is_empty() {
+            self.drafts.remove(&chat_id);
+        } else {
+            self.drafts.insert(chat_id.clone(), text.clone());
+        }
+        Some((chat_id, text))
+    }
+
+    /// Load the selected chat's draft into the composer (cursor at the end),
+    /// or clear the composer if the chat has none.
+    pub fn restore_draft(&mut self) {
+        let draft = self
+            .selected_chat_id()
+            .and_then(|id| self.drafts.get(id))
+            .cloned()
+            .unwrap_or_default();
+        self.set_input(&draft);
+    }
+
+    /// Replace the composer contents with `text`, placing the cursor at the end.
+    pub fn set_input(&mut self, text: &str) {
+        if text.is_empty() {
+            self.input = TextArea::default();
+            return;
+        }
+        let mut ta = TextArea::from(text.split('\n').map(|l| l.to_string()));
+        ta.move_cursor(CursorMove::Bottom);
+        ta.move_cursor(CursorMove::End);
+        self.input = ta;
+    }
+
     pub fn scroll_up(&mut self) {
         self.scroll_offset = self.scroll_offset.saturating_add(3);
     }
@@ -512,14 +1030,9 @@ impl AppState {
     pub fn open_chat_menu(&mut self) {
         if let Some(idx) = self.chat_list_state.selected() {
             if let Some(chat) = self.chats.get(idx) {
-                self.chat_menu_state = Some(ChatMenuState::new(
-                    chat.id.clone(),
-                    chat.display_name
-                        .clone()
-                        .unwrap_or_else(|| chat.name.clone()),
-                    chat.is_pinned,
-                    chat.is_muted,
-                ));
+                let auto_translate = self.auto_translate.contains(&chat.id);
+                let ai_excluded = self.ai_excluded.contains(&chat.id);
+                self.chat_menu_state = Some(ChatMenuState::new(chat, auto_translate, ai_excluded));
                 self.input_mode = InputMode::ChatMenu;
             }
         }
@@ -539,6 +1052,14 @@ impl AppState {
         }
     }
 
+    /// Keep a request for the "Sent to AI" overlay, dropping the oldest.
+    pub fn record_sent_request(&mut self, sent: SentRequest) {
+        self.sent_requests.push_back(sent);
+        if self.sent_requests.len() > MAX_SENT_REQUESTS {
+            self.sent_requests.pop_front();
+        }
+    }
+
     pub fn has_unread(&self) -> bool {
         self.chats.iter().any(|c| !c.is_muted && c.unread_count > 0)
     }
@@ -629,6 +1150,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ai_model_choices_keep_configured_model() {
+        let mut config = AppConfig::default();
+        config.ai.model = "custom:7b".to_string();
+        let mut settings = SettingsState::from_config(&config, false);
+        settings.set_ai_models(vec![
+            "llama3.2:latest".to_string(),
+            "qwen2.5:1.5b".to_string(),
+        ]);
+        settings.selected = settings
+            .items
+            .iter()
+            .position(|i| i.key == SettingsKey::AiModel)
+            .unwrap();
+        settings.apply_to_config(&mut config);
+        assert_eq!(config.ai.model, "custom:7b");
+        settings.toggle_selected();
+        settings.apply_to_config(&mut config);
+        assert_eq!(config.ai.model, "llama3.2:latest");
+    }
+
+    #[test]
+    fn test_drafts_follow_chat_selection() {
+        let mut state = AppState::new();
+        state.chats = vec![make_chat("a"), make_chat("b")];
+        state.input.insert_str("hello a");
+
+        let stashed = state.stash_draft();
+        assert_eq!(stashed, Some(("a".to_string(), "hello a".to_string())));
+        assert!(state.input.is_empty());
+
+        state.select_next_chat();
+        state.restore_draft();
+        assert!(state.input.is_empty(), "chat b has no draft");
+
+        state.select_prev_chat();
+        state.restore_draft();
+        assert_eq!(state.input.lines().join("\n"), "hello a");
+    }
+
+    #[test]
+    fn test_stash_empty_input_removes_draft() {
+        let mut state = AppState::new();
+        state.chats = vec![make_chat("a")];
+        state.drafts.insert("a".to_string(), "old".to_string());
+        let stashed = state.stash_draft();
+        assert_eq!(stashed, Some(("a".to_string(), String::new())));
+        assert!(state.drafts.is_empty());
+    }
+
+    fn make_chat(id: &str) -> UnifiedChat {
+        UnifiedChat {
+            id: id.to_string(),
+            platform: Platform::Mock,
+            name: id.to_string(),
+            display_name: None,
+            last_message: None,
+            unread_count: 0,
+            kind: crate::core::types::ChatKind::Chat,
+            is_pinned: false,
+            is_muted: false,
+            is_archived: false,
+        }
+    }
+
+    #[test]
+    fn test_folder_tabs_filter_navigation() {
+        let mut state = AppState::new();
+        let mut unread = make_chat("b");
+        unread.unread_count = 3;
+        state.chats = vec![make_chat("a"), unread, make_chat("c"), make_chat("d")];
+        state.chats[3].unread_count = 1;
+
+        // "Unread" moves the selection off the read chat "a"
+        assert!(state.set_active_folder(1));
+        assert_eq!(state.selected_chat_id(), Some("b"));
+        assert_eq!(state.visible_chats(), vec![1, 3]);
+        state.select_next_chat();
+        assert_eq!(state.selected_chat_id(), Some("d"));
+        state.select_next_chat();
+        assert_eq!(state.selected_chat_id(), Some("b"));
+
+        // Reading the selected chat keeps it listed until the selection moves
+        state.chats[1].unread_count = 0;
+        assert_eq!(state.visible_chats(), vec![1, 3]);
+        state.select_last_chat();
+        assert_eq!(state.visible_chats(), vec![3]);
+
+        assert!(!state.set_active_folder(0));
+        assert_eq!(state.visible_chats().len(), 4);
+    }
+
+    #[test]
+    fn test_chat_menu_cycles_prompt_templates() {
+        let mut menu = ChatMenuState::new(&make_chat("a"), false, false);
+        menu.add_persona_items(
+            Persona::default(),
+            vec!["friends".to_string(), "work".to_string()],
+        );
+        assert_eq!(menu.items.len(), 7);
+        assert_eq!(menu.items[4].label(&menu), "AI prompt: none");
+        assert_eq!(menu.next_prompt().as_deref(), Some("friends"));
+        menu.persona.prompt = Some("friends".to_string());
+        assert_eq!(menu.next_prompt().as_deref(), Some("work"));
+        menu.persona.prompt = Some("work".to_string());
+        assert_eq!(menu.next_prompt(), None);
+        // A template removed from the config starts the cycle over
+        menu.persona.prompt = Some("old".to_string());
+        assert_eq!(menu.next_prompt().as_deref(), Some("friends"));
+        assert_eq!(menu.items[6].label(&menu), "Use AI persona for all DMs");
+    }
+
     #[test]
     fn test_blink_phase_cycles_0_1_2_0() {
         // Simulate the tick handler: advance by (phase + 1) % 3 and verify full wrap-around.
@@ -0,0 +1,131 @@
+use std::path::Path;
+
+/// Footer appended to reply templates; every template line starts with `#`
+/// so it can be stripped when the editor exits.
+const TEMPLATE_FOOTER: &str = "# Write your message above. Lines starting with '#' are ignored.";
+
+/// Resolve the external editor command: `$VISUAL`, then `$EDITOR`, then a platform default.
+/// Returns the program followed by any arguments (e.g. `["code", "--wait"]`).
+pub fn resolve_editor() -> Vec<String> {
+    resolve_editor_from(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok())
+}
+
+fn resolve_editor_from(visual: Option<String>, editor: Option<String>) -> Vec<String> {
+    let fallback = if cfg!(windows) { "notepad" } else { "vi" };
+    let cmd = [visual, editor]
+        .into_iter()
+        .flatten()
+        .find(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| fallback.to_string());
+    cmd.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+/// Build the initial file contents for the editor.
+///
+/// Non-empty composer text is used verbatim. Otherwise, when `reply_to` is
+/// `(sender, text)` of the message being answered, a reply template quoting it
+/// as `#` comment lines is produced. The returned flag is `true` when the
+/// contents are a template whose comment lines must be stripped afterwards.
+pub fn build_template(input: &str, reply_to: Option<(&str, &str)>) -> (String, bool) {
+    if !input.is_empty() {
+        return (input.to_string(), false);
+    }
+    let Some((sender, text)) = reply_to else {
+        return (String::new(), false);
+    };
+    let mut out = String::from("\n\n");
+    out.push_str(&format!("# Replying to {}:\n", sender));
+    for line in text.lines() {
+        out.push_str(&format!("# > {}\n", line));
+    }
+    out.push_str("#\n");
+    out.push_str(TEMPLATE_FOOTER);
+    out.push('\n');
+    (out, true)
+}
+
+/// Turn the edited file contents back into composer text: drop template
+/// comment lines (only when the file started as a template) and trim the
+/// blank lines and trailing newline editors typically leave behind.
+pub fn finish_edit(edited: &str, from_template: bool) -> String {
+    let kept: Vec<&str> = edited
+        .lines()
+        .filter(|l| !(from_template && l.starts_with('#')))
+        .collect();
+    let joined = kept.join("\n");
+    joined
+        .trim_start_matches(['\n', '\r'])
+        .trim_end()
+        .to_string()
+}
+
+/// Run the resolved editor on `path` and wait for it to exit.
+/// Fails if the editor cannot be launched or exits with a non-zero status
+/// (e.g. `:cq` in vim), so callers can keep the original input.
+pub async fn run_editor(path: &Path) -> anyhow::Result<()> {
+    let cmd = resolve_editor();
+    let (program, args) = cmd
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("no editor configured"))?;
+    let status = tokio::process::Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .await
+        .map_err(|e| anyhow::anyhow!("could not launch {}: {}", program, e))?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visual_takes_precedence_over_editor() {
+        let cmd = resolve_editor_from(Some("nvim".into()), Some("nano".into()));
+        assert_eq!(cmd, vec!["nvim"]);
+    }
+
+    #[test]
+    fn editor_used_when_visual_blank() {
+        let cmd = resolve_editor_from(Some("  ".into()), Some("code --wait".into()));
+        assert_eq!(cmd, vec!["code", "--wait"]);
+    }
+
+    #[test]
+    fn falls_back_to_platform_default() {
+        let cmd = resolve_editor_from(None, None);
+        assert_eq!(cmd.len(), 1);
+        assert!(!cmd[0].is_empty());
+    }
+
+    #[test]
+    fn existing_input_is_used_verbatim() {
+        let (contents, is_template) = build_template("hello\nworld", Some(("Bob", "hi")));
+        assert_eq!(contents, "hello\nworld");
+        assert!(!is_template);
+    }
+
+    #[test]
+    fn empty_input_builds_quoted_reply_template() {
+        let (contents, is_template) = build_template("", Some(("Bob", "line one\nline two")));
+        assert!(is_template);
+        assert!(contents.contains("# Replying to Bob:"));
+        assert!(contents.contains("# > line one\n# > line two"));
+    }
+
+    #[test]
+    fn template_comments_are_stripped() {
+        let (template, is_template) = build_template("", Some(("Bob", "hi")));
+        let edited = format!("sounds good\n{}", template);
+        assert_eq!(finish_edit(&edited, is_template), "sounds good");
+    }
+
+    #[test]
+    fn hash_lines_kept_when_not_a_template() {
+        assert_eq!(finish_edit("#general is busy\n", false), "#general is busy");
+    }
+}
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crossterm::event::{Event, EventStream, KeyEventKind};
+use crossterm::event::{Event, EventStream, KeyEventKind, MouseEventKind};
 use futures::StreamExt;
 use tokio::sync::mpsc;
 
@@ -8,14 +8,39 @@ use tokio::sync::mpsc;
 #[allow(dead_code)]
 pub enum AppEvent {
     Key(crossterm::event::KeyEvent),
+    Mouse(crossterm::event::MouseEvent),
     Paste(String),
     Resize(u16, u16),
     Tick,
     Render,
     Quit,
     AiSuggestion(String),
+    /// Suggested replies to the latest message in the chat with this id.
+    AiSmartReplies(String, Vec<String>),
+    /// A catch-me-up summary of one chat's unread messages.
+    AiCatchUp(crate::ai::worker::CatchUpSummary),
+    /// A message translated on request or by a chat's auto-translate.
+    AiTranslation(crate::ai::worker::MessageTranslation),
+    /// Our draft in the chat with this id, translated into their language.
+    AiDraftTranslation(String, Result<String, String>),
+    /// Vectors for a batch of messages handed to the indexer, by message id.
+    Embedded(Result<Vec<(String, Vec<f32>)>, String>),
+    /// The embedding of a semantic search query.
+    QueryEmbedding(String, Result<Vec<f32>, String>),
+    /// A request as it was sent to the AI provider, after redaction.
+    AiRequestSent(crate::ai::redact::SentRequest),
+    /// What a finished AI request used, or that it was answered from the cache.
+    AiUsage(crate::ai::usage::UsageRecord),
+    /// The AI backend now answering requests, or `None` when none is reachable.
+    AiBackend(Option<String>),
     AiError(String),
     MediaError(String),
+    /// A background thumbnail build finished for the message with this id.
+    ImagePreview(String, Result<crate::tui::image_preview::Thumbnail, String>),
+    /// The earliest pending scheduled message is due.
+    ScheduleDue,
+    /// Models installed in the local Ollama, for the settings overlay.
+    AiModels(Result<Vec<String>, String>),
 }
 
 pub struct EventHandler {
@@ -81,6 +106,19 @@ impl EventHandler {
                                     break;
                                 }
                             }
+                            // Motion and release events are not used; don't flood the channel
+                            Event::Mouse(mouse)
+                                if !matches!(
+                                    mouse.kind,
+                                    MouseEventKind::Moved
+                                        | MouseEventKind::Drag(_)
+                                        | MouseEventKind::Up(_)
+                                ) =>
+                            {
+                                if task_tx.send(AppEvent::Mouse(mouse)).is_err() {
+                                    break;
+                                }
+                            }
                             Event::Resize(w, h) => {
                                 if task_tx.send(AppEvent::Resize(w, h)).is_err() {
                                     break;
@@ -96,6 +134,14 @@ impl EventHandler {
         self._task = Some(task);
     }
 
+    /// Stop reading terminal events (e.g. while an external editor owns the TTY).
+    /// Call [`start`] again to resume.
+    pub fn stop(&mut self) {
+        if let Some(task) = self._task.take() {
+            task.abort();
+        }
+    }
+
     pub async fn next(&mut self) -> Option<AppEvent> {
         self.rx.recv().await
     }
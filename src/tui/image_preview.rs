@@ -0,0 +1,626 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+
+use base64::Engine;
+use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
+    queue,
+    style::Print,
+};
+use image::{imageops::FilterType, DynamicImage, RgbaImage};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// Largest thumbnail, in terminal cells.
+const MAX_COLS: u32 = 32;
+const MAX_ROWS: u32 = 10;
+
+/// Largest image file previewed; bigger images are only opened on request.
+pub const MAX_PREVIEW_BYTES: u64 = 16 * 1024 * 1024;
+/// Largest width or height decoded, and the most memory a decode may use.
+const MAX_DECODE_DIMENSION: u32 = 8192;
+const MAX_DECODE_ALLOC: u64 = 256 * 1024 * 1024;
+
+/// Cell size assumed when the terminal does not report its pixel size.
+const DEFAULT_CELL_PX: (u16, u16) = (8, 16);
+
+/// How thumbnails are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+    /// Unicode `▀` cells with true-color foreground/background; works everywhere.
+    HalfBlocks,
+}
+
+impl GraphicsProtocol {
+    /// Resolve the `tui.image_previews` setting. `None` means previews are off.
+    pub fn from_setting(setting: &str) -> Option<Self> {
+        match setting {
+            "off" => None,
+            "kitty" => Some(Self::Kitty),
+            "iterm2" => Some(Self::Iterm2),
+            "sixel" => Some(Self::Sixel),
+            "halfblocks" => Some(Self::HalfBlocks),
+            other => {
+                if other != "auto" {
+                    tracing::warn!("Unknown tui.image_previews '{}', using auto", other);
+                }
+                Some(detect(|key| std::env::var(key).ok()))
+            }
+        }
+    }
+
+    /// Protocols that paint pixels into the cell grid: stale pixels are only
+    /// cleared by redrawing the cells underneath.
+    fn is_cell_based(self) -> bool {
+        matches!(self, Self::Iterm2 | Self::Sixel)
+    }
+}
+
+/// Guess the graphics protocol from environment variables. Multiplexers need
+/// passthrough configuration for graphics, so they get half-blocks.
+pub fn detect(env: impl Fn(&str) -> Option<String>) -> GraphicsProtocol {
+    let var = |key: &str| env(key).unwrap_or_default();
+    let term = var("TERM");
+    let program = var("TERM_PROGRAM");
+
+    if env("TMUX").is_some() || env("STY").is_some() {
+        return GraphicsProtocol::HalfBlocks;
+    }
+    if env("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || program == "ghostty"
+    {
+        return GraphicsProtocol::Kitty;
+    }
+    if program == "iTerm.app" || program == "WezTerm" || var("LC_TERMINAL") == "iTerm2" {
+        return GraphicsProtocol::Iterm2;
+    }
+    if term.starts_with("foot") || term.contains("mlterm") || program == "contour" {
+        return GraphicsProtocol::Sixel;
+    }
+    GraphicsProtocol::HalfBlocks
+}
+
+/// Everything a background task needs to build a thumbnail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewSettings {
+    pub protocol: GraphicsProtocol,
+    /// Pixel size of one terminal cell.
+    pub cell_px: (u16, u16),
+}
+
+impl PreviewSettings {
+    pub fn new(protocol: GraphicsProtocol) -> Self {
+        let cell_px = crossterm::terminal::window_size()
+            .ok()
+            .filter(|s| s.width > 0 && s.height > 0 && s.columns > 0 && s.rows > 0)
+            .map(|s| (s.width / s.columns, s.height / s.rows))
+            .filter(|&(w, h)| w > 0 && h > 0)
+            .unwrap_or(DEFAULT_CELL_PX);
+        Self { protocol, cell_px }
+    }
+}
+
+pub struct Thumbnail {
+    pub cols: u16,
+    pub rows: u16,
+    payload: Payload,
+}
+
+enum Payload {
+    Kitty {
+        id: u32,
+        width: u32,
+        height: u32,
+        /// Base64 of the raw RGBA pixels.
+        data: String,
+    },
+    /// Complete escape sequence, ready to print at the placement.
+    Escape(String),
+    HalfBlocks(Vec<Line<'static>>),
+}
+
+impl fmt::Debug for Thumbnail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Thumbnail")
+            .field("cols", &self.cols)
+            .field("rows", &self.rows)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Thumbnail {
+    /// Rows to draw as text, for the half-block fallback.
+    pub fn lines(&self) -> Option<&[Line<'static>]> {
+        match &self.payload {
+            Payload::HalfBlocks(lines) => Some(lines),
+            _ => None,
+        }
+    }
+}
+
+/// Decode downloaded image bytes into a thumbnail. `id` identifies the image
+/// to kitty. CPU-bound: run it on a blocking thread.
+pub fn build_thumbnail(
+    bytes: &[u8],
+    settings: PreviewSettings,
+    id: u32,
+) -> anyhow::Result<Thumbnail> {
+    anyhow::ensure!(
+        bytes.len() as u64 <= MAX_PREVIEW_BYTES,
+        "too large ({} bytes)",
+        bytes.len()
+    );
+    let mut limits = image::Limits::default();
+    limits.max_image_width = Some(MAX_DECODE_DIMENSION);
+    limits.max_image_height = Some(MAX_DECODE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+    let mut reader = image::ImageReader::new(io::Cursor::new(bytes)).with_guessed_format()?;
+    reader.limits(limits);
+    let img = reader.decode()?;
+    Ok(thumbnail_from_image(&img, bytes, settings, id))
+}
+
+fn thumbnail_from_image(
+    img: &DynamicImage,
+    original: &[u8],
+    settings: PreviewSettings,
+    id: u32,
+) -> Thumbnail {
+    if settings.protocol == GraphicsProtocol::HalfBlocks {
+        // One cell is one pixel wide and two pixels tall
+        let (w, h) = fit(img.width(), img.height(), MAX_COLS, MAX_ROWS * 2);
+        let small = img.resize_exact(w, h, FilterType::Triangle).to_rgba8();
+        return Thumbnail {
+            cols: w as u16,
+            rows: h.div_ceil(2) as u16,
+            payload: Payload::HalfBlocks(half_block_lines(&small)),
+        };
+    }
+
+    let (cell_w, cell_h) = (settings.cell_px.0 as u32, settings.cell_px.1 as u32);
+    let (w, h) = fit(
+        img.width(),
+        img.height(),
+        MAX_COLS * cell_w,
+        MAX_ROWS * cell_h,
+    );
+    let (cols, rows) = (w.div_ceil(cell_w), h.div_ceil(cell_h));
+    let small = img.resize_exact(w, h, FilterType::Triangle).to_rgba8();
+    let base64 = base64::engine::general_purpose::STANDARD;
+    let payload = match settings.protocol {
+        GraphicsProtocol::Kitty => Payload::Kitty {
+            id,
+            width: w,
+            height: h,
+            data: base64.encode(small.as_raw()),
+        },
+        // iTerm2 decodes and scales the original file itself
+        GraphicsProtocol::Iterm2 => Payload::Escape(format!(
+            "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+            original.len(),
+            cols,
+            rows,
+            base64.encode(original)
+        )),
+        GraphicsProtocol::Sixel => Payload::Escape(encode_sixel(&small)),
+        GraphicsProtocol::HalfBlocks => unreachable!("handled above"),
+    };
+    Thumbnail {
+        cols: cols as u16,
+        rows: rows as u16,
+        payload,
+    }
+}
+
+/// Scale `w`×`h` down (never up) to fit within `max_w`×`max_h`, keeping the aspect ratio.
+fn fit(w: u32, h: u32, max_w: u32, max_h: u32) -> (u32, u32) {
+    let scale = (max_w as f64 / w.max(1) as f64)
+        .min(max_h as f64 / h.max(1) as f64)
+        .min(1.0);
+    let scaled = |v: u32| ((v as f64 * scale).round() as u32).max(1);
+    (scaled(w), scaled(h))
+}
+
+/// Pixel colour blended onto black, so transparent areas look like the background.
+fn pixel_rgb(img: &RgbaImage, x: u32, y: u32) -> (u8, u8, u8) {
+    let [r, g, b, a] = img.get_pixel(x, y).0;
+    let blend = |c: u8| (c as u16 * a as u16 / 255) as u8;
+    (blend(r), blend(g), blend(b))
+}
+
+/// Two pixel rows per line: `▀` in the top pixel's colour on the bottom pixel's colour.
+fn half_block_lines(img: &RgbaImage) -> Vec<Line<'static>> {
+    (0..img.height())
+        .step_by(2)
+        .map(|y| {
+            let spans: Vec<Span<'static>> = (0..img.width())
+                .map(|x| {
+                    let (r, g, b) = pixel_rgb(img, x, y);
+                    let mut style = Style::default().fg(Color::Rgb(r, g, b));
+                    if y + 1 < img.height() {
+                        let (r, g, b) = pixel_rgb(img, x, y + 1);
+                        style = style.bg(Color::Rgb(r, g, b));
+                    }
+                    Span::styled("▀", style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Encode as sixel using a fixed 6×6×6 colour cube.
+fn encode_sixel(img: &RgbaImage) -> String {
+    let (w, h) = img.dimensions();
+    let mut out = format!("\x1bPq\"1;1;{};{}", w, h);
+    for i in 0..216u32 {
+        let level = |v: u32| v * 20;
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            i,
+            level(i / 36),
+            level(i / 6 % 6),
+            level(i % 6)
+        ));
+    }
+    let palette_index = |x: u32, y: u32| {
+        let (r, g, b) = pixel_rgb(img, x, y);
+        let q = |c: u8| (c as usize * 5 + 127) / 255;
+        q(r) * 36 + q(g) * 6 + q(b)
+    };
+
+    for band in (0..h).step_by(6) {
+        let band_rows = (h - band).min(6);
+        let indices: Vec<Vec<usize>> = (0..band_rows)
+            .map(|dy| (0..w).map(|x| palette_index(x, band + dy)).collect())
+            .collect();
+        let mut used = [false; 216];
+        indices.iter().flatten().for_each(|&i| used[i] = true);
+
+        let mut first = true;
+        for color in (0..216).filter(|&c| used[c]) {
+            if !first {
+                out.push('$'); // carriage return within the band
+            }
+            first = false;
+            out.push_str(&format!("#{}", color));
+            let mut run: Option<(char, usize)> = None;
+            let columns = (0..w as usize).map(|x| {
+                indices
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| row[x] == color)
+                    .fold(0u8, |acc, (dy, _)| acc | (1 << dy))
+            });
+            for bits in columns {
+                let ch = (63 + bits) as char;
+                run = match run {
+                    Some((c, n)) if c == ch => Some((c, n + 1)),
+                    Some((c, n)) => {
+                        push_sixel_run(&mut out, c, n);
+                        Some((ch, 1))
+                    }
+                    None => Some((ch, 1)),
+                };
+            }
+            if let Some((c, n)) = run {
+                push_sixel_run(&mut out, c, n);
+            }
+        }
+        out.push('-'); // next band
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, ch: char, count: usize) {
+    if count > 3 {
+        out.push_str(&format!("!{}{}", count, ch));
+    } else {
+        out.extend(std::iter::repeat_n(ch, count));
+    }
+}
+
+pub enum Preview {
+    Loading,
+    Ready(Thumbnail),
+    Failed,
+}
+
+/// Where a thumbnail landed on screen in the last rendered frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    pub msg_id: String,
+    pub x: u16,
+    pub y: u16,
+}
+
+/// In-memory thumbnail cache plus the bookkeeping for drawing graphics
+/// protocol images after each frame.
+#[derive(Default)]
+pub struct ImagePreviews {
+    /// `None` when previews are disabled.
+    pub settings: Option<PreviewSettings>,
+    previews: HashMap<String, Preview>,
+    next_id: u32,
+    /// Filled by the message view during render.
+    pub placements: Vec<Placement>,
+    /// Placements currently on screen.
+    drawn: Vec<Placement>,
+    /// Kitty image ids already transmitted to the terminal.
+    transmitted: HashSet<u32>,
+}
+
+impl ImagePreviews {
+    pub fn new(settings: Option<PreviewSettings>) -> Self {
+        Self {
+            settings,
+            next_id: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn get(&self, msg_id: &str) -> Option<&Thumbnail> {
+        match self.previews.get(msg_id) {
+            Some(Preview::Ready(thumb)) => Some(thumb),
+            _ => None,
+        }
+    }
+
+    /// Mark `msg_id` as loading. Returns the image id to build it with, or
+    /// `None` if previews are off or this message was already requested.
+    pub fn begin_load(&mut self, msg_id: &str) -> Option<u32> {
+        if self.settings.is_none() || self.previews.contains_key(msg_id) {
+            return None;
+        }
+        self.previews.insert(msg_id.to_string(), Preview::Loading);
+        let id = self.next_id;
+        self.next_id += 1;
+        Some(id)
+    }
+
+    /// Store a finished load, unless `msg_id` was dropped by [`Self::retain`]
+    /// while it loaded.
+    pub fn finish_load(&mut self, msg_id: String, result: Result<Thumbnail, String>) {
+        if !self.previews.contains_key(&msg_id) {
+            return;
+        }
+        let preview = match result {
+            Ok(thumb) => Preview::Ready(thumb),
+            Err(e) => {
+                tracing::debug!("No preview for {}: {}", msg_id, e);
+                Preview::Failed
+            }
+        };
+        self.previews.insert(msg_id, preview);
+    }
+
+    /// Drop previews of messages not matching `keep`, e.g. those of chats
+    /// no longer open.
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.previews.retain(|msg_id, _| keep(msg_id));
+    }
+
+    /// True when the last frame moved or removed pixel-grid images: the
+    /// cells underneath must be repainted before drawing again.
+    pub fn needs_full_redraw(&self) -> bool {
+        self.settings.is_some_and(|s| s.protocol.is_cell_based())
+            && !self.drawn.is_empty()
+            && self.placements != self.drawn
+    }
+
+    /// Forget what is on screen, e.g. after the terminal was cleared or resized.
+    pub fn invalidate(&mut self) {
+        self.drawn.clear();
+        self.transmitted.clear();
+    }
+
+    /// Draw this frame's placements with the graphics protocol. Writes
+    /// straight to stdout after ratatui has flushed, like the OSC 8 pass.
+    pub fn draw(&mut self) -> io::Result<()> {
+        let Some(settings) = self.settings else {
+            return Ok(());
+        };
+        if settings.protocol == GraphicsProtocol::HalfBlocks || self.placements == self.drawn {
+            return Ok(());
+        }
+        let mut out = io::stdout().lock();
+        if settings.protocol == GraphicsProtocol::Kitty {
+            // Remove all placements; transmitted image data is kept
+            queue!(out, Print("\x1b_Ga=d,d=a,q=2\x1b\\"))?;
+        }
+        for placement in &self.placements {
+            let Some(Preview::Ready(thumb)) = self.previews.get(&placement.msg_id) else {
+                continue;
+            };
+            queue!(out, SavePosition, MoveTo(placement.x, placement.y))?;
+            match &thumb.payload {
+                Payload::Kitty {
+                    id,
+                    width,
+                    height,
+                    data,
+                } => {
+                    if self.transmitted.insert(*id) {
+                        write_kitty_transmit(&mut out, *id, *width, *height, data)?;
+                    }
+                    queue!(
+                        out,
+                        Print(format!(
+                            "\x1b_Ga=p,i={},c={},r={},C=1,q=2\x1b\\",
+                            id, thumb.cols, thumb.rows
+                        ))
+                    )?;
+                }
+                Payload::Escape(seq) => queue!(out, Print(seq))?,
+                Payload::HalfBlocks(_) => {}
+            }
+            queue!(out, RestorePosition)?;
+        }
+        self.drawn = self.placements.clone();
+        out.flush()
+    }
+}
+
+/// Send raw RGBA pixels to kitty in 4096-byte chunks without displaying them.
+fn write_kitty_transmit(
+    out: &mut impl Write,
+    id: u32,
+    width: u32,
+    height: u32,
+    data: &str,
+) -> io::Result<()> {
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(4096).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            write!(
+                out,
+                "\x1b_Ga=t,f=32,s={},v={},i={},q=2,m={};{}\x1b\\",
+                width, height, id, more, chunk
+            )?;
+        } else {
+            write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn detects_protocol_from_environment() {
+        assert_eq!(
+            detect(env(&[("KITTY_WINDOW_ID", "1")])),
+            GraphicsProtocol::Kitty
+        );
+        assert_eq!(
+            detect(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            GraphicsProtocol::Iterm2
+        );
+        assert_eq!(
+            detect(env(&[("TERM", "foot-extra")])),
+            GraphicsProtocol::Sixel
+        );
+        assert_eq!(
+            detect(env(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")])),
+            GraphicsProtocol::HalfBlocks
+        );
+        assert_eq!(
+            detect(env(&[("TERM", "xterm-256color")])),
+            GraphicsProtocol::HalfBlocks
+        );
+    }
+
+    #[test]
+    fn fit_keeps_aspect_and_never_upscales() {
+        assert_eq!(fit(640, 480, 32, 20), (27, 20));
+        assert_eq!(fit(10, 5, 32, 20), (10, 5));
+        assert_eq!(fit(1000, 1, 32, 20), (32, 1));
+    }
+
+    #[test]
+    fn half_blocks_pair_pixel_rows() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_fn(4, 3, |_, y| {
+            if y == 0 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 255])
+            }
+        }));
+        let settings = PreviewSettings {
+            protocol: GraphicsProtocol::HalfBlocks,
+            cell_px: DEFAULT_CELL_PX,
+        };
+        let thumb = thumbnail_from_image(&img, &[], settings, 1);
+        assert_eq!((thumb.cols, thumb.rows), (4, 2));
+        let lines = thumb.lines().unwrap();
+        assert_eq!(lines.len(), 2);
+        let cell = &lines[0].spans[0];
+        assert_eq!(cell.style.fg, Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(cell.style.bg, Some(Color::Rgb(0, 0, 255)));
+        // Odd height: the last row has no bottom pixel
+        assert_eq!(lines[1].spans[0].style.bg, None);
+    }
+
+    #[test]
+    fn graphics_thumbnails_are_sized_in_cells() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(64, 32, Rgba([0, 0, 0, 255])));
+        let settings = PreviewSettings {
+            protocol: GraphicsProtocol::Sixel,
+            cell_px: (8, 16),
+        };
+        let thumb = thumbnail_from_image(&img, &[], settings, 1);
+        assert_eq!((thumb.cols, thumb.rows), (8, 2));
+        match &thumb.payload {
+            Payload::Escape(seq) => {
+                assert!(seq.starts_with("\x1bPq\"1;1;64;32"));
+                assert!(seq.ends_with("\x1b\\"));
+                // A solid image is one colour run per band
+                assert!(seq.contains("#0!64~"));
+            }
+            _ => panic!("expected a sixel escape"),
+        }
+    }
+
+    #[test]
+    fn oversized_images_are_not_decoded() {
+        let settings = PreviewSettings {
+            protocol: GraphicsProtocol::HalfBlocks,
+            cell_px: DEFAULT_CELL_PX,
+        };
+        let bytes = vec![0; MAX_PREVIEW_BYTES as usize + 1];
+        let err = build_thumbnail(&bytes, settings, 1).unwrap_err();
+        assert!(err.to_string().starts_with("too large"), "{}", err);
+    }
+
+    #[test]
+    fn previews_load_once() {
+        let mut previews = ImagePreviews::new(Some(PreviewSettings {
+            protocol: GraphicsProtocol::HalfBlocks,
+            cell_px: DEFAULT_CELL_PX,
+        }));
+        assert_eq!(previews.begin_load("m1"), Some(1));
+        assert_eq!(previews.begin_load("m1"), None);
+        assert_eq!(previews.begin_load("m2"), Some(2));
+        previews.finish_load("m1".to_string(), Err("boom".to_string()));
+        assert!(previews.get("m1").is_none());
+        assert_eq!(previews.begin_load("m1"), None);
+
+        assert_eq!(ImagePreviews::new(None).begin_load("m1"), None);
+    }
+
+    #[test]
+    fn retain_drops_other_chats_previews() {
+        let mut previews = ImagePreviews::new(Some(PreviewSettings {
+            protocol: GraphicsProtocol::HalfBlocks,
+            cell_px: DEFAULT_CELL_PX,
+        }));
+        previews.begin_load("old");
+        previews.begin_load("open");
+        previews.retain(|id| id == "open");
+        previews.finish_load("old".to_string(), Err("late".to_string()));
+        assert!(previews.previews.keys().eq(["open"]));
+        assert_eq!(previews.begin_load("old"), Some(3));
+    }
+}
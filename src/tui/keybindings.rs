@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::app_state::InputMode;
@@ -12,6 +14,7 @@ pub enum Action {
     ExitEditing,
     SubmitMessage,
     ClearInput,
+    ComposeInEditor, // Ctrl+G — edit the composer text in $VISUAL/$EDITOR
     InputKey(KeyEvent),
     ScrollUp,
     ScrollDown,
@@ -24,6 +27,8 @@ pub enum Action {
     RenameChat,
     ConfirmRename,
     CancelRename,
+    ConfirmStyleNotes,
+    CancelStyleNotes,
     OpenChatMenu,
     ChatMenuNext,
     ChatMenuPrev,
@@ -35,16 +40,21 @@ pub enum Action {
     SearchPrev,
     SearchConfirm,
     SearchClose,
-    AiSuggestAccept,    // Tab — accept ghost text suggestion
-    AiSuggestRequest,   // Ctrl+Space — on-demand trigger
-    CopyLastMessage,    // y — copy last message text to clipboard via OSC 52
+    SearchToggleMode, // Tab — switch between chat names and semantic message search
+    AiSuggestAccept,  // accept ghost text suggestion
+    TabComplete,      // Tab — complete a /command, else accept the AI suggestion
+    AiSuggestRequest, // Ctrl+Space — on-demand trigger
+    InsertSmartReply(usize), // 1-3 / Alt+1-3 — put a suggested reply in the composer
+    CopyLastMessage,  // y — copy last message text to clipboard via OSC 52
     EnterMessageSelect, // v — enter message selection mode
-    MessageSelectPrev,  // k/Up — move selection up (older)
-    MessageSelectNext,  // j/Down — move selection down (newer)
-    MessageSelectCopy,  // y — copy selected message and exit
-    MessageSelectExit,  // Esc — exit without copying
-    OpenMedia,          // Enter — open media attachment in viewer
-    ScheduleMessage,    // Ctrl+D — open schedule prompt
+    MessageSelectPrev, // k/Up — move selection up (older)
+    MessageSelectNext, // j/Down — move selection down (newer)
+    MessageSelectCopy, // y — copy selected message and exit
+    MessageSelectExit, // Esc — exit without copying
+    OpenMedia,        // Enter — open media attachment in viewer
+    TranslateMessage, // t — translate the selected message
+    TranslateDraft,   // Ctrl+T — translate the draft into the chat's language
+    ScheduleMessage,  // Ctrl+D — open schedule prompt
     ScheduleInput(KeyEvent),
     ScheduleConfirm,
     ScheduleCancel,
@@ -52,162 +62,882 @@ pub enum Action {
     ScheduleListNext,
     ScheduleListPrev,
     ScheduleListDelete,
+    ScheduleListRepeat,   // r — change how the selected message repeats
+    ScheduleListSendNow,  // s — send the selected message now, resetting its retries
+    ScheduleListEdit,     // e — edit the selected message's text in the composer
+    ScheduleListEditTime, // t — change the selected message's send time
     ScheduleListClose,
     TelegramAuthChar(char),
     TelegramAuthBackspace,
     TelegramAuthSubmit,
     TelegramAuthCancel,
+    FirstChat, // gg — jump to the top of the chat list
+    LastChat,  // G — jump to the bottom of the chat list
+    OpenHelp,  // ? — list the active keybindings
+    HelpScrollUp,
+    HelpScrollDown,
+    HelpClose,
+    OpenPalette, // : / Ctrl+P — command palette
+    PaletteInput(KeyEvent),
+    PaletteNext,
+    PalettePrev,
+    PaletteConfirm,
+    PaletteClose,
+    TogglePin,
+    ToggleMute,
+    ToggleArchive,
+    ExportChat,
+    ToggleActivityGraph,
+    ToggleAway,
+    CatchUp,    // c — summarise the selected chat's unread messages
+    CatchUpAll, // C — summarise every chat with unread messages
+    CatchUpNext,
+    CatchUpPrev,
+    CatchUpOpen, // Enter — open the selected chat
+    CatchUpClose,
+    OpenSentRequests, // show the latest requests exactly as sent to the AI provider
+    SentRequestsScrollUp,
+    SentRequestsScrollDown,
+    SentRequestsClose,
+    NextFolder, // ] — next chat list tab
+    PrevFolder, // [ — previous chat list tab
     None,
 }
 
-pub fn map_key(key: KeyEvent, mode: InputMode, enter_sends: bool) -> Action {
-    match mode {
-        InputMode::Normal => map_normal_mode(key),
-        InputMode::Editing => map_editing_mode(key, enter_sends),
-        InputMode::Settings => map_settings_mode(key),
-        InputMode::Renaming => map_renaming_mode(key),
-        InputMode::ChatMenu => map_chat_menu_mode(key),
-        InputMode::Searching => map_search_mode(key),
-        InputMode::MessageSelect => map_message_select_mode(key),
-        InputMode::SchedulePrompt => map_schedule_prompt_mode(key),
-        InputMode::ScheduleList => map_schedule_list_mode(key),
-        InputMode::TelegramAuth => map_telegram_auth_mode(key),
-    }
-}
-
-fn map_normal_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Char('q') => Action::Quit,
-        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
-        KeyCode::Tab => Action::SwitchPanel,
-        KeyCode::Char('j') | KeyCode::Down => Action::NextChat,
-        KeyCode::Char('k') | KeyCode::Up => Action::PrevChat,
-        KeyCode::Char('i') | KeyCode::Enter => Action::EnterEditing,
-        KeyCode::Char('s') => Action::OpenSettings,
-        KeyCode::Char('r') => Action::RenameChat,
-        KeyCode::Char('x') => Action::OpenChatMenu,
-        KeyCode::Char('/') => Action::OpenSearch,
-        KeyCode::Char('y') => Action::CopyLastMessage,
-        KeyCode::Char('v') => Action::EnterMessageSelect,
-        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            Action::OpenScheduleList
-        }
-        KeyCode::PageUp => Action::ScrollUp,
-        KeyCode::PageDown => Action::ScrollDown,
-        _ => Action::None,
+/// Config-facing names for every bindable action (`"ctrl+l" = "open_schedule_list"`).
+/// `none` unbinds a default key.
+const ACTION_NAMES: &[(&str, Action)] = &[
+    ("quit", Action::Quit),
+    ("switch_panel", Action::SwitchPanel),
+    ("next_chat", Action::NextChat),
+    ("prev_chat", Action::PrevChat),
+    ("first_chat", Action::FirstChat),
+    ("last_chat", Action::LastChat),
+    ("enter_editing", Action::EnterEditing),
+    ("exit_editing", Action::ExitEditing),
+    ("submit_message", Action::SubmitMessage),
+    ("clear_input", Action::ClearInput),
+    ("compose_in_editor", Action::ComposeInEditor),
+    ("scroll_up", Action::ScrollUp),
+    ("scroll_down", Action::ScrollDown),
+    ("open_settings", Action::OpenSettings),
+    ("settings_next", Action::SettingsNext),
+    ("settings_prev", Action::SettingsPrev),
+    ("settings_toggle", Action::SettingsToggle),
+    ("settings_save", Action::SettingsSave),
+    ("settings_close", Action::SettingsClose),
+    ("rename_chat", Action::RenameChat),
+    ("confirm_rename", Action::ConfirmRename),
+    ("cancel_rename", Action::CancelRename),
+    ("confirm_style_notes", Action::ConfirmStyleNotes),
+    ("cancel_style_notes", Action::CancelStyleNotes),
+    ("open_chat_menu", Action::OpenChatMenu),
+    ("chat_menu_next", Action::ChatMenuNext),
+    ("chat_menu_prev", Action::ChatMenuPrev),
+    ("chat_menu_confirm", Action::ChatMenuConfirm),
+    ("chat_menu_close", Action::ChatMenuClose),
+    ("open_search", Action::OpenSearch),
+    ("search_next", Action::SearchNext),
+    ("search_prev", Action::SearchPrev),
+    ("search_confirm", Action::SearchConfirm),
+    ("search_close", Action::SearchClose),
+    ("search_toggle_mode", Action::SearchToggleMode),
+    ("ai_suggest_accept", Action::AiSuggestAccept),
+    ("tab_complete", Action::TabComplete),
+    ("ai_suggest_request", Action::AiSuggestRequest),
+    ("smart_reply_1", Action::InsertSmartReply(0)),
+    ("smart_reply_2", Action::InsertSmartReply(1)),
+    ("smart_reply_3", Action::InsertSmartReply(2)),
+    ("copy_last_message", Action::CopyLastMessage),
+    ("enter_message_select", Action::EnterMessageSelect),
+    ("message_select_prev", Action::MessageSelectPrev),
+    ("message_select_next", Action::MessageSelectNext),
+    ("message_select_copy", Action::MessageSelectCopy),
+    ("message_select_exit", Action::MessageSelectExit),
+    ("open_media", Action::OpenMedia),
+    ("translate_message", Action::TranslateMessage),
+    ("translate_draft", Action::TranslateDraft),
+    ("schedule_message", Action::ScheduleMessage),
+    ("schedule_confirm", Action::ScheduleConfirm),
+    ("schedule_cancel", Action::ScheduleCancel),
+    ("open_schedule_list", Action::OpenScheduleList),
+    ("schedule_list_next", Action::ScheduleListNext),
+    ("schedule_list_prev", Action::ScheduleListPrev),
+    ("schedule_list_delete", Action::ScheduleListDelete),
+    ("schedule_list_repeat", Action::ScheduleListRepeat),
+    ("schedule_list_send_now", Action::ScheduleListSendNow),
+    ("schedule_list_edit", Action::ScheduleListEdit),
+    ("schedule_list_edit_time", Action::ScheduleListEditTime),
+    ("schedule_list_close", Action::ScheduleListClose),
+    ("telegram_auth_backspace", Action::TelegramAuthBackspace),
+    ("telegram_auth_submit", Action::TelegramAuthSubmit),
+    ("telegram_auth_cancel", Action::TelegramAuthCancel),
+    ("open_help", Action::OpenHelp),
+    ("help_scroll_up", Action::HelpScrollUp),
+    ("help_scroll_down", Action::HelpScrollDown),
+    ("help_close", Action::HelpClose),
+    ("open_palette", Action::OpenPalette),
+    ("palette_next", Action::PaletteNext),
+    ("palette_prev", Action::PalettePrev),
+    ("palette_confirm", Action::PaletteConfirm),
+    ("palette_close", Action::PaletteClose),
+    ("toggle_pin", Action::TogglePin),
+    ("toggle_mute", Action::ToggleMute),
+    ("toggle_archive", Action::ToggleArchive),
+    ("export_chat", Action::ExportChat),
+    ("toggle_activity_graph", Action::ToggleActivityGraph),
+    ("toggle_away", Action::ToggleAway),
+    ("catch_up", Action::CatchUp),
+    ("catch_up_all", Action::CatchUpAll),
+    ("catch_up_next", Action::CatchUpNext),
+    ("catch_up_prev", Action::CatchUpPrev),
+    ("catch_up_open", Action::CatchUpOpen),
+    ("catch_up_close", Action::CatchUpClose),
+    ("open_sent_requests", Action::OpenSentRequests),
+    ("sent_requests_scroll_up", Action::SentRequestsScrollUp),
+    ("sent_requests_scroll_down", Action::SentRequestsScrollDown),
+    ("sent_requests_close", Action::SentRequestsClose),
+    ("next_folder", Action::NextFolder),
+    ("prev_folder", Action::PrevFolder),
+    ("none", Action::None),
+];
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTION_NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, a)| a.clone())
     }
-}
 
-fn map_editing_mode(key: KeyEvent, enter_sends: bool) -> Action {
-    match (key.code, key.modifiers) {
-        (KeyCode::Esc, _) => Action::ExitEditing,
+    pub fn name(&self) -> Option<&'static str> {
+        ACTION_NAMES
+            .iter()
+            .find(|(_, a)| a == self)
+            .map(|(n, _)| *n)
+    }
+}
 
-        // enter_sends=true (default): plain Enter submits, Shift/Alt+Enter inserts newline
-        (KeyCode::Enter, m) if enter_sends && m == KeyModifiers::NONE => Action::SubmitMessage,
-        (KeyCode::Enter, _) if enter_sends => Action::InputKey(key), // Shift/Alt+Enter → forward to textarea as newline
+/// `[keybindings.<mode>]` table names, in help-overlay order.
+const MODE_NAMES: &[(InputMode, &str)] = &[
+    (InputMode::Normal, "normal"),
+    (InputMode::Editing, "editing"),
+    (InputMode::Settings, "settings"),
+    (InputMode::Renaming, "renaming"),
+    (InputMode::ChatMenu, "chat_menu"),
+    (InputMode::Searching, "search"),
+    (InputMode::MessageSelect, "message_select"),
+    (InputMode::SchedulePrompt, "schedule_prompt"),
+    (InputMode::ScheduleList, "schedule_list"),
+    (InputMode::TelegramAuth, "telegram_auth"),
+    (InputMode::Help, "help"),
+    (InputMode::Palette, "palette"),
+    (InputMode::CatchUp, "catch_up"),
+    (InputMode::StyleNotes, "style_notes"),
+    (InputMode::SentRequests, "sent_requests"),
+];
 
-        // enter_sends=false: Shift/Alt+Enter submits, plain Enter inserts newline
-        (KeyCode::Enter, m) if !enter_sends && m.contains(KeyModifiers::SHIFT) => {
-            Action::SubmitMessage
-        }
-        (KeyCode::Enter, m) if !enter_sends && m.contains(KeyModifiers::ALT) => {
-            Action::SubmitMessage
-        }
+fn mode_from_name(name: &str) -> Option<InputMode> {
+    MODE_NAMES.iter().find(|(_, n)| *n == name).map(|(m, _)| *m)
+}
 
-        // Ctrl+J: WSL-friendly newline insert (Shift+Enter not reliably transmitted in WSL)
-        (KeyCode::Char('j'), m) if m.contains(KeyModifiers::CONTROL) => {
-            Action::InputKey(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
-        }
-        // Ctrl+S always submits regardless of mode
-        (KeyCode::Char('s'), m) if m.contains(KeyModifiers::CONTROL) => Action::SubmitMessage,
-        // Ctrl+U always clears
-        (KeyCode::Char('u'), m) if m.contains(KeyModifiers::CONTROL) => Action::ClearInput,
-        (KeyCode::Char('d'), m) if m.contains(KeyModifiers::CONTROL) => Action::ScheduleMessage,
-        (KeyCode::Tab, _) => Action::AiSuggestAccept,
-        (KeyCode::Char(' '), m) if m.contains(KeyModifiers::CONTROL) => Action::AiSuggestRequest,
-        // Everything else forwarded to TextArea
-        _ => Action::InputKey(key),
-    }
-}
-
-fn map_settings_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => Action::SettingsNext,
-        KeyCode::Char('k') | KeyCode::Up => Action::SettingsPrev,
-        KeyCode::Enter | KeyCode::Char(' ') => Action::SettingsToggle,
-        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::SettingsSave,
-        KeyCode::Esc | KeyCode::Char('q') => Action::SettingsClose,
-        _ => Action::None,
+/// Built-in bindings per mode as `(key spec, action name)`.
+/// Keys that feed text input (typing, Enter-to-send, Ctrl+J) are handled by
+/// [`fallback`] instead, so they keep working whatever the user rebinds.
+fn default_bindings(mode: InputMode) -> &'static [(&'static str, &'static str)] {
+    match mode {
+        InputMode::Normal => &[
+            ("q", "quit"),
+            ("ctrl+c", "quit"),
+            ("tab", "switch_panel"),
+            ("j", "next_chat"),
+            ("down", "next_chat"),
+            ("k", "prev_chat"),
+            ("up", "prev_chat"),
+            ("gg", "first_chat"),
+            ("G", "last_chat"),
+            ("]", "next_folder"),
+            ("[", "prev_folder"),
+            ("i", "enter_editing"),
+            ("enter", "enter_editing"),
+            ("s", "open_settings"),
+            ("r", "rename_chat"),
+            ("x", "open_chat_menu"),
+            ("/", "open_search"),
+            ("y", "copy_last_message"),
+            ("c", "catch_up"),
+            ("C", "catch_up_all"),
+            ("v", "enter_message_select"),
+            ("ctrl+l", "open_schedule_list"),
+            ("1", "smart_reply_1"),
+            ("2", "smart_reply_2"),
+            ("3", "smart_reply_3"),
+            ("pageup", "scroll_up"),
+            ("pagedown", "scroll_down"),
+            ("?", "open_help"),
+            (":", "open_palette"),
+            ("ctrl+p", "open_palette"),
+        ],
+        InputMode::Editing => &[
+            ("esc", "exit_editing"),
+            ("ctrl+s", "submit_message"),
+            ("ctrl+u", "clear_input"),
+            ("ctrl+d", "schedule_message"),
+            ("ctrl+g", "compose_in_editor"),
+            ("ctrl+t", "translate_draft"),
+            ("tab", "tab_complete"),
+            ("ctrl+space", "ai_suggest_request"),
+            ("alt+1", "smart_reply_1"),
+            ("alt+2", "smart_reply_2"),
+            ("alt+3", "smart_reply_3"),
+        ],
+        InputMode::Settings => &[
+            ("j", "settings_next"),
+            ("down", "settings_next"),
+            ("k", "settings_prev"),
+            ("up", "settings_prev"),
+            ("enter", "settings_toggle"),
+            ("space", "settings_toggle"),
+            ("ctrl+s", "settings_save"),
+            ("esc", "settings_close"),
+            ("q", "settings_close"),
+        ],
+        InputMode::Renaming => &[("esc", "cancel_rename"), ("enter", "confirm_rename")],
+        InputMode::StyleNotes => &[
+            ("esc", "cancel_style_notes"),
+            ("enter", "confirm_style_notes"),
+        ],
+        InputMode::ChatMenu => &[
+            ("j", "chat_menu_next"),
+            ("down", "chat_menu_next"),
+            ("k", "chat_menu_prev"),
+            ("up", "chat_menu_prev"),
+            ("enter", "chat_menu_confirm"),
+            ("p", "chat_menu_confirm"),
+            ("esc", "chat_menu_close"),
+            ("q", "chat_menu_close"),
+        ],
+        InputMode::Searching => &[
+            ("esc", "search_close"),
+            ("enter", "search_confirm"),
+            ("down", "search_next"),
+            ("up", "search_prev"),
+            ("tab", "search_toggle_mode"),
+        ],
+        InputMode::MessageSelect => &[
+            ("k", "message_select_prev"),
+            ("up", "message_select_prev"),
+            ("j", "message_select_next"),
+            ("down", "message_select_next"),
+            ("y", "message_select_copy"),
+            ("enter", "open_media"),
+            ("t", "translate_message"),
+            ("esc", "message_select_exit"),
+            ("q", "message_select_exit"),
+        ],
+        InputMode::SchedulePrompt => &[("esc", "schedule_cancel"), ("enter", "schedule_confirm")],
+        InputMode::ScheduleList => &[
+            ("j", "schedule_list_next"),
+            ("down", "schedule_list_next"),
+            ("k", "schedule_list_prev"),
+            ("up", "schedule_list_prev"),
+            ("d", "schedule_list_delete"),
+            ("r", "schedule_list_repeat"),
+            ("s", "schedule_list_send_now"),
+            ("e", "schedule_list_edit"),
+            ("t", "schedule_list_edit_time"),
+            ("esc", "schedule_list_close"),
+            ("q", "schedule_list_close"),
+        ],
+        InputMode::TelegramAuth => &[
+            ("esc", "telegram_auth_cancel"),
+            ("enter", "telegram_auth_submit"),
+            ("backspace", "telegram_auth_backspace"),
+        ],
+        InputMode::Help => &[
+            ("j", "help_scroll_down"),
+            ("down", "help_scroll_down"),
+            ("k", "help_scroll_up"),
+            ("up", "help_scroll_up"),
+            ("esc", "help_close"),
+            ("q", "help_close"),
+            ("?", "help_close"),
+        ],
+        InputMode::Palette => &[
+            ("esc", "palette_close"),
+            ("enter", "palette_confirm"),
+            ("down", "palette_next"),
+            ("ctrl+n", "palette_next"),
+            ("up", "palette_prev"),
+            ("ctrl+p", "palette_prev"),
+        ],
+        InputMode::CatchUp => &[
+            ("j", "catch_up_next"),
+            ("down", "catch_up_next"),
+            ("k", "catch_up_prev"),
+            ("up", "catch_up_prev"),
+            ("enter", "catch_up_open"),
+            ("esc", "catch_up_close"),
+            ("q", "catch_up_close"),
+        ],
+        InputMode::SentRequests => &[
+            ("j", "sent_requests_scroll_down"),
+            ("down", "sent_requests_scroll_down"),
+            ("k", "sent_requests_scroll_up"),
+            ("up", "sent_requests_scroll_up"),
+            ("esc", "sent_requests_close"),
+            ("q", "sent_requests_close"),
+        ],
     }
 }
 
-fn map_chat_menu_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => Action::ChatMenuNext,
-        KeyCode::Char('k') | KeyCode::Up => Action::ChatMenuPrev,
-        KeyCode::Enter | KeyCode::Char('p') => Action::ChatMenuConfirm,
-        KeyCode::Esc | KeyCode::Char('q') => Action::ChatMenuClose,
-        _ => Action::None,
+/// Actions with no default key in `mode` that can still be bound.
+fn unbound_actions(mode: InputMode) -> &'static [&'static str] {
+    match mode {
+        InputMode::Normal => &[
+            "toggle_pin",
+            "toggle_mute",
+            "toggle_archive",
+            "export_chat",
+            "toggle_activity_graph",
+            "open_sent_requests",
+        ],
+        InputMode::Editing => &["ai_suggest_accept"],
+        _ => &[],
     }
 }
 
-fn map_search_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Esc => Action::SearchClose,
-        KeyCode::Enter => Action::SearchConfirm,
-        KeyCode::Down => Action::SearchNext,
-        KeyCode::Up => Action::SearchPrev,
-        _ => Action::SearchInput(key),
+/// One entry of a mode's status bar hints.
+enum Hint {
+    /// Keys that are not bindable, such as text entry, shown as they are.
+    Fixed(&'static str),
+    /// Actions shown with their active keys: every key of a single action
+    /// (`esc/q:Close`), or the first key of each of several (`j/k:Navigate`).
+    Keys(&'static [&'static str], &'static str),
+}
+
+/// Status bar hints per mode, by action name.
+fn mode_hints(mode: InputMode, enter_sends: bool) -> &'static [Hint] {
+    use Hint::{Fixed, Keys};
+    match mode {
+        InputMode::Normal => &[
+            Keys(&["quit"], "Quit"),
+            Keys(&["enter_editing"], "Insert"),
+            Keys(&["open_settings"], "Settings"),
+            Keys(&["rename_chat"], "Rename"),
+            Keys(&["open_chat_menu"], "Menu"),
+            Keys(&["copy_last_message"], "Copy last"),
+            Keys(&["enter_message_select"], "Select msg"),
+            Keys(&["open_schedule_list"], "Scheduled"),
+            Keys(&["open_palette"], "Commands"),
+            Keys(&["switch_panel"], "Switch"),
+            Keys(&["open_help"], "Help"),
+        ],
+        InputMode::Editing if enter_sends => &[
+            Keys(&["exit_editing"], "Normal"),
+            Fixed("Enter:Send | Shift+Enter/Ctrl+J:Newline"),
+            Keys(&["submit_message"], "Send"),
+            Keys(&["clear_input"], "Clear"),
+            Keys(&["schedule_message"], "Schedule"),
+            Keys(&["compose_in_editor"], "Editor"),
+            Keys(&["translate_draft"], "Translate"),
+        ],
+        InputMode::Editing => &[
+            Keys(&["exit_editing"], "Normal"),
+            Fixed("Enter:Newline | Shift+Enter:Send"),
+            Keys(&["submit_message"], "Send"),
+            Keys(&["clear_input"], "Clear"),
+            Keys(&["schedule_message"], "Schedule"),
+            Keys(&["compose_in_editor"], "Editor"),
+            Keys(&["translate_draft"], "Translate"),
+        ],
+        InputMode::Settings => &[
+            Keys(&["settings_next", "settings_prev"], "Navigate"),
+            Keys(&["settings_toggle"], "Toggle"),
+            Keys(&["settings_save"], "Save"),
+            Keys(&["settings_close"], "Cancel"),
+        ],
+        InputMode::Renaming => &[
+            Keys(&["confirm_rename"], "Confirm"),
+            Keys(&["cancel_rename"], "Cancel"),
+            Fixed("Type new name"),
+        ],
+        InputMode::StyleNotes => &[
+            Keys(&["confirm_style_notes"], "Save"),
+            Keys(&["cancel_style_notes"], "Cancel"),
+            Fixed("e.g. 'formal, no emoji' (empty clears)"),
+        ],
+        InputMode::ChatMenu => &[
+            Keys(&["chat_menu_next", "chat_menu_prev"], "Navigate"),
+            Keys(&["chat_menu_confirm"], "Confirm"),
+            Keys(&["chat_menu_close"], "Close"),
+        ],
+        InputMode::Searching => &[
+            Fixed("Type to filter"),
+            Keys(&["search_next", "search_prev"], "Navigate"),
+            Keys(&["search_confirm"], "Open+Insert"),
+            Keys(&["search_toggle_mode"], "Chats/Messages"),
+            Keys(&["search_close"], "Cancel"),
+        ],
+        InputMode::MessageSelect => &[
+            Keys(&["message_select_next", "message_select_prev"], "Navigate"),
+            Keys(&["message_select_copy"], "Copy"),
+            Keys(&["open_media"], "Open"),
+            Keys(&["translate_message"], "Translate"),
+            Keys(&["message_select_exit"], "Cancel"),
+        ],
+        InputMode::SchedulePrompt => &[
+            Fixed("Type time (e.g. 'tomorrow 9am', 'Mar 15 14:30', 'every monday 9am')"),
+            Keys(&["schedule_confirm"], "Confirm"),
+            Keys(&["schedule_cancel"], "Cancel"),
+        ],
+        InputMode::ScheduleList => &[
+            Keys(&["schedule_list_next", "schedule_list_prev"], "Navigate"),
+            Keys(&["schedule_list_edit"], "Edit"),
+            Keys(&["schedule_list_edit_time"], "Time"),
+            Keys(&["schedule_list_repeat"], "Repeat"),
+            Keys(&["schedule_list_send_now"], "Send now"),
+            Keys(&["schedule_list_delete"], "Cancel"),
+            Keys(&["schedule_list_close"], "Close"),
+        ],
+        InputMode::TelegramAuth => &[
+            Fixed("Type"),
+            Keys(&["telegram_auth_submit"], "Confirm"),
+            Keys(&["telegram_auth_cancel"], "Cancel"),
+        ],
+        InputMode::Help => &[
+            Keys(&["help_scroll_down", "help_scroll_up"], "Scroll"),
+            Keys(&["help_close"], "Close"),
+        ],
+        InputMode::CatchUp => &[
+            Keys(&["catch_up_next", "catch_up_prev"], "Navigate"),
+            Keys(&["catch_up_open"], "Open chat"),
+            Keys(&["catch_up_close"], "Close"),
+        ],
+        InputMode::SentRequests => &[
+            Keys(
+                &["sent_requests_scroll_down", "sent_requests_scroll_up"],
+                "Scroll",
+            ),
+            Keys(&["sent_requests_close"], "Close"),
+        ],
+        InputMode::Palette => &[
+            Fixed("Type to filter"),
+            Keys(&["palette_next", "palette_prev"], "Navigate"),
+            Keys(&["palette_confirm"], "Run"),
+            Keys(&["palette_close"], "Cancel"),
+        ],
     }
 }
 
-fn map_renaming_mode(key: KeyEvent) -> Action {
-    match (key.code, key.modifiers) {
-        (KeyCode::Esc, _) => Action::CancelRename,
-        // Plain Enter confirms rename (single-line context)
-        (KeyCode::Enter, m) if m == KeyModifiers::NONE => Action::ConfirmRename,
+/// Keys not claimed by any binding: text entry for the input modes.
+fn fallback(key: KeyEvent, mode: InputMode, enter_sends: bool) -> Action {
+    match mode {
+        InputMode::Editing => match (key.code, key.modifiers) {
+            // enter_sends=true (default): plain Enter submits, Shift/Alt+Enter inserts newline
+            (KeyCode::Enter, m) if enter_sends && m == KeyModifiers::NONE => Action::SubmitMessage,
+            (KeyCode::Enter, _) if enter_sends => Action::InputKey(key), // Shift/Alt+Enter → forward to textarea as newline
+
+            // enter_sends=false: Shift/Alt+Enter submits, plain Enter inserts newline
+            (KeyCode::Enter, m) if !enter_sends && m.contains(KeyModifiers::SHIFT) => {
+                Action::SubmitMessage
+            }
+            (KeyCode::Enter, m) if !enter_sends && m.contains(KeyModifiers::ALT) => {
+                Action::SubmitMessage
+            }
+
+            // Ctrl+J: WSL-friendly newline insert (Shift+Enter not reliably transmitted in WSL)
+            (KeyCode::Char('j'), m) if m.contains(KeyModifiers::CONTROL) => {
+                Action::InputKey(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            }
+            // Everything else forwarded to TextArea
+            _ => Action::InputKey(key),
+        },
         // Block Shift+Enter / Alt+Enter from inserting newlines into a chat name
-        (KeyCode::Enter, _) => Action::None,
-        _ => Action::InputKey(key),
+        InputMode::Renaming if key.code == KeyCode::Enter => Action::None,
+        InputMode::Renaming => Action::InputKey(key),
+        InputMode::StyleNotes if key.code == KeyCode::Enter => Action::None,
+        InputMode::StyleNotes => Action::InputKey(key),
+        InputMode::Searching => Action::SearchInput(key),
+        InputMode::SchedulePrompt => Action::ScheduleInput(key),
+        InputMode::Palette => Action::PaletteInput(key),
+        InputMode::TelegramAuth => match key.code {
+            KeyCode::Char(c) => Action::TelegramAuthChar(c),
+            _ => Action::None,
+        },
+        _ => Action::None,
     }
 }
 
-fn map_message_select_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Char('k') | KeyCode::Up => Action::MessageSelectPrev,
-        KeyCode::Char('j') | KeyCode::Down => Action::MessageSelectNext,
-        KeyCode::Char('y') => Action::MessageSelectCopy,
-        KeyCode::Enter => Action::OpenMedia,
-        KeyCode::Esc | KeyCode::Char('q') => Action::MessageSelectExit,
-        _ => Action::None,
+/// A single key press with normalized modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    fn from_event(key: KeyEvent) -> Self {
+        let mut modifiers =
+            key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        // Shift is already part of the character ('G', '?'), and BackTab implies it
+        if matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self::new(key.code, modifiers)
     }
 }
 
-fn map_schedule_prompt_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Esc => Action::ScheduleCancel,
-        KeyCode::Enter => Action::ScheduleConfirm,
-        _ => Action::ScheduleInput(key),
+/// Parse a key spec into a key sequence. Tokens are separated by spaces;
+/// each token is a named key or character with optional `ctrl+`/`alt+`/`shift+`
+/// prefixes (`ctrl+l`, `shift+tab`, `f5`), or an unmodified run of characters
+/// typed in order (`gg`).
+pub fn parse_key_sequence(spec: &str) -> Result<Vec<KeyChord>, String> {
+    let mut keys = Vec::new();
+    for token in spec.split_whitespace() {
+        keys.extend(parse_key_token(token)?);
+    }
+    if keys.is_empty() {
+        return Err("empty key".to_string());
     }
+    Ok(keys)
 }
 
-fn map_schedule_list_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => Action::ScheduleListNext,
-        KeyCode::Char('k') | KeyCode::Up => Action::ScheduleListPrev,
-        KeyCode::Char('d') => Action::ScheduleListDelete,
-        KeyCode::Esc | KeyCode::Char('q') => Action::ScheduleListClose,
-        _ => Action::None,
+fn parse_key_token(token: &str) -> Result<Vec<KeyChord>, String> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = token;
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        let found = [
+            ("ctrl+", KeyModifiers::CONTROL),
+            ("alt+", KeyModifiers::ALT),
+            ("shift+", KeyModifiers::SHIFT),
+        ]
+        .into_iter()
+        .find(|(p, _)| lower.starts_with(p) && lower.len() > p.len());
+        match found {
+            Some((prefix, m)) => {
+                modifiers |= m;
+                rest = &rest[prefix.len()..];
+            }
+            None => break,
+        }
+    }
+
+    if let Some(code) = named_key(&rest.to_ascii_lowercase()) {
+        let code = if code == KeyCode::Tab && modifiers.contains(KeyModifiers::SHIFT) {
+            KeyCode::BackTab
+        } else {
+            code
+        };
+        return Ok(vec![KeyChord::from_event(KeyEvent::new(code, modifiers))]);
     }
+
+    let chars: Vec<char> = rest.chars().collect();
+    if chars.len() == 1 {
+        let c = if modifiers.contains(KeyModifiers::SHIFT) {
+            chars[0].to_ascii_uppercase()
+        } else {
+            chars[0]
+        };
+        return Ok(vec![KeyChord::from_event(KeyEvent::new(
+            KeyCode::Char(c),
+            modifiers,
+        ))]);
+    }
+    if modifiers.is_empty() {
+        return Ok(chars
+            .into_iter()
+            .map(|c| KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE))
+            .collect());
+    }
+    Err(format!("unknown key '{}'", rest))
 }
 
-fn map_telegram_auth_mode(key: KeyEvent) -> Action {
-    match key.code {
-        KeyCode::Esc => Action::TelegramAuthCancel,
-        KeyCode::Enter => Action::TelegramAuthSubmit,
-        KeyCode::Backspace => Action::TelegramAuthBackspace,
-        KeyCode::Char(c) => Action::TelegramAuthChar(c),
-        _ => Action::None,
+fn named_key(name: &str) -> Option<KeyCode> {
+    let code = match name {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "space" => KeyCode::Char(' '),
+        _ => {
+            let n: u8 = name.strip_prefix('f')?.parse().ok()?;
+            if (1..=24).contains(&n) {
+                KeyCode::F(n)
+            } else {
+                return None;
+            }
+        }
+    };
+    Some(code)
+}
+
+#[derive(Debug, Clone)]
+struct Binding {
+    keys: Vec<KeyChord>,
+    spec: String,
+    action: Action,
+}
+
+/// Two bindings conflict when one key sequence equals or prefixes the other.
+fn conflicts(a: &[KeyChord], b: &[KeyChord]) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+/// One mode's active bindings, grouped by action, for the help overlay.
+#[derive(Debug, Clone)]
+pub struct HelpSection {
+    pub mode: &'static str,
+    pub entries: Vec<(String, &'static str)>,
+}
+
+/// Per-mode key → action lookup built from the defaults plus `[keybindings]`
+/// overrides. Holds the pending prefix of a multi-key sequence between presses.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<InputMode, Vec<Binding>>,
+    pending: Vec<KeyEvent>,
+    pending_mode: Option<InputMode>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).expect("built-in keybindings are valid")
+    }
+}
+
+impl Keymap {
+    /// Build the keymap, layering user bindings over the defaults. A user binding
+    /// shadows every default it collides with. Unknown modes, keys and actions,
+    /// and conflicts between user bindings, are all reported in one error.
+    pub fn from_config(
+        overrides: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> anyhow::Result<Self> {
+        let mut errors = Vec::new();
+        let mut bindings = HashMap::new();
+
+        for &(mode, mode_name) in MODE_NAMES {
+            let defaults: Vec<Binding> = default_bindings(mode)
+                .iter()
+                .map(|(spec, action)| Binding {
+                    keys: parse_key_sequence(spec).expect("default key spec"),
+                    spec: spec.to_string(),
+                    action: Action::from_name(action).expect("default action name"),
+                })
+                .collect();
+
+            let Some(table) = overrides.get(mode_name) else {
+                bindings.insert(mode, defaults);
+                continue;
+            };
+
+            let mut user: Vec<Binding> = Vec::new();
+            for (spec, action_name) in table {
+                let keys = match parse_key_sequence(spec) {
+                    Ok(keys) => keys,
+                    Err(e) => {
+                        errors.push(format!("[keybindings.{}] \"{}\": {}", mode_name, spec, e));
+                        continue;
+                    }
+                };
+                let action = match Action::from_name(action_name) {
+                    Some(a)
+                        if a == Action::None
+                            || defaults.iter().any(|d| d.action == a)
+                            || unbound_actions(mode).contains(&action_name.as_str()) =>
+                    {
+                        a
+                    }
+                    Some(_) => {
+                        errors.push(format!(
+                            "[keybindings.{}] \"{}\": action '{}' is not available in this mode",
+                            mode_name, spec, action_name
+                        ));
+                        continue;
+                    }
+                    None => {
+                        errors.push(format!(
+                            "[keybindings.{}] \"{}\": unknown action '{}'",
+                            mode_name, spec, action_name
+                        ));
+                        continue;
+                    }
+                };
+                if let Some(other) = user.iter().find(|b| conflicts(&b.keys, &keys)) {
+                    errors.push(format!(
+                        "[keybindings.{}] \"{}\" conflicts with \"{}\"",
+                        mode_name, spec, other.spec
+                    ));
+                    continue;
+                }
+                user.push(Binding {
+                    keys,
+                    spec: spec.clone(),
+                    action,
+                });
+            }
+
+            let shadowed = |d: &Binding| user.iter().any(|u| conflicts(&u.keys, &d.keys));
+            let kept: Vec<Binding> = defaults.into_iter().filter(|d| !shadowed(d)).collect();
+            user.extend(kept);
+            bindings.insert(mode, user);
+        }
+
+        for mode_name in overrides.keys() {
+            if mode_from_name(mode_name).is_none() {
+                errors.push(format!("[keybindings.{}]: unknown mode", mode_name));
+            }
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!("Invalid keybindings:\n  {}", errors.join("\n  "));
+        }
+        Ok(Self {
+            bindings,
+            pending: Vec::new(),
+            pending_mode: None,
+        })
+    }
+
+    /// Resolve a key press into the actions to run, in order. A key that only
+    /// starts a multi-key binding yields nothing until the sequence completes;
+    /// if the sequence breaks, the buffered keys act on their own first (so
+    /// text typed into an input is kept) and the new key is resolved after them.
+    pub fn resolve(&mut self, key: KeyEvent, mode: InputMode, enter_sends: bool) -> Vec<Action> {
+        if self.pending_mode != Some(mode) {
+            self.pending.clear();
+            self.pending_mode = Some(mode);
+        }
+        self.pending.push(key);
+        let chords: Vec<KeyChord> = self
+            .pending
+            .iter()
+            .map(|k| KeyChord::from_event(*k))
+            .collect();
+
+        let bindings = self.bindings.get(&mode).map(Vec::as_slice).unwrap_or(&[]);
+        if let Some(b) = bindings.iter().find(|b| b.keys == chords) {
+            self.pending.clear();
+            return vec![b.action.clone()];
+        }
+        if bindings.iter().any(|b| b.keys.starts_with(&chords)) {
+            return Vec::new();
+        }
+
+        let mut buffered = std::mem::take(&mut self.pending);
+        buffered.pop();
+        if buffered.is_empty() {
+            return vec![fallback(key, mode, enter_sends)];
+        }
+        let mut actions: Vec<Action> = buffered
+            .into_iter()
+            .map(|k| self.resolve_single(k, mode, enter_sends))
+            .collect();
+        actions.extend(self.resolve(key, mode, enter_sends));
+        actions
+    }
+
+    /// `key` on its own: its single-key binding, or the mode's fallback.
+    fn resolve_single(&self, key: KeyEvent, mode: InputMode, enter_sends: bool) -> Action {
+        let chord = [KeyChord::from_event(key)];
+        self.bindings
+            .get(&mode)
+            .into_iter()
+            .flatten()
+            .find(|b| b.keys == chord)
+            .map(|b| b.action.clone())
+            .unwrap_or_else(|| fallback(key, mode, enter_sends))
+    }
+
+    /// Key specs bound to `action` in `mode`, in lookup order.
+    fn specs(&self, mode: InputMode, action: &Action) -> Vec<&str> {
+        self.bindings
+            .get(&mode)
+            .into_iter()
+            .flatten()
+            .filter(|b| &b.action == action)
+            .map(|b| b.spec.as_str())
+            .collect()
+    }
+
+    /// Keys bound to `action` in `mode`, e.g. `"ctrl+l"` or `"j, down"`.
+    pub fn keys_for(&self, mode: InputMode, action: &Action) -> Option<String> {
+        let specs = self.specs(mode, action);
+        if specs.is_empty() {
+            None
+        } else {
+            Some(specs.join(", "))
+        }
+    }
+
+    /// Status bar hints for `mode` with the active keys, e.g.
+    /// `"q/ctrl+c:Quit | i/enter:Insert"`. Hints whose actions are unbound
+    /// are left out.
+    pub fn hints(&self, mode: InputMode, enter_sends: bool) -> String {
+        let specs = |name: &str| {
+            Action::from_name(name)
+                .map(|action| self.specs(mode, &action))
+                .unwrap_or_default()
+        };
+        mode_hints(mode, enter_sends)
+            .iter()
+            .filter_map(|hint| match *hint {
+                Hint::Fixed(text) => Some(text.to_string()),
+                Hint::Keys(names, label) => {
+                    let keys: Vec<&str> = match names {
+                        [name] => specs(name),
+                        _ => names
+                            .iter()
+                            .filter_map(|name| specs(name).first().copied())
+                            .collect(),
+                    };
+                    (!keys.is_empty()).then(|| format!("{}:{}", keys.join("/"), label))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Active bindings for every mode, keys grouped by action.
+    pub fn describe(&self) -> Vec<HelpSection> {
+        MODE_NAMES
+            .iter()
+            .map(|&(mode, mode_name)| {
+                let mut entries: Vec<(String, &'static str)> = Vec::new();
+                for b in self.bindings.get(&mode).into_iter().flatten() {
+                    let Some(name) = b.action.name().filter(|n| *n != "none") else {
+                        continue;
+                    };
+                    match entries.iter_mut().find(|(_, n)| *n == name) {
+                        Some((keys, _)) => {
+                            keys.push_str(", ");
+                            keys.push_str(&b.spec);
+                        }
+                        None => entries.push((b.spec.clone(), name)),
+                    }
+                }
+                HelpSection {
+                    mode: mode_name,
+                    entries,
+                }
+            })
+            .collect()
     }
 }
 
@@ -220,6 +950,21 @@ mod tests {
         KeyEvent::new(code, KeyModifiers::NONE)
     }
 
+    fn map_key(key: KeyEvent, mode: InputMode, enter_sends: bool) -> Action {
+        Keymap::default()
+            .resolve(key, mode, enter_sends)
+            .pop()
+            .unwrap_or(Action::None)
+    }
+
+    fn overrides(mode: &str, pairs: &[(&str, &str)]) -> BTreeMap<String, BTreeMap<String, String>> {
+        let table = pairs
+            .iter()
+            .map(|(k, a)| (k.to_string(), a.to_string()))
+            .collect();
+        BTreeMap::from([(mode.to_string(), table)])
+    }
+
     #[test]
     fn enter_in_message_select_maps_to_open_media() {
         let action = map_key(key(KeyCode::Enter), InputMode::MessageSelect, true);
@@ -232,9 +977,200 @@ mod tests {
         assert_eq!(action, Action::MessageSelectCopy);
     }
 
+    #[test]
+    fn ctrl_g_in_editing_opens_external_editor() {
+        let action = map_key(
+            KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL),
+            InputMode::Editing,
+            true,
+        );
+        assert_eq!(action, Action::ComposeInEditor);
+    }
+
     #[test]
     fn esc_in_message_select_maps_to_exit() {
         let action = map_key(key(KeyCode::Esc), InputMode::MessageSelect, true);
         assert_eq!(action, Action::MessageSelectExit);
     }
+
+    #[test]
+    fn enter_sends_setting_still_applies() {
+        assert_eq!(
+            map_key(key(KeyCode::Enter), InputMode::Editing, true),
+            Action::SubmitMessage
+        );
+        assert!(matches!(
+            map_key(key(KeyCode::Enter), InputMode::Editing, false),
+            Action::InputKey(_)
+        ));
+    }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        assert_eq!(
+            parse_key_sequence("ctrl+l").unwrap(),
+            vec![KeyChord::new(KeyCode::Char('l'), KeyModifiers::CONTROL)]
+        );
+        assert_eq!(
+            parse_key_sequence("shift+tab").unwrap(),
+            vec![KeyChord::new(KeyCode::BackTab, KeyModifiers::NONE)]
+        );
+        assert_eq!(
+            parse_key_sequence("shift+g").unwrap(),
+            parse_key_sequence("G").unwrap()
+        );
+        assert_eq!(parse_key_sequence("gg").unwrap().len(), 2);
+        assert!(parse_key_sequence("ctrl+bogus").is_err());
+    }
+
+    #[test]
+    fn gg_sequence_jumps_to_first_chat() {
+        let mut keymap = Keymap::default();
+        let g = key(KeyCode::Char('g'));
+        assert!(keymap.resolve(g, InputMode::Normal, true).is_empty());
+        assert_eq!(
+            keymap.resolve(g, InputMode::Normal, true),
+            [Action::FirstChat]
+        );
+    }
+
+    #[test]
+    fn broken_sequence_resolves_new_key() {
+        let mut keymap = Keymap::default();
+        keymap.resolve(key(KeyCode::Char('g')), InputMode::Normal, true);
+        assert_eq!(
+            keymap.resolve(key(KeyCode::Char('j')), InputMode::Normal, true),
+            [Action::None, Action::NextChat]
+        );
+    }
+
+    #[test]
+    fn broken_sequence_keeps_typed_text() {
+        let mut keymap =
+            Keymap::from_config(&overrides("editing", &[("jk", "exit_editing")])).unwrap();
+        let j = key(KeyCode::Char('j'));
+        let x = key(KeyCode::Char('x'));
+        assert!(keymap.resolve(j, InputMode::Editing, true).is_empty());
+        assert_eq!(
+            keymap.resolve(x, InputMode::Editing, true),
+            [Action::InputKey(j), Action::InputKey(x)]
+        );
+        keymap.resolve(j, InputMode::Editing, true);
+        assert_eq!(
+            keymap.resolve(key(KeyCode::Char('k')), InputMode::Editing, true),
+            [Action::ExitEditing]
+        );
+    }
+
+    #[test]
+    fn user_binding_shadows_default() {
+        let mut keymap = Keymap::from_config(&overrides(
+            "normal",
+            &[("ctrl+l", "open_search"), ("q", "none")],
+        ))
+        .unwrap();
+        let ctrl_l = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        assert_eq!(
+            keymap.resolve(ctrl_l, InputMode::Normal, true),
+            [Action::OpenSearch]
+        );
+        assert_eq!(
+            keymap.resolve(key(KeyCode::Char('q')), InputMode::Normal, true),
+            [Action::None]
+        );
+        // Untouched defaults survive
+        assert_eq!(
+            keymap.resolve(key(KeyCode::Char('j')), InputMode::Normal, true),
+            [Action::NextChat]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_action_and_mode() {
+        let err = Keymap::from_config(&overrides("normal", &[("z", "launch_rockets")]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown action 'launch_rockets'"));
+        assert!(Keymap::from_config(&overrides("nowhere", &[("z", "quit")])).is_err());
+    }
+
+    #[test]
+    fn rejects_action_from_another_mode() {
+        let err = Keymap::from_config(&overrides("search", &[("ctrl+q", "quit")]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not available"));
+    }
+
+    #[test]
+    fn rejects_conflicting_user_bindings() {
+        let err = Keymap::from_config(&overrides("normal", &[("z", "quit"), ("zz", "first_chat")]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("conflicts"));
+        let err = Keymap::from_config(&overrides(
+            "normal",
+            &[
+                ("ctrl+l", "quit"),
+                ("ctrl+L", "open_search"),
+                ("Ctrl+l", "quit"),
+            ],
+        ));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn unbound_actions_can_be_bound() {
+        let mut keymap =
+            Keymap::from_config(&overrides("normal", &[("M", "toggle_mute")])).unwrap();
+        assert_eq!(
+            keymap.resolve(key(KeyCode::Char('M')), InputMode::Normal, true),
+            [Action::ToggleMute]
+        );
+        assert_eq!(
+            keymap.keys_for(InputMode::Normal, &Action::ToggleMute),
+            Some("M".to_string())
+        );
+    }
+
+    #[test]
+    fn hints_follow_remapped_keys() {
+        let defaults = Keymap::default().hints(InputMode::Normal, true);
+        assert!(
+            defaults.starts_with("q/ctrl+c:Quit | i/enter:Insert"),
+            "{}",
+            defaults
+        );
+        assert!(defaults.contains("ctrl+l:Scheduled"), "{}", defaults);
+        assert_eq!(
+            Keymap::default().hints(InputMode::Help, true),
+            "j/k:Scroll | esc/q/?:Close"
+        );
+
+        let keymap = Keymap::from_config(&overrides(
+            "normal",
+            &[
+                ("ctrl+l", "none"),
+                ("S", "open_schedule_list"),
+                ("?", "none"),
+            ],
+        ))
+        .unwrap();
+        let hints = keymap.hints(InputMode::Normal, true);
+        assert!(hints.contains("S:Scheduled"), "{}", hints);
+        assert!(!hints.contains("ctrl+l"), "{}", hints);
+        assert!(!hints.contains("Help"), "{}", hints);
+    }
+
+    #[test]
+    fn describe_groups_keys_by_action() {
+        let sections = Keymap::default().describe();
+        let normal = sections.iter().find(|s| s.mode == "normal").unwrap();
+        let next = normal
+            .entries
+            .iter()
+            .find(|(_, a)| *a == "next_chat")
+            .unwrap();
+        assert_eq!(next.0, "j, down");
+    }
 }
@@ -168,6 +168,17 @@ pub async fn open_image(url: String) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Open a web link in the default browser.
+pub async fn open_url(url: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        url.starts_with("http://") || url.starts_with("https://"),
+        "not a web link: {}",
+        url
+    );
+    spawn_os_opener(std::path::Path::new(url)).await;
+    Ok(())
+}
+
 /// Spawn the OS default image viewer for `path` and wait for it to exit.
 /// Uses `spawn_blocking` so the `.wait()` call does not block the async executor.
 /// Waiting prevents zombie processes in the kernel process table.
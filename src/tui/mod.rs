@@ -1,9 +1,16 @@
 pub mod app_state;
+pub mod editor;
 pub mod event;
+pub mod image_preview;
 pub mod keybindings;
 pub mod media;
+pub mod mouse;
 pub mod osc8;
+pub mod palette;
 pub mod render;
+pub mod scheduler;
 pub mod search;
+pub mod slash_commands;
+pub mod theme;
 pub mod time_parse;
 pub mod widgets;
@@ -0,0 +1,79 @@
+use ratatui::layout::{Position, Rect};
+
+/// Something clickable, recorded while rendering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HitTarget {
+    ChatList,
+    /// Index into `AppState::folders`.
+    Folder(usize),
+    /// Index into `AppState::chats`.
+    Chat(usize),
+    MessageView,
+    /// Index into `AppState::messages`.
+    Message(usize),
+    Url(String),
+    Input,
+    /// Index into `AppState::smart_replies`.
+    SmartReply(usize),
+    SettingsItem(usize),
+    /// Position in the search results.
+    SearchResult(usize),
+    ChatMenuItem(usize),
+    /// Popup background; keeps clicks from reaching widgets underneath.
+    Overlay,
+}
+
+/// Screen regions of the last frame, in draw order.
+#[derive(Debug, Default)]
+pub struct HitMap {
+    regions: Vec<(Rect, HitTarget)>,
+}
+
+impl HitMap {
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    pub fn push(&mut self, area: Rect, target: HitTarget) {
+        if !area.is_empty() {
+            self.regions.push((area, target));
+        }
+    }
+
+    /// Topmost target under a cell: regions pushed later were drawn on top.
+    pub fn at(&self, column: u16, row: u16) -> Option<&HitTarget> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, target)| target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_regions_win() {
+        let mut hits = HitMap::default();
+        hits.push(Rect::new(0, 0, 20, 10), HitTarget::MessageView);
+        hits.push(Rect::new(0, 2, 20, 1), HitTarget::Message(3));
+        hits.push(
+            Rect::new(5, 2, 4, 1),
+            HitTarget::Url("https://a.b".to_string()),
+        );
+        hits.push(Rect::new(0, 0, 0, 5), HitTarget::Overlay);
+
+        assert_eq!(hits.at(1, 1), Some(&HitTarget::MessageView));
+        assert_eq!(hits.at(1, 2), Some(&HitTarget::Message(3)));
+        assert_eq!(
+            hits.at(6, 2),
+            Some(&HitTarget::Url("https://a.b".to_string()))
+        );
+        assert_eq!(hits.at(20, 2), None);
+
+        hits.clear();
+        assert_eq!(hits.at(1, 1), None);
+    }
+}
@@ -4,27 +4,27 @@ use crossterm::{cursor::MoveTo, execute, style::Print};
 use ratatui::{buffer::Buffer, style::Color};
 
 /// After `terminal.draw()` flushes, scan the completed buffer for contiguous
-/// Color::LightBlue cell runs (URL spans), reconstruct the URL text, then
+/// cell runs in the theme's URL color, reconstruct the URL text, then
 /// re-print those characters to stdout wrapped in OSC 8 hyperlink sequences.
 ///
 /// This approach avoids modifying ratatui Cell symbols (which would corrupt
 /// ratatui's unicode-width based diff algorithm) and instead writes directly
 /// to stdout after the frame has been flushed.
-pub fn inject_osc8_hyperlinks(buffer: &Buffer) -> io::Result<()> {
+pub fn inject_osc8_hyperlinks(buffer: &Buffer, url_color: Color) -> io::Result<()> {
     let area = buffer.area;
     let mut stdout = io::stdout().lock();
 
     for row in area.top()..area.bottom() {
         let mut col = area.left();
         while col < area.right() {
-            if buffer[(col, row)].fg != Color::LightBlue {
+            if buffer[(col, row)].fg != url_color {
                 col += 1;
                 continue;
             }
             // Collect the full text of this contiguous blue run
             let run_start = col;
             let mut url = String::new();
-            while col < area.right() && buffer[(col, row)].fg == Color::LightBlue {
+            while col < area.right() && buffer[(col, row)].fg == url_color {
                 url.push_str(buffer[(col, row)].symbol());
                 col += 1;
             }
@@ -0,0 +1,107 @@
+use super::keybindings::{Action, Keymap};
+use super::search::fuzzy_score;
+use crate::tui::app_state::InputMode;
+
+/// Commands offered by the palette, in the order shown for an empty query.
+const COMMANDS: &[(&str, Action)] = &[
+    ("Go to chat…", Action::OpenSearch),
+    ("Compose message", Action::EnterEditing),
+    ("Schedule message…", Action::ScheduleMessage),
+    ("Scheduled messages", Action::OpenScheduleList),
+    ("Mute / unmute chat", Action::ToggleMute),
+    ("Pin / unpin chat", Action::TogglePin),
+    ("Archive / unarchive chat", Action::ToggleArchive),
+    ("Rename chat", Action::RenameChat),
+    ("Export chat", Action::ExportChat),
+    ("Chat menu", Action::OpenChatMenu),
+    ("Copy last message", Action::CopyLastMessage),
+    ("Select messages", Action::EnterMessageSelect),
+    ("Next chat", Action::NextChat),
+    ("Previous chat", Action::PrevChat),
+    ("First chat", Action::FirstChat),
+    ("Last chat", Action::LastChat),
+    ("Next folder", Action::NextFolder),
+    ("Previous folder", Action::PrevFolder),
+    ("Scroll messages up", Action::ScrollUp),
+    ("Scroll messages down", Action::ScrollDown),
+    ("Switch panel", Action::SwitchPanel),
+    ("Toggle activity graph", Action::ToggleActivityGraph),
+    ("Toggle away auto-reply", Action::ToggleAway),
+    ("Catch me up on this chat", Action::CatchUp),
+    ("Catch me up on all unread chats", Action::CatchUpAll),
+    ("Show what was sent to AI", Action::OpenSentRequests),
+    ("Settings", Action::OpenSettings),
+    ("Keybindings", Action::OpenHelp),
+    ("Quit", Action::Quit),
+];
+
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub title: &'static str,
+    /// Normal-mode keys for the action, shown as a hint ("" if unbound).
+    pub keys: String,
+    pub action: Action,
+}
+
+/// Every palette command, annotated with its current keybinding.
+pub fn palette_entries(keymap: &Keymap) -> Vec<PaletteEntry> {
+    COMMANDS
+        .iter()
+        .map(|(title, action)| PaletteEntry {
+            title,
+            keys: keymap
+                .keys_for(InputMode::Normal, action)
+                .or_else(|| keymap.keys_for(InputMode::Editing, action))
+                .unwrap_or_default(),
+            action: action.clone(),
+        })
+        .collect()
+}
+
+/// Indices into `entries` matching `query`, best fuzzy match first.
+/// An empty query keeps the declared order.
+pub fn filter_entries(query: &str, entries: &[PaletteEntry]) -> Vec<usize> {
+    let mut scored: Vec<(usize, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| fuzzy_score(query, e.title).map(|s| (i, s)))
+        .collect();
+    scored.sort_by_key(|&(_, s)| s);
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_lists_everything_in_order() {
+        let entries = palette_entries(&Keymap::default());
+        let results = filter_entries("", &entries);
+        assert_eq!(results.len(), entries.len());
+        assert_eq!(entries[results[0]].title, "Go to chat…");
+    }
+
+    #[test]
+    fn fuzzy_query_ranks_tight_matches_first() {
+        let entries = palette_entries(&Keymap::default());
+        let results = filter_entries("mute", &entries);
+        assert_eq!(entries[results[0]].action, Action::ToggleMute);
+        assert!(filter_entries("zzzz", &entries).is_empty());
+    }
+
+    #[test]
+    fn entries_show_current_keys() {
+        let entries = palette_entries(&Keymap::default());
+        let sched = entries
+            .iter()
+            .find(|e| e.action == Action::OpenScheduleList)
+            .unwrap();
+        assert_eq!(sched.keys, "ctrl+l");
+        let export = entries
+            .iter()
+            .find(|e| e.action == Action::ExportChat)
+            .unwrap();
+        assert!(export.keys.is_empty());
+    }
+}
@@ -6,13 +6,22 @@ use ratatui::{
     Frame,
 };
 
-use super::app_state::{AppState, InputMode};
+use super::app_state::{AppState, InputMode, ScheduleTarget};
+use super::mouse::HitTarget;
 use super::widgets::{
-    self, chat_list, input_bar, message_view, qr_overlay, settings_overlay, status_bar,
-    telegram_auth_overlay,
+    self, chat_list, input_bar, message_view, qr_overlay, settings_overlay, smart_replies,
+    status_bar, telegram_auth_overlay,
 };
 
 pub fn draw(f: &mut Frame, state: &mut AppState) {
+    state.hit_map.clear();
+    if state.theme.canvas != Color::Reset {
+        f.render_widget(
+            Block::default().style(Style::default().bg(state.theme.canvas).fg(state.theme.text)),
+            f.area(),
+        );
+    }
+
     let outer = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(1), Constraint::Length(1)])
@@ -32,18 +41,21 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
     // Input box grows with content: +2 for borders, clamped between 3 (1 line) and 8 (6 lines)
     let input_height = (state.input.lines().len() as u16 + 2).clamp(3, 8);
     let debug_height: u16 = if state.ai_debug { 8 } else { 0 };
+    let replies_height: u16 = if state.smart_replies.is_empty() { 0 } else { 1 };
     let message_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(1),
+            Constraint::Length(replies_height),
             Constraint::Length(input_height),
             Constraint::Length(debug_height),
         ])
         .split(message_area);
 
     let message_view_area = message_layout[0];
-    let input_area = message_layout[1];
-    let debug_area = message_layout[2];
+    let replies_area = message_layout[1];
+    let input_area = message_layout[2];
+    let debug_area = message_layout[3];
 
     // Get current chat name
     let chat_name = state
@@ -53,10 +65,14 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
         .map(|c| c.name.as_str())
         .unwrap_or("No chat selected");
 
+    let visible = state.visible_chats();
     chat_list::render_chat_list(
         f,
         chat_list_area,
         &state.chats,
+        &visible,
+        &state.folders,
+        state.active_folder,
         &mut state.chat_list_state,
         state.active_panel,
         state.input_mode,
@@ -64,6 +80,9 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
         state.blink_phase,
         &state.activity_cache,
         state.show_activity_graph,
+        &state.drafts,
+        &mut state.hit_map,
+        &state.theme,
     );
 
     message_view::render_message_view(
@@ -75,14 +94,31 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
         state.active_panel,
         state.new_message_count,
         state.selected_message_idx,
+        &state.translations,
+        &mut state.image_previews,
+        &mut state.hit_map,
+        &state.theme,
     );
+    if !state.smart_replies.is_empty() {
+        smart_replies::render_smart_replies(
+            f,
+            replies_area,
+            &state.smart_replies,
+            state.input_mode,
+            &mut state.hit_map,
+            &state.theme,
+        );
+    }
+    state.hit_map.push(input_area, HitTarget::Input);
 
     input_bar::render_input_bar(
         f,
         input_area,
         &state.input,
         state.input_mode,
+        state.editing_scheduled.is_some(),
         state.ai_suggestion.as_deref(),
+        &state.theme,
     );
 
     // AI debug panel
@@ -90,7 +126,7 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
         let log_lines: Vec<Line> = if state.ai_debug_log.is_empty() {
             vec![Line::from(Span::styled(
                 "  waiting for AI activity... (type something in INSERT mode)",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(state.theme.muted),
             ))]
         } else {
             state
@@ -98,11 +134,11 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
                 .iter()
                 .map(|entry| {
                     let color = if entry.starts_with("[error]") {
-                        Color::Red
+                        state.theme.error
                     } else if entry.starts_with("[suggestion]") {
-                        Color::Green
+                        state.theme.success
                     } else {
-                        Color::Cyan
+                        state.theme.accent
                     };
                     Line::from(Span::styled(entry.as_str(), Style::default().fg(color)))
                 })
@@ -112,7 +148,7 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
             Block::default()
                 .title(" AI Debug ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(state.theme.muted)),
         );
         f.render_widget(debug_widget, debug_area);
     }
@@ -121,54 +157,80 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
         f,
         status_area,
         state.input_mode,
-        state.enter_sends,
+        &state.key_hints,
         state.mock_enabled,
         state.whatsapp_connected,
+        state.away,
+        state.ai_enabled,
+        state.ai_usage,
+        state.ai_backend.as_deref(),
         state.copy_status.as_deref(),
-        state.schedule_status.as_deref(),
+        state.flash_status.as_deref(),
+        &state.theme,
     );
 
     // Render QR code overlay on top if present
     if let Some(ref qr) = state.qr_code {
-        qr_overlay::render_qr_overlay(f, qr);
+        qr_overlay::render_qr_overlay(f, qr, &state.theme);
     }
 
     // Render settings overlay on top if open
     if let Some(ref settings) = state.settings_state {
-        settings_overlay::render_settings_overlay(f, settings);
+        settings_overlay::render_settings_overlay(f, settings, &mut state.hit_map, &state.theme);
     }
 
     // Render chat context menu popup on top if active
     if state.input_mode == InputMode::ChatMenu {
         if let Some(ref menu_state) = state.chat_menu_state {
-            widgets::chat_menu::render_chat_menu(f, chat_list_area, menu_state);
+            widgets::chat_menu::render_chat_menu(
+                f,
+                chat_list_area,
+                menu_state,
+                &mut state.hit_map,
+                &state.theme,
+            );
         }
     }
 
     // Render search overlay on top if active
     if state.input_mode == InputMode::Searching {
         if let Some(ref search) = state.search_state {
-            widgets::search_overlay::render_search_overlay(f, chat_list_area, search, &state.chats);
+            widgets::search_overlay::render_search_overlay(
+                f,
+                chat_list_area,
+                search,
+                &state.chats,
+                &mut state.hit_map,
+                &state.theme,
+            );
         }
     }
 
     // Render schedule time prompt (replaces input area)
     if state.input_mode == InputMode::SchedulePrompt {
         if let Some(ref sp) = state.schedule_prompt_state {
+            let label = match sp.target {
+                ScheduleTarget::NewMessage => "Schedule for: ",
+                ScheduleTarget::Recurrence(_) => "Repeat (empty = once): ",
+                ScheduleTarget::SendTime(_) => "Move to: ",
+            };
             let prompt_line = Line::from(vec![
                 Span::styled(
-                    "Schedule for: ",
+                    label,
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(state.theme.highlight)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(format!("{}▌", sp.query), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("{}▌", sp.query),
+                    Style::default().fg(state.theme.text),
+                ),
             ]);
             let prompt_widget = Paragraph::new(prompt_line).block(
                 Block::default()
                     .title(" Schedule ")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Yellow)),
+                    .border_style(Style::default().fg(state.theme.highlight)),
             );
             f.render_widget(Clear, input_area);
             f.render_widget(prompt_widget, input_area);
@@ -188,12 +250,62 @@ pub fn draw(f: &mut Frame, state: &mut AppState) {
                     )
                 })
                 .collect();
-            widgets::schedule_overlay::render_schedule_list_overlay(f, sl, &chat_names);
+            widgets::schedule_overlay::render_schedule_list_overlay(
+                f,
+                sl,
+                &chat_names,
+                &state.theme,
+            );
+        }
+    }
+
+    // Render command palette
+    if state.input_mode == InputMode::Palette {
+        if let Some(ref palette) = state.palette_state {
+            widgets::palette_overlay::render_palette_overlay(f, palette, &state.theme);
+        }
+    }
+
+    // Render keybinding help overlay
+    if state.input_mode == InputMode::Help {
+        if let Some(ref help) = state.help_state {
+            widgets::help_overlay::render_help_overlay(f, help, &state.theme);
+        }
+    }
+
+    // Render catch-me-up summaries
+    if state.input_mode == InputMode::CatchUp {
+        if let Some(ref catch_up) = state.catch_up_state {
+            widgets::catch_up_overlay::render_catch_up_overlay(f, catch_up, &state.theme);
+        }
+    }
+
+    // Render the requests last sent to the AI provider
+    if state.input_mode == InputMode::SentRequests {
+        if let Some(ref mut sent) = state.sent_requests_state {
+            widgets::sent_requests_overlay::render_sent_requests_overlay(f, sent, &state.theme);
         }
     }
 
     // Render Telegram auth overlay on top if active
     if let Some(ref auth_state) = state.telegram_auth_state {
-        telegram_auth_overlay::render_telegram_auth_overlay(f, auth_state);
+        telegram_auth_overlay::render_telegram_auth_overlay(f, auth_state, &state.theme);
+    }
+
+    // Graphics-protocol thumbnails are painted after the frame, above every
+    // widget, so hide them while a centered overlay is open
+    let overlay_open = state.qr_code.is_some()
+        || state.settings_state.is_some()
+        || state.telegram_auth_state.is_some()
+        || matches!(
+            state.input_mode,
+            InputMode::ScheduleList
+                | InputMode::Help
+                | InputMode::Palette
+                | InputMode::CatchUp
+                | InputMode::SentRequests
+        );
+    if overlay_open {
+        state.image_previews.placements.clear();
     }
 }
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::{mpsc, watch};
+
+use crate::tui::event::AppEvent;
+
+/// Longest the timer sleeps before re-reading the clock, so a suspended
+/// laptop or a clock change is noticed within a minute.
+const MAX_SLEEP: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Delay before retrying a send that has failed `attempts` times: 30s,
+/// doubling each time, capped at an hour.
+pub fn retry_delay(attempts: u32) -> Duration {
+    let secs = 30i64.saturating_mul(1 << attempts.saturating_sub(1).min(20));
+    Duration::seconds(secs.min(3600))
+}
+
+/// What to do with messages that came due while the app was closed
+/// (`schedule.missed_sends`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissedSendPolicy {
+    /// Send them late, as soon as the app starts.
+    Send,
+    /// Drop one-shots; recurring messages move to their next occurrence.
+    Skip,
+    /// Hold them as `missed` until sent or cancelled from the schedule list.
+    Ask,
+}
+
+impl MissedSendPolicy {
+    pub fn from_setting(setting: &str) -> Self {
+        match setting {
+            "skip" => Self::Skip,
+            "ask" => Self::Ask,
+            other => {
+                if other != "send" {
+                    tracing::warn!("Unknown schedule.missed_sends '{}', using send", other);
+                }
+                Self::Send
+            }
+        }
+    }
+}
+
+/// Background timer that wakes the app with [`AppEvent::ScheduleDue`] when
+/// the earliest pending scheduled message is due.
+pub struct Scheduler {
+    next_due: watch::Sender<Option<DateTime<Utc>>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Scheduler {
+    pub fn start(event_tx: mpsc::UnboundedSender<AppEvent>) -> Self {
+        let (next_due, mut rx) = watch::channel(None::<DateTime<Utc>>);
+        let task = tokio::spawn(async move {
+            loop {
+                let due = *rx.borrow_and_update();
+                let wait = due
+                    .map(|at| (at - Utc::now()).to_std().unwrap_or_default())
+                    .unwrap_or(MAX_SLEEP)
+                    .min(MAX_SLEEP);
+                tokio::select! {
+                    changed = rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    _ = tokio::time::sleep(wait) => {
+                        if due.is_some_and(|at| at <= Utc::now()) {
+                            if event_tx.send(AppEvent::ScheduleDue).is_err() {
+                                break;
+                            }
+                            // Sleep until the app has sent them and reports the next due time
+                            if rx.changed().await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        Self { next_due, task }
+    }
+
+    /// Arm the timer for `at`; `None` when nothing is pending.
+    pub fn set_next_due(&self, at: Option<DateTime<Utc>>) {
+        self.next_due.send_replace(at);
+    }
+
+    pub fn shutdown(&self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        let delays: Vec<i64> = (1..=9).map(|n| retry_delay(n).num_seconds()).collect();
+        assert_eq!(delays, [30, 60, 120, 240, 480, 960, 1920, 3600, 3600]);
+        assert_eq!(retry_delay(u32::MAX).num_seconds(), 3600);
+    }
+
+    #[test]
+    fn missed_send_policy_from_setting() {
+        assert_eq!(MissedSendPolicy::from_setting("ask"), MissedSendPolicy::Ask);
+        assert_eq!(
+            MissedSendPolicy::from_setting("skip"),
+            MissedSendPolicy::Skip
+        );
+        assert_eq!(
+            MissedSendPolicy::from_setting("later"),
+            MissedSendPolicy::Send
+        );
+    }
+
+    #[tokio::test]
+    async fn fires_when_due() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let scheduler = Scheduler::start(tx);
+        scheduler.set_next_due(Some(Utc::now() + Duration::milliseconds(20)));
+        let event = tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+            .await
+            .unwrap();
+        assert!(matches!(event, Some(AppEvent::ScheduleDue)));
+        scheduler.shutdown();
+    }
+}
@@ -117,6 +117,7 @@ mod tests {
             kind: crate::core::types::ChatKind::Chat,
             is_pinned: false,
             is_muted: false,
+            is_archived: false,
         }
     }
 }
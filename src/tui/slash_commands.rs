@@ -0,0 +1,277 @@
+use std::path::PathBuf;
+
+use super::time_parse::{parse_schedule_time, ScheduleTime};
+
+/// A slash command known to the input bar.
+pub struct SlashCommandSpec {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub help: &'static str,
+}
+
+/// Registry used for parsing and Tab completion, in completion order.
+pub const SLASH_COMMANDS: &[SlashCommandSpec] = &[
+    SlashCommandSpec {
+        name: "me",
+        usage: "/me <action>",
+        help: "Send an action message",
+    },
+    SlashCommandSpec {
+        name: "schedule",
+        usage: "/schedule <time> <text>",
+        help: "Schedule a message, e.g. /schedule tomorrow 9am hi or /schedule every monday 9am hi",
+    },
+    SlashCommandSpec {
+        name: "mute",
+        usage: "/mute",
+        help: "Mute or unmute this chat",
+    },
+    SlashCommandSpec {
+        name: "pin",
+        usage: "/pin",
+        help: "Pin or unpin this chat",
+    },
+    SlashCommandSpec {
+        name: "archive",
+        usage: "/archive",
+        help: "Archive or unarchive this chat",
+    },
+    SlashCommandSpec {
+        name: "rename",
+        usage: "/rename [name]",
+        help: "Rename this chat",
+    },
+    SlashCommandSpec {
+        name: "attach",
+        usage: "/attach <path>",
+        help: "Send a file",
+    },
+    SlashCommandSpec {
+        name: "search",
+        usage: "/search [query]",
+        help: "Find a chat",
+    },
+    SlashCommandSpec {
+        name: "export",
+        usage: "/export",
+        help: "Export this chat to a text file",
+    },
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlashCommand {
+    Me(String),
+    Schedule { time: ScheduleTime, text: String },
+    Mute,
+    Pin,
+    Archive,
+    Rename(Option<String>),
+    Attach(PathBuf),
+    Search(String),
+    Export,
+}
+
+/// What the composer text turned out to be on submit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposerInput {
+    Message(String),
+    Command(SlashCommand),
+}
+
+/// Interpret submitted composer text. Text starting with `/` is a command;
+/// `//` escapes a literal leading slash. Errors are ready for the status bar.
+pub fn parse_input(input: &str) -> Result<ComposerInput, String> {
+    if let Some(rest) = input.strip_prefix("//") {
+        return Ok(ComposerInput::Message(format!("/{}", rest)));
+    }
+    let Some(rest) = input.strip_prefix('/') else {
+        return Ok(ComposerInput::Message(input.to_string()));
+    };
+
+    let (name, args) = match rest.find(char::is_whitespace) {
+        Some(i) => (&rest[..i], rest[i..].trim()),
+        None => (rest, ""),
+    };
+    let spec = SLASH_COMMANDS
+        .iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| format!("unknown command /{} (use // to send a leading /)", name))?;
+    let usage = || format!("usage: {}", spec.usage);
+
+    let command = match spec.name {
+        "me" if !args.is_empty() => SlashCommand::Me(args.to_string()),
+        "schedule" => {
+            let (time, text) = split_schedule_args(args).ok_or_else(usage)?;
+            SlashCommand::Schedule {
+                time,
+                text: text.to_string(),
+            }
+        }
+        "mute" => SlashCommand::Mute,
+        "pin" => SlashCommand::Pin,
+        "archive" => SlashCommand::Archive,
+        "rename" => SlashCommand::Rename(Some(args.to_string()).filter(|a| !a.is_empty())),
+        "attach" if !args.is_empty() => SlashCommand::Attach(expand_home(args)),
+        "search" => SlashCommand::Search(args.to_string()),
+        "export" => SlashCommand::Export,
+        _ => return Err(usage()),
+    };
+    Ok(ComposerInput::Command(command))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Split `<time> <text>` by trying the longest leading time phrase first
+/// ("cron 0 9 * * 1-5", …, "Mar 15 9am", then "tomorrow 9am", then "9am").
+fn split_schedule_args(args: &str) -> Option<(ScheduleTime, &str)> {
+    let ends: Vec<usize> = args
+        .char_indices()
+        .filter(|&(i, c)| {
+            !c.is_whitespace() && args[i + c.len_utf8()..].starts_with(char::is_whitespace)
+        })
+        .map(|(i, c)| i + c.len_utf8())
+        .collect();
+    (1..=ends.len().min(6)).rev().find_map(|n| {
+        let end = ends[n - 1];
+        let text = args[end..].trim();
+        if text.is_empty() {
+            return None;
+        }
+        parse_schedule_time(&args[..end]).map(|t| (t, text))
+    })
+}
+
+/// Complete a partially typed command name. Returns the completed input when
+/// the prefix is unambiguous (or extends to a longer common prefix), plus all
+/// matching command names for display.
+pub fn complete(input: &str) -> Option<(String, Vec<&'static SlashCommandSpec>)> {
+    let prefix = input.strip_prefix('/')?;
+    if prefix.starts_with('/') || prefix.contains(char::is_whitespace) {
+        return None;
+    }
+    let matches: Vec<&'static SlashCommandSpec> = SLASH_COMMANDS
+        .iter()
+        .filter(|c| c.name.starts_with(prefix))
+        .collect();
+    let completed = match matches.as_slice() {
+        [] => return None,
+        [only] => format!("/{} ", only.name),
+        [first, rest @ ..] => {
+            let first = first.name;
+            let common = rest.iter().fold(first.len(), |len, c| {
+                first
+                    .bytes()
+                    .zip(c.name.bytes())
+                    .take(len)
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            });
+            format!("/{}", &first[..common])
+        }
+    };
+    Some((completed, matches))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_and_escaped_slash_are_messages() {
+        assert_eq!(
+            parse_input("hello"),
+            Ok(ComposerInput::Message("hello".to_string()))
+        );
+        assert_eq!(
+            parse_input("//shrug"),
+            Ok(ComposerInput::Message("/shrug".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_commands_and_arguments() {
+        assert_eq!(
+            parse_input("/me waves"),
+            Ok(ComposerInput::Command(SlashCommand::Me(
+                "waves".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_input("/rename"),
+            Ok(ComposerInput::Command(SlashCommand::Rename(None)))
+        );
+        assert_eq!(
+            parse_input("/rename  Team Ops "),
+            Ok(ComposerInput::Command(SlashCommand::Rename(Some(
+                "Team Ops".to_string()
+            ))))
+        );
+        assert_eq!(
+            parse_input("/attach docs/a b.png"),
+            Ok(ComposerInput::Command(SlashCommand::Attach(PathBuf::from(
+                "docs/a b.png"
+            ))))
+        );
+        assert!(parse_input("/me").is_err());
+        assert!(parse_input("/frobnicate")
+            .unwrap_err()
+            .contains("unknown command /frobnicate"));
+    }
+
+    #[test]
+    fn schedule_splits_time_from_text() {
+        match parse_input("/schedule tomorrow 9am call\nmom") {
+            Ok(ComposerInput::Command(SlashCommand::Schedule { text, .. })) => {
+                assert_eq!(text, "call\nmom")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_input("/schedule 14:30 hi") {
+            Ok(ComposerInput::Command(SlashCommand::Schedule { text, .. })) => {
+                assert_eq!(text, "hi")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_input("/schedule every monday 9am standup notes") {
+            Ok(ComposerInput::Command(SlashCommand::Schedule { time, text })) => {
+                assert!(time.recurrence.is_some());
+                assert_eq!(text, "standup notes")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match parse_input("/schedule cron 0 9 * * 1-5 stretch") {
+            Ok(ComposerInput::Command(SlashCommand::Schedule { time, text })) => {
+                assert!(time.recurrence.is_some());
+                assert_eq!(text, "stretch")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(parse_input("/schedule tomorrow 9am").is_err());
+        assert!(parse_input("/schedule whenever hi").is_err());
+    }
+
+    #[test]
+    fn completes_from_registry() {
+        let names = |input: &str| {
+            complete(input).map(|(completed, matches)| {
+                let names: Vec<&str> = matches.iter().map(|c| c.name).collect();
+                (completed, names)
+            })
+        };
+        assert_eq!(
+            names("/sc"),
+            Some(("/schedule ".to_string(), vec!["schedule"]))
+        );
+        // "mute" and "me" share only "m"
+        assert_eq!(names("/m"), Some(("/m".to_string(), vec!["me", "mute"])));
+        assert_eq!(names("/x"), None);
+        assert_eq!(names("//"), None);
+        assert_eq!(names("/me hi"), None);
+        assert_eq!(names("hi"), None);
+    }
+}
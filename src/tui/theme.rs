@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::core::types::Platform;
+use crate::tui::app_state::InputMode;
+
+pub const BUILTIN_THEMES: &[&str] = &["dark", "light", "high-contrast"];
+
+/// Named color roles used by the widgets. Select with `tui.theme`; custom
+/// themes live in `<data_dir>/themes/<name>.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    /// Fill behind the whole UI (`reset` keeps the terminal's own background).
+    pub canvas: Color,
+    /// Background of message text, overlays and the status bar.
+    pub background: Color,
+    pub text: Color,
+    pub text_dim: Color,
+    pub muted: Color,
+    /// Focused borders, overlay frames, search matches.
+    pub accent: Color,
+    /// Pins, the "N new" separator, overlay titles and key hints.
+    pub highlight: Color,
+    pub success: Color,
+    pub error: Color,
+    /// Outgoing bubble bar and sender name.
+    pub outgoing: Color,
+    /// Incoming bubble bar and sender name.
+    pub incoming: Color,
+    /// Incoming bubbles that arrived since the chat was last opened.
+    pub incoming_new: Color,
+    /// Background of the selected chat / message.
+    pub selection: Color,
+    /// Highlighted row in overlay lists (search, scheduled messages).
+    pub list_highlight_bg: Color,
+    pub list_highlight_fg: Color,
+    pub unread_badge: Color,
+    /// URL color. Also the marker used to find URLs for OSC 8 hyperlinks,
+    /// so it should not be shared with another role.
+    pub url: Color,
+    /// Inline code and code block text.
+    pub code: Color,
+    /// Background of multi-line code blocks.
+    pub code_bg: Color,
+    pub whatsapp_fg: Color,
+    pub whatsapp_bg: Color,
+    pub telegram_fg: Color,
+    pub telegram_bg: Color,
+    pub slack_fg: Color,
+    pub slack_bg: Color,
+    pub mock_fg: Color,
+    pub mock_bg: Color,
+    /// Mode badges in the input bar border and the status bar pill.
+    pub mode_normal: Color,
+    /// Insert, chat menu and command palette.
+    pub mode_insert: Color,
+    /// Settings, search, help and the other read-only overlays.
+    pub mode_view: Color,
+    /// Renaming a chat and editing AI style notes.
+    pub mode_rename: Color,
+    pub mode_select: Color,
+    /// Scheduling, scheduled messages and Telegram login.
+    pub mode_schedule: Color,
+    /// Text on the status bar pill.
+    pub mode_fg: Color,
+    pub mode_select_fg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    /// Built-in theme to start from (default: dark).
+    base: Option<String>,
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            canvas: Color::Reset,
+            background: Color::Black,
+            text: Color::White,
+            text_dim: Color::Gray,
+            muted: Color::DarkGray,
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            success: Color::Green,
+            error: Color::Red,
+            outgoing: Color::Cyan,
+            incoming: Color::Magenta,
+            incoming_new: Color::Yellow,
+            selection: Color::Blue,
+            list_highlight_bg: Color::Cyan,
+            list_highlight_fg: Color::Black,
+            unread_badge: Color::Yellow,
+            url: Color::LightBlue,
+            code: Color::LightGreen,
+            code_bg: Color::Rgb(38, 38, 38),
+            whatsapp_fg: Color::Rgb(63, 185, 80),
+            whatsapp_bg: Color::Rgb(26, 71, 33),
+            telegram_fg: Color::Rgb(163, 113, 247),
+            telegram_bg: Color::Rgb(30, 21, 53),
+            slack_fg: Color::Rgb(224, 148, 0),
+            slack_bg: Color::Rgb(60, 40, 0),
+            mock_fg: Color::DarkGray,
+            mock_bg: Color::Black,
+            mode_normal: Color::DarkGray,
+            mode_insert: Color::Yellow,
+            mode_view: Color::Cyan,
+            mode_rename: Color::Magenta,
+            mode_select: Color::Blue,
+            mode_schedule: Color::Green,
+            mode_fg: Color::Black,
+            mode_select_fg: Color::White,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            canvas: Color::Rgb(250, 250, 250),
+            background: Color::Rgb(250, 250, 250),
+            text: Color::Rgb(20, 20, 20),
+            text_dim: Color::Rgb(70, 70, 70),
+            muted: Color::Rgb(130, 130, 130),
+            accent: Color::Rgb(0, 110, 160),
+            highlight: Color::Rgb(170, 100, 0),
+            success: Color::Rgb(20, 130, 40),
+            error: Color::Rgb(190, 30, 30),
+            outgoing: Color::Rgb(0, 110, 160),
+            incoming: Color::Rgb(140, 40, 140),
+            incoming_new: Color::Rgb(170, 100, 0),
+            selection: Color::Rgb(200, 220, 245),
+            list_highlight_bg: Color::Rgb(0, 110, 160),
+            list_highlight_fg: Color::Rgb(250, 250, 250),
+            unread_badge: Color::Rgb(170, 100, 0),
+            url: Color::Rgb(20, 80, 200),
+            code: Color::Rgb(160, 40, 90),
+            code_bg: Color::Rgb(232, 232, 232),
+            whatsapp_fg: Color::Rgb(20, 110, 40),
+            whatsapp_bg: Color::Rgb(210, 240, 215),
+            telegram_fg: Color::Rgb(90, 50, 170),
+            telegram_bg: Color::Rgb(230, 220, 250),
+            slack_fg: Color::Rgb(140, 80, 0),
+            slack_bg: Color::Rgb(250, 230, 200),
+            mock_fg: Color::Rgb(90, 90, 90),
+            mock_bg: Color::Rgb(225, 225, 225),
+            mode_normal: Color::Rgb(130, 130, 130),
+            mode_insert: Color::Rgb(170, 100, 0),
+            mode_view: Color::Rgb(0, 110, 160),
+            mode_rename: Color::Rgb(140, 40, 140),
+            mode_select: Color::Rgb(20, 80, 200),
+            mode_schedule: Color::Rgb(20, 130, 40),
+            mode_fg: Color::Rgb(250, 250, 250),
+            mode_select_fg: Color::Rgb(250, 250, 250),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            canvas: Color::Black,
+            background: Color::Black,
+            text: Color::White,
+            text_dim: Color::White,
+            muted: Color::Gray,
+            accent: Color::LightCyan,
+            highlight: Color::LightYellow,
+            success: Color::LightGreen,
+            error: Color::LightRed,
+            outgoing: Color::LightCyan,
+            incoming: Color::LightMagenta,
+            incoming_new: Color::LightYellow,
+            selection: Color::Blue,
+            list_highlight_bg: Color::White,
+            list_highlight_fg: Color::Black,
+            unread_badge: Color::LightYellow,
+            url: Color::Rgb(120, 190, 255),
+            code: Color::LightGreen,
+            code_bg: Color::Rgb(48, 48, 48),
+            whatsapp_fg: Color::Black,
+            whatsapp_bg: Color::LightGreen,
+            telegram_fg: Color::Black,
+            telegram_bg: Color::LightMagenta,
+            slack_fg: Color::Black,
+            slack_bg: Color::LightYellow,
+            mock_fg: Color::Black,
+            mock_bg: Color::Gray,
+            mode_normal: Color::Gray,
+            mode_insert: Color::LightYellow,
+            mode_view: Color::LightCyan,
+            mode_rename: Color::LightMagenta,
+            mode_select: Color::Blue,
+            mode_schedule: Color::LightGreen,
+            mode_fg: Color::Black,
+            mode_select_fg: Color::White,
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Resolve a theme by name: a built-in, or `<data_dir>/themes/<name>.toml`.
+    pub fn load(name: &str, data_dir: &Path) -> anyhow::Result<Self> {
+        if let Some(theme) = Self::builtin(name) {
+            return Ok(theme);
+        }
+        let path = data_dir.join("themes").join(format!("{}.toml", name));
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            anyhow::anyhow!("Theme '{}' not found ({}): {}", name, path.display(), e)
+        })?;
+        Self::from_toml(name, &content)
+            .map_err(|e| anyhow::anyhow!("Invalid theme {}: {}", path.display(), e))
+    }
+
+    /// Parse a theme file: an optional `base` built-in plus a `[colors]` table
+    /// overriding individual roles (`url = "#5fafff"`, `incoming = "magenta"`).
+    pub fn from_toml(name: &str, content: &str) -> anyhow::Result<Self> {
+        let file: ThemeFile = toml::from_str(content)?;
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut theme =
+            Self::builtin(base).ok_or_else(|| anyhow::anyhow!("unknown base theme '{}'", base))?;
+        theme.name = name.to_string();
+        for (role, value) in &file.colors {
+            let color = Color::from_str(value)
+                .map_err(|_| anyhow::anyhow!("invalid color '{}' for '{}'", value, role))?;
+            let slot = theme
+                .role_mut(role)
+                .ok_or_else(|| anyhow::anyhow!("unknown color role '{}'", role))?;
+            *slot = color;
+        }
+        Ok(theme)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        let slot = match role {
+            "canvas" => &mut self.canvas,
+            "background" => &mut self.background,
+            "text" => &mut self.text,
+            "text_dim" => &mut self.text_dim,
+            "muted" => &mut self.muted,
+            "accent" => &mut self.accent,
+            "highlight" => &mut self.highlight,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            "outgoing" => &mut self.outgoing,
+            "incoming" => &mut self.incoming,
+            "incoming_new" => &mut self.incoming_new,
+            "selection" => &mut self.selection,
+            "list_highlight_bg" => &mut self.list_highlight_bg,
+            "list_highlight_fg" => &mut self.list_highlight_fg,
+            "unread_badge" => &mut self.unread_badge,
+            "url" => &mut self.url,
+            "code" => &mut self.code,
+            "code_bg" => &mut self.code_bg,
+            "whatsapp_fg" => &mut self.whatsapp_fg,
+            "whatsapp_bg" => &mut self.whatsapp_bg,
+            "telegram_fg" => &mut self.telegram_fg,
+            "telegram_bg" => &mut self.telegram_bg,
+            "slack_fg" => &mut self.slack_fg,
+            "slack_bg" => &mut self.slack_bg,
+            "mock_fg" => &mut self.mock_fg,
+            "mock_bg" => &mut self.mock_bg,
+            "mode_normal" => &mut self.mode_normal,
+            "mode_insert" => &mut self.mode_insert,
+            "mode_view" => &mut self.mode_view,
+            "mode_rename" => &mut self.mode_rename,
+            "mode_select" => &mut self.mode_select,
+            "mode_schedule" => &mut self.mode_schedule,
+            "mode_fg" => &mut self.mode_fg,
+            "mode_select_fg" => &mut self.mode_select_fg,
+            _ => return None,
+        };
+        Some(slot)
+    }
+
+    /// (fg, bg) of the platform tag pill.
+    pub fn platform_colors(&self, platform: &Platform) -> (Color, Color) {
+        match platform {
+            Platform::WhatsApp => (self.whatsapp_fg, self.whatsapp_bg),
+            Platform::Telegram => (self.telegram_fg, self.telegram_bg),
+            Platform::Slack => (self.slack_fg, self.slack_bg),
+            Platform::Mock => (self.mock_fg, self.mock_bg),
+        }
+    }
+
+    /// (badge, text on it) for the mode badge of `mode`.
+    pub fn mode_colors(&self, mode: InputMode) -> (Color, Color) {
+        match mode {
+            InputMode::Normal => (self.mode_normal, self.mode_fg),
+            InputMode::Editing | InputMode::ChatMenu | InputMode::Palette => {
+                (self.mode_insert, self.mode_fg)
+            }
+            InputMode::Settings
+            | InputMode::Searching
+            | InputMode::Help
+            | InputMode::CatchUp
+            | InputMode::SentRequests => (self.mode_view, self.mode_fg),
+            InputMode::Renaming | InputMode::StyleNotes => (self.mode_rename, self.mode_fg),
+            InputMode::MessageSelect => (self.mode_select, self.mode_select_fg),
+            InputMode::SchedulePrompt | InputMode::ScheduleList | InputMode::TelegramAuth => {
+                (self.mode_schedule, self.mode_fg)
+            }
+        }
+    }
+}
+
+/// Built-in theme names followed by custom themes found in `<data_dir>/themes`.
+pub fn available_themes(data_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|s| s.to_string()).collect();
+    let mut custom: Vec<String> = std::fs::read_dir(data_dir.join("themes"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .filter(|n| !names.contains(n))
+        .collect();
+    custom.sort();
+    names.extend(custom);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtins_resolve_by_name() {
+        for name in BUILTIN_THEMES {
+            let theme = Theme::builtin(name).unwrap();
+            assert_eq!(theme.name, *name);
+        }
+        assert!(Theme::builtin("solarized").is_none());
+    }
+
+    #[test]
+    fn custom_theme_overrides_base() {
+        let theme = Theme::from_toml(
+            "mine",
+            r##"
+base = "light"
+[colors]
+url = "#5fafff"
+incoming = "magenta"
+"##,
+        )
+        .unwrap();
+        assert_eq!(theme.name, "mine");
+        assert_eq!(theme.url, Color::Rgb(0x5f, 0xaf, 0xff));
+        assert_eq!(theme.incoming, Color::Magenta);
+        assert_eq!(theme.text, Theme::light().text);
+    }
+
+    #[test]
+    fn rejects_unknown_role_and_bad_color() {
+        assert!(Theme::from_toml("x", "[colors]\nsparkle = \"red\"").is_err());
+        assert!(Theme::from_toml("x", "[colors]\nurl = \"not-a-color\"").is_err());
+        assert!(Theme::from_toml("x", "base = \"nope\"").is_err());
+    }
+
+    #[test]
+    fn loads_theme_files_from_data_dir() {
+        let dir = std::env::temp_dir().join(format!("zd-themes-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("themes")).unwrap();
+        std::fs::write(
+            dir.join("themes").join("ocean.toml"),
+            "[colors]\naccent = \"blue\"\n",
+        )
+        .unwrap();
+
+        let theme = Theme::load("ocean", &dir).unwrap();
+        assert_eq!(theme.accent, Color::Blue);
+        assert!(Theme::load("missing", &dir).is_err());
+        assert_eq!(
+            available_themes(&dir),
+            vec!["dark", "light", "high-contrast", "ocean"]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
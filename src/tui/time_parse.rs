@@ -1,7 +1,16 @@
 use chrono::{DateTime, Datelike, Local, NaiveTime, TimeZone, Utc, Weekday};
 
-/// Parse a natural language time string into a UTC DateTime.
-/// Returns None if the input cannot be parsed.
+use crate::core::recurrence::{CronSpec, Recurrence};
+
+/// When a message should be sent, and how it repeats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleTime {
+    pub send_at: DateTime<Utc>,
+    pub recurrence: Option<Recurrence>,
+}
+
+/// Parse a natural language time string into the first send time and an
+/// optional recurrence. Returns None if the input cannot be parsed.
 ///
 /// Supported formats:
 ///   "9am", "9:00", "21:30"                → today (or tomorrow if past)
@@ -9,8 +18,91 @@ use chrono::{DateTime, Datelike, Local, NaiveTime, TimeZone, Utc, Weekday};
 ///   "monday 3pm", "fri 9:00"              → next occurrence of weekday
 ///   "Mar 15 9am", "mar 15 14:30"          → specific month + day + time (current year)
 ///   "2026-03-15 09:00"                    → ISO-ish date + time
-pub fn parse_schedule_time(input: &str) -> Option<DateTime<Utc>> {
+///   "every monday 9am", "daily 8:30"      → repeating; see [`parse_recurrence`]
+///   "cron 0 9 * * 1-5"                    → repeating on a cron schedule
+pub fn parse_schedule_time(input: &str) -> Option<ScheduleTime> {
     let input = input.trim().to_lowercase();
+    if input.starts_with("cron ") {
+        let recurrence = parse_recurrence(&input)?;
+        let now = Utc::now();
+        return Some(ScheduleTime {
+            send_at: recurrence.next_after(now, now)?,
+            recurrence: Some(recurrence),
+        });
+    }
+    if let Some((phrase, time)) = input.rsplit_once(char::is_whitespace) {
+        if let Some(recurrence) = parse_recurrence(phrase) {
+            return Some(ScheduleTime {
+                send_at: next_at_time(&recurrence, parse_time(time)?)?,
+                recurrence: Some(recurrence),
+            });
+        }
+    }
+    parse_one_shot(&input).map(|send_at| ScheduleTime {
+        send_at,
+        recurrence: None,
+    })
+}
+
+/// Parse a repeat rule without a time of day:
+///   "every day" / "daily", "every weekday" / "weekdays",
+///   "every monday", "every 15th" (monthly), "cron <minute hour day month weekday>"
+pub fn parse_recurrence(phrase: &str) -> Option<Recurrence> {
+    let phrase = phrase.trim().to_lowercase();
+    if let Some(expr) = phrase.strip_prefix("cron ") {
+        let expr = expr.split_whitespace().collect::<Vec<_>>().join(" ");
+        CronSpec::parse(&expr).ok()?;
+        return Some(Recurrence::Cron(expr));
+    }
+    match phrase.split_whitespace().collect::<Vec<_>>()[..] {
+        ["daily"] | ["every", "day"] => Some(Recurrence::Daily),
+        ["weekdays"] | ["every", "weekday"] => Some(Recurrence::Weekdays),
+        ["every", unit] => {
+            if let Some(day) = parse_weekday(unit) {
+                return Some(Recurrence::Weekly(day));
+            }
+            let day: u32 = unit
+                .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+                .parse()
+                .ok()?;
+            (1..=31).contains(&day).then_some(Recurrence::Monthly(day))
+        }
+        _ => None,
+    }
+}
+
+/// Change how an already scheduled message repeats. Accepts a repeat rule with
+/// or without a time of day ("every monday" keeps the current time), or
+/// "once" / nothing to stop repeating.
+pub fn parse_recurrence_change(input: &str, current: DateTime<Utc>) -> Option<ScheduleTime> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() || input == "once" {
+        return Some(ScheduleTime {
+            send_at: current,
+            recurrence: None,
+        });
+    }
+    if let Some(recurrence) = parse_recurrence(&input) {
+        return Some(ScheduleTime {
+            send_at: recurrence.next_after(current, Utc::now())?,
+            recurrence: Some(recurrence),
+        });
+    }
+    parse_schedule_time(&input).filter(|t| t.recurrence.is_some())
+}
+
+/// First occurrence of `recurrence` at `time` (local) from now on.
+fn next_at_time(recurrence: &Recurrence, time: NaiveTime) -> Option<DateTime<Utc>> {
+    let today = Local::now().date_naive();
+    let anchor = Local
+        .from_local_datetime(&today.and_time(time))
+        .earliest()?
+        .with_timezone(&Utc);
+    recurrence.next_after(anchor, Utc::now())
+}
+
+/// One-shot times; see [`parse_schedule_time`].
+fn parse_one_shot(input: &str) -> Option<DateTime<Utc>> {
     if input.is_empty() {
         return None;
     }
@@ -230,14 +322,14 @@ mod tests {
 
     #[test]
     fn parse_schedule_tomorrow() {
-        let result = parse_schedule_time("tomorrow 9am").unwrap();
+        let result = parse_schedule_time("tomorrow 9am").unwrap().send_at;
         let expected_date = (Local::now() + chrono::Duration::days(1)).date_naive();
         assert_eq!(result.with_timezone(&Local).date_naive(), expected_date);
     }
 
     #[test]
     fn parse_schedule_iso() {
-        let result = parse_schedule_time("2026-12-25 14:30").unwrap();
+        let result = parse_schedule_time("2026-12-25 14:30").unwrap().send_at;
         let local = result.with_timezone(&Local);
         assert_eq!(local.hour(), 14);
         assert_eq!(local.minute(), 30);
@@ -252,14 +344,14 @@ mod tests {
     #[test]
     fn parse_schedule_month_day_past_rolls_to_next_year() {
         // "jan 1 9am" on March 11 2026 should schedule for Jan 1 2027
-        let result = parse_schedule_time("jan 1 9am").unwrap();
+        let result = parse_schedule_time("jan 1 9am").unwrap().send_at;
         // The result should be in the future
         assert!(result > Utc::now());
     }
 
     #[test]
     fn parse_schedule_month_day_time() {
-        let result = parse_schedule_time("mar 15 9am").unwrap();
+        let result = parse_schedule_time("mar 15 9am").unwrap().send_at;
         let local = result.with_timezone(&Local);
         assert_eq!(local.month(), 3);
         assert_eq!(local.day(), 15);
@@ -280,13 +372,77 @@ mod tests {
             Weekday::Sun => "sunday",
         };
         let input = format!("{} 3pm", day_name);
-        let result = parse_schedule_time(&input).unwrap();
+        let result = parse_schedule_time(&input).unwrap().send_at;
         let local = result.with_timezone(&Local);
         assert_eq!(local.hour(), 15);
         assert_eq!(local.minute(), 0);
         // Should be within the next 7 days
         let days_diff = (local.date_naive() - today).num_days();
-        assert!(days_diff >= 1 && days_diff <= 7);
+        assert!((1..=7).contains(&days_diff));
+    }
+
+    #[test]
+    fn parse_schedule_recurring() {
+        let every_monday = parse_schedule_time("every Monday 9am").unwrap();
+        assert_eq!(
+            every_monday.recurrence,
+            Some(Recurrence::Weekly(Weekday::Mon))
+        );
+        let local = every_monday.send_at.with_timezone(&Local);
+        assert_eq!(local.weekday(), Weekday::Mon);
+        assert_eq!((local.hour(), local.minute()), (9, 0));
+        assert!(every_monday.send_at > Utc::now());
+
+        let daily = parse_schedule_time("daily 8:30").unwrap();
+        assert_eq!(daily.recurrence, Some(Recurrence::Daily));
+        assert!(daily.send_at - Utc::now() <= chrono::Duration::days(1));
+
+        let cron = parse_schedule_time("cron 0  9 * * 1-5").unwrap();
+        assert_eq!(
+            cron.recurrence,
+            Some(Recurrence::Cron("0 9 * * 1-5".to_string()))
+        );
+        assert!(parse_schedule_time("cron 0 9 * *").is_none());
+        assert!(parse_schedule_time("every blursday 9am").is_none());
+        // One-shots are unaffected
+        assert_eq!(
+            parse_schedule_time("tomorrow 9am").unwrap().recurrence,
+            None
+        );
+    }
+
+    #[test]
+    fn parse_recurrence_phrases_round_trip() {
+        for phrase in [
+            "every day",
+            "every weekday",
+            "every friday",
+            "every 15th",
+            "cron */30 8-18 * * 1-5",
+        ] {
+            let recurrence = parse_recurrence(phrase).unwrap();
+            assert_eq!(recurrence.to_string(), phrase);
+        }
+        assert_eq!(parse_recurrence("every 32nd"), None);
+        assert_eq!(parse_recurrence("every 15"), Some(Recurrence::Monthly(15)));
+    }
+
+    #[test]
+    fn recurrence_change_keeps_time_unless_given() {
+        let current = Utc::now() + chrono::Duration::hours(2);
+        let once = parse_recurrence_change("once", current).unwrap();
+        assert_eq!(once.send_at, current);
+        assert_eq!(once.recurrence, None);
+
+        let daily = parse_recurrence_change("every day", current).unwrap();
+        assert_eq!(
+            daily.send_at.with_timezone(&Local).time(),
+            current.with_timezone(&Local).time()
+        );
+        let weekly = parse_recurrence_change("every sunday 7pm", current).unwrap();
+        assert_eq!(weekly.send_at.with_timezone(&Local).hour(), 19);
+        // A one-shot time is not a recurrence change
+        assert!(parse_recurrence_change("tomorrow 9am", current).is_none());
     }
 
     #[test]
@@ -0,0 +1,128 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::tui::app_state::CatchUpState;
+use crate::tui::theme::Theme;
+
+/// Break `text` into lines of at most `width` columns at spaces.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.width() + 1 + word.width() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+pub fn render_catch_up_overlay(f: &mut Frame, state: &CatchUpState, theme: &Theme) {
+    let area = f.area();
+    let width = (area.width * 70 / 100)
+        .max(50)
+        .min(area.width.saturating_sub(4));
+    let height = (area.height * 80 / 100).min(area.height.saturating_sub(2));
+
+    let popup = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, popup);
+
+    let title = Title::from(Line::from(vec![Span::styled(
+        " Catch me up ",
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let text_width = (popup.width as usize).saturating_sub(8).max(10);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    for (i, entry) in state.entries.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::default());
+        }
+        if i == state.selected {
+            selected_line = lines.len();
+        }
+        let name_style = if i == state.selected {
+            Style::default()
+                .fg(theme.list_highlight_fg)
+                .bg(theme.list_highlight_bg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", entry.chat_name), name_style),
+            Span::styled(
+                format!("  {} unread", entry.unread),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+        match &entry.bullets {
+            None => lines.push(Line::from(Span::styled(
+                "  summarising…",
+                Style::default().fg(theme.muted),
+            ))),
+            Some(Err(e)) => lines.push(Line::from(Span::styled(
+                format!("  ✗ {}", e),
+                Style::default().fg(theme.error),
+            ))),
+            Some(Ok(bullets)) => {
+                for bullet in bullets {
+                    for (n, part) in wrap(bullet, text_width).into_iter().enumerate() {
+                        let marker = if n == 0 { "  • " } else { "    " };
+                        lines.push(Line::from(vec![
+                            Span::styled(marker, Style::default().fg(theme.accent)),
+                            Span::styled(part, Style::default().fg(theme.text)),
+                        ]));
+                    }
+                }
+            }
+        }
+    }
+
+    // Keep the selected chat's header in view
+    let visible = popup.height.saturating_sub(2) as usize;
+    let scroll = selected_line.saturating_sub(visible.saturating_sub(4)) as u16;
+    let paragraph = Paragraph::new(lines).block(block).scroll((scroll, 0));
+    f.render_widget(paragraph, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_at_spaces() {
+        assert_eq!(
+            wrap("Alice asked if you can bring the cake", 16),
+            ["Alice asked if", "you can bring", "the cake"]
+        );
+        assert!(wrap("   ", 10).is_empty());
+    }
+}
@@ -2,16 +2,27 @@ use std::collections::HashMap;
 
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
+use unicode_width::UnicodeWidthStr;
+
+use crate::core::folders::ChatFolder;
 use crate::core::types::{ChatKind, Platform, UnifiedChat};
 use crate::tui::app_state::{ActivePanel, InputMode, TypingInfo};
+use crate::tui::mouse::{HitMap, HitTarget};
+use crate::tui::theme::Theme;
 
-fn make_item(chat: &UnifiedChat, is_selected: bool, typing_blink: Option<u8>) -> ListItem<'static> {
+fn make_item(
+    chat: &UnifiedChat,
+    is_selected: bool,
+    typing_blink: Option<u8>,
+    has_draft: bool,
+    theme: &Theme,
+) -> ListItem<'static> {
     let unread = if chat.unread_count > 0 {
         format!(" ({})", chat.unread_count)
     } else {
@@ -23,22 +34,24 @@ fn make_item(chat: &UnifiedChat, is_selected: bool, typing_blink: Option<u8>) ->
         .unwrap_or(&chat.name)
         .to_string();
     let selector = if is_selected { "▶ " } else { "  " };
+    let draft_tag = if has_draft { " ✎" } else { "" };
     let pin_tag = if chat.is_pinned { "★ " } else { "  " };
 
     // Muted chats render dimmed
     let (name_color, unread_color) = if chat.is_muted {
-        (Color::DarkGray, Color::Gray)
+        (theme.muted, theme.text_dim)
     } else {
-        (Color::White, Color::Yellow)
+        (theme.text, theme.unread_badge)
     };
 
     // Level 1: platform pill
-    let (platform_label, platform_fg, platform_bg) = match chat.platform {
-        Platform::WhatsApp => ("WA", Color::Rgb(63, 185, 80), Color::Rgb(26, 71, 33)),
-        Platform::Telegram => ("TG", Color::Rgb(163, 113, 247), Color::Rgb(30, 21, 53)),
-        Platform::Slack => ("SL", Color::Rgb(224, 148, 0), Color::Rgb(60, 40, 0)),
-        Platform::Mock => ("MK", Color::DarkGray, Color::Black),
+    let platform_label = match chat.platform {
+        Platform::WhatsApp => "WA",
+        Platform::Telegram => "TG",
+        Platform::Slack => "SL",
+        Platform::Mock => "MK",
     };
+    let (platform_fg, platform_bg) = theme.platform_colors(&chat.platform);
     let platform_span = Span::styled(
         format!(" {} ", platform_label),
         Style::default().fg(platform_fg).bg(platform_bg),
@@ -56,41 +69,91 @@ fn make_item(chat: &UnifiedChat, is_selected: bool, typing_blink: Option<u8>) ->
     let spans = if let Some(phase) = typing_blink {
         let dot = |i: u8| {
             let color = if phase == i {
-                Color::Green
+                theme.success
             } else {
-                Color::DarkGray
+                theme.muted
             };
             Span::styled("● ", Style::default().fg(color))
         };
         vec![
             Span::raw(selector),
-            Span::styled(pin_tag.to_string(), Style::default().fg(Color::Yellow)),
+            Span::styled(pin_tag.to_string(), Style::default().fg(theme.highlight)),
             platform_span,
             emoji_span,
             dot(0),
             dot(1),
             dot(2),
             Span::styled(name, Style::default().fg(name_color)),
-            Span::styled(" typing", Style::default().fg(Color::DarkGray)),
+            Span::styled(" typing", Style::default().fg(theme.muted)),
         ]
     } else {
         vec![
             Span::raw(selector),
-            Span::styled(pin_tag.to_string(), Style::default().fg(Color::Yellow)),
+            Span::styled(pin_tag.to_string(), Style::default().fg(theme.highlight)),
             platform_span,
             emoji_span,
             Span::styled(name, Style::default().fg(name_color)),
+            Span::styled(draft_tag, Style::default().fg(theme.muted)),
             Span::styled(unread, Style::default().fg(unread_color)),
         ]
     };
     ListItem::new(Line::from(spans))
 }
 
+/// Record a click target for each visible row; `rows` are the chat indices from the top row down.
+fn push_row_hits(hits: &mut HitMap, area: Rect, rows: &[usize]) {
+    for (row, &idx) in rows.iter().take(area.height as usize).enumerate() {
+        hits.push(
+            Rect::new(area.x, area.y + row as u16, area.width, 1),
+            HitTarget::Chat(idx),
+        );
+    }
+}
+
+/// One-row folder tab strip, scrolled so the active tab stays visible.
+fn render_folder_tabs(
+    f: &mut Frame,
+    area: Rect,
+    folders: &[ChatFolder],
+    active: usize,
+    hits: &mut HitMap,
+    theme: &Theme,
+) {
+    let labels: Vec<String> = folders.iter().map(|f| format!(" {} ", f.name)).collect();
+    let widths: Vec<u16> = labels.iter().map(|l| l.width() as u16 + 1).collect();
+    let mut first = 0;
+    while first < active && widths[first..=active].iter().sum::<u16>() > area.width {
+        first += 1;
+    }
+
+    let mut spans = Vec::new();
+    let mut x = area.x;
+    for (i, label) in labels.iter().enumerate().skip(first) {
+        let style = if i == active {
+            Style::default()
+                .fg(theme.accent)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        let width = widths[i].min(area.right().saturating_sub(x));
+        hits.push(Rect::new(x, area.y, width, 1), HitTarget::Folder(i));
+        x += width;
+        spans.push(Span::styled(label.clone(), style));
+        spans.push(Span::raw(" "));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn render_chat_list(
     f: &mut Frame,
     area: Rect,
     chats: &[UnifiedChat],
+    visible: &[usize],
+    folders: &[ChatFolder],
+    active_folder: usize,
     list_state: &mut ListState,
     active_panel: ActivePanel,
     input_mode: InputMode,
@@ -98,6 +161,9 @@ pub fn render_chat_list(
     blink_phase: u8,
     activity_cache: &HashMap<String, [u32; 24]>,
     show_activity_graph: bool,
+    drafts: &HashMap<String, String>,
+    hits: &mut HitMap,
+    theme: &Theme,
 ) {
     let (list_area, graph_area_opt) = if show_activity_graph {
         let chunks = Layout::default()
@@ -113,9 +179,9 @@ pub fn render_chat_list(
     };
 
     let border_color = if active_panel == ActivePanel::ChatList {
-        Color::Cyan
+        theme.accent
     } else {
-        Color::DarkGray
+        theme.muted
     };
 
     let (title, title_alignment) = if input_mode == InputMode::Editing {
@@ -146,29 +212,62 @@ pub fn render_chat_list(
         .border_style(Style::default().fg(border_color));
     let inner = block.inner(list_area);
     f.render_widget(block, list_area);
+    hits.push(list_area, HitTarget::ChatList);
+
+    let tabs_area = Rect {
+        height: inner.height.min(1),
+        ..inner
+    };
+    render_folder_tabs(f, tabs_area, folders, active_folder, hits, theme);
+    let inner = Rect {
+        y: inner.y + tabs_area.height,
+        height: inner.height - tabs_area.height,
+        ..inner
+    };
 
-    let selected = list_state.selected().unwrap_or(0);
-    let pinned_count = chats.iter().filter(|c| c.is_pinned).count();
+    // Positions below are into `visible`, not `chats`.
+    let selected = list_state
+        .selected()
+        .and_then(|s| visible.iter().position(|&i| i == s));
+    let pinned_count = visible.iter().filter(|&&i| chats[i].is_pinned).count();
 
     // fg intentionally omitted: letting span-level colors show through (green dot, yellow pin, etc.)
-    // The ▶ selector and blue background together communicate selection without overriding span colors.
+    // The ▶ selector and selection background together communicate selection without overriding span colors.
     let highlight = Style::default()
-        .bg(Color::Blue)
+        .bg(theme.selection)
         .add_modifier(Modifier::BOLD);
 
-    if pinned_count == 0 {
-        // No pinned chats — plain scrollable list
-        let items: Vec<ListItem> = chats
-            .iter()
+    let items = |rows: &[usize], selected: Option<usize>| -> Vec<ListItem> {
+        rows.iter()
             .enumerate()
-            .map(|(i, chat)| {
+            .map(|(pos, &i)| {
+                let chat = &chats[i];
                 let blink = typing_states.get(&chat.id).map(|_| blink_phase);
-                make_item(chat, i == selected, blink)
+                make_item(
+                    chat,
+                    selected == Some(pos),
+                    blink,
+                    drafts.contains_key(&chat.id),
+                    theme,
+                )
             })
-            .collect();
+            .collect()
+    };
+
+    if pinned_count == 0 {
+        // No pinned chats — plain scrollable list; keep the scroll offset across frames
+        let mut state = ListState::default()
+            .with_offset(list_state.offset())
+            .with_selected(selected);
         // No highlight_symbol — selector is embedded in item content
-        let list = List::new(items).highlight_style(highlight);
-        f.render_stateful_widget(list, inner, list_state);
+        let list = List::new(items(visible, selected)).highlight_style(highlight);
+        f.render_stateful_widget(list, inner, &mut state);
+        *list_state.offset_mut() = state.offset();
+        push_row_hits(
+            hits,
+            inner,
+            visible.get(state.offset()..).unwrap_or_default(),
+        );
         // Do NOT return — fall through to render graph column below
     } else {
         // Split inner area: fixed pinned section on top, scrollable unpinned below
@@ -176,44 +275,28 @@ pub fn render_chat_list(
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(pinned_count as u16), Constraint::Min(0)])
             .split(inner);
+        let (pinned, unpinned) = visible.split_at(pinned_count);
 
         // --- Pinned section (always visible, no scroll) ---
-        let pinned_items: Vec<ListItem> = chats
-            .iter()
-            .filter(|c| c.is_pinned)
-            .enumerate()
-            .map(|(i, chat)| {
-                let blink = typing_states.get(&chat.id).map(|_| blink_phase);
-                make_item(chat, selected < pinned_count && i == selected, blink)
-            })
-            .collect();
-        let mut pinned_state = ListState::default();
-        if selected < pinned_count {
-            pinned_state.select(Some(selected));
-        }
-        let pinned_list = List::new(pinned_items).highlight_style(highlight);
+        let pinned_selected = selected.filter(|&s| s < pinned_count);
+        let mut pinned_state = ListState::default().with_selected(pinned_selected);
+        let pinned_list = List::new(items(pinned, pinned_selected)).highlight_style(highlight);
         f.render_stateful_widget(pinned_list, sections[0], &mut pinned_state);
+        push_row_hits(hits, sections[0], pinned);
 
         // --- Unpinned section (scrollable) ---
-        let unpinned_items: Vec<ListItem> = chats
-            .iter()
-            .filter(|c| !c.is_pinned)
-            .enumerate()
-            .map(|(i, chat)| {
-                let blink = typing_states.get(&chat.id).map(|_| blink_phase);
-                make_item(
-                    chat,
-                    selected >= pinned_count && i == selected - pinned_count,
-                    blink,
-                )
-            })
-            .collect();
-        let mut unpinned_state = ListState::default();
-        if selected >= pinned_count {
-            unpinned_state.select(Some(selected - pinned_count));
-        }
-        let unpinned_list = List::new(unpinned_items).highlight_style(highlight);
+        let unpinned_selected = selected
+            .filter(|&s| s >= pinned_count)
+            .map(|s| s - pinned_count);
+        let mut unpinned_state = ListState::default().with_selected(unpinned_selected);
+        let unpinned_list =
+            List::new(items(unpinned, unpinned_selected)).highlight_style(highlight);
         f.render_stateful_widget(unpinned_list, sections[1], &mut unpinned_state);
+        push_row_hits(
+            hits,
+            sections[1],
+            unpinned.get(unpinned_state.offset()..).unwrap_or_default(),
+        );
     }
 
     // Render activity graph column if wide enough
@@ -222,15 +305,17 @@ pub fn render_chat_list(
 
         let mut graph_lines: Vec<Line> = vec![Line::from(vec![Span::styled(
             " 24h       ",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )])];
-        for chat in chats.iter() {
+        // Blank row beside the folder tabs
+        graph_lines.push(Line::default());
+        for chat in visible.iter().map(|&i| &chats[i]) {
             let arr = activity_cache.get(&chat.id).copied().unwrap_or([0u32; 24]);
             let braille = encode_braille(&arr);
             let color = if chat.unread_count > 0 {
-                Color::Green
+                theme.success
             } else {
-                Color::DarkGray
+                theme.muted
             };
             graph_lines.push(Line::from(vec![
                 Span::raw(" "),
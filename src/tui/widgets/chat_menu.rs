@@ -1,16 +1,24 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState},
     Frame,
 };
 
 use crate::tui::app_state::ChatMenuState;
+use crate::tui::mouse::{HitMap, HitTarget};
+use crate::tui::theme::Theme;
 
-pub fn render_chat_menu(f: &mut Frame, parent_area: Rect, state: &ChatMenuState) {
+pub fn render_chat_menu(
+    f: &mut Frame,
+    parent_area: Rect,
+    state: &ChatMenuState,
+    hits: &mut HitMap,
+    theme: &Theme,
+) {
     // Calculate popup size centered over parent_area
-    let popup_width = 28u16.min(parent_area.width.saturating_sub(2));
+    let popup_width = 34u16.min(parent_area.width.saturating_sub(2));
     let popup_height = (state.items.len() as u16 + 4).min(parent_area.height.saturating_sub(2));
     let x = parent_area.x + (parent_area.width.saturating_sub(popup_width)) / 2;
     let y = parent_area.y + (parent_area.height.saturating_sub(popup_height)) / 2;
@@ -22,12 +30,7 @@ pub fn render_chat_menu(f: &mut Frame, parent_area: Rect, state: &ChatMenuState)
     let items: Vec<ListItem> = state
         .items
         .iter()
-        .map(|item| {
-            ListItem::new(Line::from(Span::raw(format!(
-                " {}",
-                item.label(state.is_pinned, state.is_muted)
-            ))))
-        })
+        .map(|item| ListItem::new(Line::from(Span::raw(format!(" {}", item.label(state))))))
         .collect();
 
     let mut list_state = ListState::default();
@@ -38,14 +41,28 @@ pub fn render_chat_menu(f: &mut Frame, parent_area: Rect, state: &ChatMenuState)
             Block::default()
                 .title(format!(" {} ", state.chat_name))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow)),
+                .border_style(Style::default().fg(theme.highlight)),
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme.selection)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("▶ ");
 
     f.render_stateful_widget(list, area, &mut list_state);
+
+    hits.push(area, HitTarget::Overlay);
+    let rows = area.height.saturating_sub(2);
+    for (i, row) in (0..rows).take(state.items.len()).enumerate() {
+        hits.push(
+            Rect::new(
+                area.x + 1,
+                area.y + 1 + row,
+                area.width.saturating_sub(2),
+                1,
+            ),
+            HitTarget::ChatMenuItem(i),
+        );
+    }
 }
@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::tui::app_state::HelpState;
+use crate::tui::theme::Theme;
+
+pub fn render_help_overlay(f: &mut Frame, state: &HelpState, theme: &Theme) {
+    let area = f.area();
+    let width = (area.width * 60 / 100)
+        .max(50)
+        .min(area.width.saturating_sub(4));
+    let height = (area.height * 80 / 100).min(area.height.saturating_sub(2));
+
+    let popup = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, popup);
+
+    let title = Title::from(Line::from(vec![Span::styled(
+        " Keybindings ",
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let key_width = state
+        .sections
+        .iter()
+        .flat_map(|s| s.entries.iter())
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for section in &state.sections {
+        if section.entries.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(Span::styled(
+            section.mode,
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (keys, action) in &section.entries {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}  ", keys, width = key_width),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(*action, Style::default().fg(theme.text_dim)),
+            ]));
+        }
+    }
+
+    // Clamp so scrolling past the end doesn't leave an empty box
+    let visible = popup.height.saturating_sub(2);
+    let max_scroll = (lines.len() as u16).saturating_sub(visible);
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((state.scroll.min(max_scroll), 0));
+    f.render_widget(paragraph, popup);
+}
@@ -1,31 +1,48 @@
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 use tui_textarea::TextArea;
 
 use crate::tui::app_state::InputMode;
+use crate::tui::theme::Theme;
 
 pub fn render_input_bar(
     f: &mut Frame,
     area: Rect,
     textarea: &TextArea<'static>,
     mode: InputMode,
+    editing_scheduled: bool,
     ai_suggestion: Option<&str>,
+    theme: &Theme,
 ) {
-    let (mode_tag, border_color, title_align) = match mode {
-        InputMode::Normal => ("NORMAL", Color::DarkGray, Alignment::Left),
-        InputMode::Editing => ("✏  INSERT", Color::Yellow, Alignment::Center),
-        InputMode::Settings => ("SETTINGS", Color::Cyan, Alignment::Left),
-        InputMode::Renaming => ("RENAME", Color::Magenta, Alignment::Left),
-        InputMode::ChatMenu => ("MENU", Color::Yellow, Alignment::Left),
-        InputMode::Searching => ("SEARCH", Color::Cyan, Alignment::Left),
-        InputMode::MessageSelect => ("SELECT", Color::Blue, Alignment::Left),
-        InputMode::SchedulePrompt => ("SCHEDULE", Color::Green, Alignment::Left),
-        InputMode::ScheduleList => ("SCHEDULED", Color::Green, Alignment::Left),
-        InputMode::TelegramAuth => ("AUTH", Color::Green, Alignment::Left),
+    let (mode_tag, title_align) = match mode {
+        InputMode::Normal => ("NORMAL", Alignment::Left),
+        InputMode::Editing if editing_scheduled => (
+            "✏  EDIT SCHEDULED — Enter:Save  Esc:Cancel",
+            Alignment::Center,
+        ),
+        InputMode::Editing => ("✏  INSERT", Alignment::Center),
+        InputMode::Settings => ("SETTINGS", Alignment::Left),
+        InputMode::Renaming => ("RENAME", Alignment::Left),
+        InputMode::ChatMenu => ("MENU", Alignment::Left),
+        InputMode::Searching => ("SEARCH", Alignment::Left),
+        InputMode::MessageSelect => ("SELECT", Alignment::Left),
+        InputMode::SchedulePrompt => ("SCHEDULE", Alignment::Left),
+        InputMode::ScheduleList => ("SCHEDULED", Alignment::Left),
+        InputMode::TelegramAuth => ("AUTH", Alignment::Left),
+        InputMode::Help => ("HELP", Alignment::Left),
+        InputMode::Palette => ("COMMAND", Alignment::Left),
+        InputMode::CatchUp => ("CATCH UP", Alignment::Left),
+        InputMode::StyleNotes => ("AI STYLE NOTES", Alignment::Left),
+        InputMode::SentRequests => ("SENT TO AI", Alignment::Left),
+    };
+    let border_color = if mode == InputMode::Editing && editing_scheduled {
+        theme.mode_schedule
+    } else {
+        theme.mode_colors(mode).0
     };
 
     let block = Block::default()
@@ -37,7 +54,10 @@ pub fn render_input_bar(
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    if mode == InputMode::Editing || mode == InputMode::Renaming {
+    if matches!(
+        mode,
+        InputMode::Editing | InputMode::Renaming | InputMode::StyleNotes
+    ) {
         // TextArea widget: handles cursor, horizontal scroll, multi-line display automatically
         f.render_widget(textarea, inner_area);
 
@@ -53,7 +73,7 @@ pub fn render_input_bar(
                     };
                     let hint_text = format!("  ↳ {}", suggestion);
                     f.render_widget(
-                        Paragraph::new(hint_text).style(Style::default().fg(Color::DarkGray)),
+                        Paragraph::new(hint_text).style(Style::default().fg(theme.muted)),
                         hint_area,
                     );
                 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use chrono::Local;
 use ratatui::{
     layout::Rect,
@@ -9,56 +11,77 @@ use ratatui::{
 
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::core::types::{MessageStatus, UnifiedMessage};
+use crate::core::markup::{self, TextFormat};
+use crate::core::types::{MessageContent, MessageStatus, Platform, UnifiedMessage};
 use crate::tui::app_state::ActivePanel;
+use crate::tui::image_preview::{ImagePreviews, Placement, Thumbnail};
+use crate::tui::mouse::{HitMap, HitTarget};
+use crate::tui::theme::Theme;
+
+/// A character and the formatting applied to it.
+type Cell = (char, TextFormat);
 
 /// Word-wrap `text` so each output line is at most `max_w` columns wide.
 /// Breaks on word boundaries; splits mid-word only when a single word
 /// exceeds `max_w`. Always returns at least one element (empty string for
 /// empty input so callers can rely on a non-empty vec).
 fn wrap_to_width(text: &str, max_w: usize) -> Vec<String> {
+    wrap_styled(&markup::plain(text), max_w)
+        .iter()
+        .map(|line| line.iter().map(|(c, _)| c).collect())
+        .collect()
+}
+
+/// `wrap_to_width` over formatted text. Lines inside a code block are
+/// hard-wrapped instead, keeping their indentation.
+fn wrap_styled(text: &[Cell], max_w: usize) -> Vec<Vec<Cell>> {
     if max_w == 0 {
-        return vec![text.to_string()];
+        return vec![text.to_vec()];
     }
     if text.is_empty() {
-        return vec![String::new()];
+        return vec![Vec::new()];
     }
-    let mut lines: Vec<String> = Vec::new();
-    for original_line in text.split('\n') {
+    let mut lines: Vec<Vec<Cell>> = Vec::new();
+    for original_line in text.split(|&(c, _)| c == '\n') {
         if original_line.is_empty() {
-            lines.push(String::new());
+            lines.push(Vec::new());
+            continue;
+        }
+        if original_line
+            .iter()
+            .any(|(_, f)| f.contains(TextFormat::CODE_BLOCK))
+        {
+            hard_wrap(original_line, max_w, &mut lines);
             continue;
         }
-        let mut current = String::new();
+        let mut current: Vec<Cell> = Vec::new();
         let mut current_w: usize = 0;
-        for word in original_line.split_whitespace() {
-            let word_w = UnicodeWidthStr::width(word);
+        for (separator, word) in split_words(original_line) {
+            let word_w = cells_width(word);
             let space_needed = if current.is_empty() { 0 } else { 1 };
             if current_w + space_needed + word_w <= max_w {
                 // Word fits on current line
                 if !current.is_empty() {
-                    current.push(' ');
+                    current.push((' ', separator));
                     current_w += 1;
                 }
-                current.push_str(word);
+                current.extend_from_slice(word);
                 current_w += word_w;
             } else if word_w > max_w {
                 // Word wider than max_w: flush current, then chop word char by char
                 if !current.is_empty() {
-                    lines.push(current.clone());
-                    current.clear();
+                    lines.push(std::mem::take(&mut current));
                     current_w = 0;
                 }
-                let mut chunk = String::new();
+                let mut chunk: Vec<Cell> = Vec::new();
                 let mut chunk_w: usize = 0;
-                for ch in word.chars() {
-                    let ch_w = UnicodeWidthChar::width(ch).unwrap_or(0);
+                for &cell in word {
+                    let ch_w = UnicodeWidthChar::width(cell.0).unwrap_or(0);
                     if chunk_w + ch_w > max_w {
-                        lines.push(chunk.clone());
-                        chunk.clear();
+                        lines.push(std::mem::take(&mut chunk));
                         chunk_w = 0;
                     }
-                    chunk.push(ch);
+                    chunk.push(cell);
                     chunk_w += ch_w;
                 }
                 if !chunk.is_empty() {
@@ -68,22 +91,82 @@ fn wrap_to_width(text: &str, max_w: usize) -> Vec<String> {
             } else {
                 // Word doesn't fit on current line; flush and start fresh
                 if !current.is_empty() {
-                    lines.push(current.clone());
+                    lines.push(std::mem::take(&mut current));
                 }
-                current = word.to_string();
+                current = word.to_vec();
                 current_w = word_w;
             }
         }
-        if !current.is_empty() || lines.last().map(|l: &String| !l.is_empty()).unwrap_or(true) {
+        if !current.is_empty() || lines.last().map(|l| !l.is_empty()).unwrap_or(true) {
             lines.push(current);
         }
     }
     if lines.is_empty() {
-        lines.push(String::new());
+        lines.push(Vec::new());
     }
     lines
 }
 
+/// Split on whitespace, pairing each word with the format of the whitespace
+/// before it (so a space inside e.g. a struck-through phrase stays struck).
+fn split_words(line: &[Cell]) -> Vec<(TextFormat, &[Cell])> {
+    let mut words = Vec::new();
+    let mut separator = TextFormat::PLAIN;
+    let mut start: Option<usize> = None;
+    for (i, &(c, format)) in line.iter().enumerate() {
+        if c.is_whitespace() {
+            if let Some(s) = start.take() {
+                words.push((separator, &line[s..i]));
+                separator = format;
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        words.push((separator, &line[s..]));
+    }
+    words
+}
+
+/// Break a code block line at exactly `max_w` columns; tabs become 4 spaces.
+fn hard_wrap(line: &[Cell], max_w: usize, lines: &mut Vec<Vec<Cell>>) {
+    let mut current: Vec<Cell> = Vec::new();
+    let mut current_w = 0;
+    for &(c, format) in line {
+        let (c, count) = if c == '\t' { (' ', 4) } else { (c, 1) };
+        for _ in 0..count {
+            let w = UnicodeWidthChar::width(c).unwrap_or(0);
+            if current_w + w > max_w && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_w = 0;
+            }
+            current.push((c, format));
+            current_w += w;
+        }
+    }
+    lines.push(current);
+}
+
+fn cells_width(cells: &[Cell]) -> usize {
+    cells
+        .iter()
+        .map(|(c, _)| UnicodeWidthChar::width(*c).unwrap_or(0))
+        .sum()
+}
+
+/// Formatted content of a message. WhatsApp (and Slack / mock) text carries
+/// inline markup; Telegram formatting arrives as entities.
+fn styled_content(msg: &UnifiedMessage) -> Vec<Cell> {
+    match &msg.content {
+        MessageContent::Formatted { text, entities } => markup::from_entities(text, entities),
+        MessageContent::Text(text) if msg.platform != Platform::Telegram => {
+            markup::parse_whatsapp(text)
+        }
+        other => markup::plain(other.as_text()),
+    }
+}
+
 /// Split a line of text into alternating (segment, is_url) pairs.
 /// URLs are contiguous non-whitespace text starting with "http://" or "https://".
 fn split_line_with_urls(line: &str) -> Vec<(&str, bool)> {
@@ -125,39 +208,147 @@ fn split_line_with_urls(line: &str) -> Vec<(&str, bool)> {
     result
 }
 
-/// Build content spans for a single pre-wrapped line with optional URL styling.
-/// `is_selected`: adds blue background when true.
+/// Build content spans for a single pre-wrapped line with URL and markup styling.
+/// `is_selected`: adds the selection background when true.
 /// `text_color`: base message text color.
-fn build_content_spans(line: &str, is_selected: bool, text_color: Color) -> Vec<Span<'static>> {
+fn build_content_spans(
+    line: &[Cell],
+    is_selected: bool,
+    text_color: Color,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
     let bg = if is_selected {
-        Color::Blue
+        theme.selection
     } else {
-        Color::Black
+        theme.background
     };
-    let url_style = Style::default()
-        .fg(Color::LightBlue)
-        .add_modifier(Modifier::UNDERLINED)
-        .bg(bg);
 
-    if !line.contains("http://") && !line.contains("https://") {
-        return vec![Span::styled(
-            line.to_string(),
-            Style::default().fg(text_color).bg(bg),
-        )];
+    let text: String = line.iter().map(|(c, _)| c).collect();
+    let mut is_url = vec![false; line.len()];
+    if text.contains("http://") || text.contains("https://") {
+        let mut idx = 0;
+        for (seg, url) in split_line_with_urls(&text) {
+            let n = seg.chars().count();
+            if url {
+                is_url[idx..idx + n].fill(true);
+            }
+            idx += n;
+        }
     }
 
-    split_line_with_urls(line)
-        .into_iter()
-        .map(|(seg, is_url)| {
-            if is_url {
-                Span::styled(seg.to_string(), url_style)
-            } else {
-                Span::styled(seg.to_string(), Style::default().fg(text_color).bg(bg))
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    for (i, &(c, format)) in line.iter().enumerate() {
+        run.push(c);
+        let ends_run = line
+            .get(i + 1)
+            .is_none_or(|&(_, next)| next != format || is_url[i + 1] != is_url[i]);
+        if ends_run {
+            let style = cell_style(format, is_url[i], text_color, bg, is_selected, theme);
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+    }
+    spans
+}
+
+fn cell_style(
+    format: TextFormat,
+    is_url: bool,
+    text_color: Color,
+    bg: Color,
+    is_selected: bool,
+    theme: &Theme,
+) -> Style {
+    let mut style = Style::default().fg(text_color).bg(bg);
+    if format.contains(TextFormat::CODE) {
+        style = style.fg(theme.code);
+    }
+    if format.contains(TextFormat::CODE_BLOCK) {
+        style = style.fg(theme.code);
+        if !is_selected {
+            style = style.bg(theme.code_bg);
+        }
+    }
+    if format.contains(TextFormat::BOLD) {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if format.contains(TextFormat::ITALIC) {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if format.contains(TextFormat::UNDERLINE) {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    if format.contains(TextFormat::STRIKE) {
+        style = style.add_modifier(Modifier::CROSSED_OUT);
+    }
+    if is_url {
+        style = style.fg(theme.url).add_modifier(Modifier::UNDERLINED);
+    }
+    style
+}
+
+/// Clickable URLs of each wrapped line of `original`, as `(column, width, url)`.
+/// Wrapping only drops or collapses whitespace, so wrapped cells are matched
+/// back to the original line to recover URLs that were split across rows.
+fn wrapped_urls(original: &[Cell], wrapped: &[Vec<Cell>]) -> Vec<Vec<(usize, usize, String)>> {
+    let text: String = original.iter().map(|(c, _)| c).collect();
+    if !text.contains("http://") && !text.contains("https://") {
+        return vec![Vec::new(); wrapped.len()];
+    }
+    let mut urls: Vec<&str> = Vec::new();
+    let mut url_at: Vec<Option<usize>> = vec![None; original.len()];
+    let mut idx = 0;
+    for (seg, is_url) in split_line_with_urls(&text) {
+        let n = seg.chars().count();
+        if is_url {
+            url_at[idx..idx + n].fill(Some(urls.len()));
+            urls.push(seg);
+        }
+        idx += n;
+    }
+
+    let mut pos = 0;
+    wrapped
+        .iter()
+        .map(|line| {
+            let mut ranges: Vec<(usize, usize, String)> = Vec::new();
+            let mut col = 0;
+            let mut prev: Option<usize> = None;
+            for &(c, _) in line {
+                while original.get(pos).is_some_and(|(o, _)| o.is_whitespace()) {
+                    pos += 1;
+                }
+                let url = if c.is_whitespace() {
+                    None
+                } else {
+                    pos += 1;
+                    url_at.get(pos - 1).copied().flatten()
+                };
+                let w = UnicodeWidthChar::width(c).unwrap_or(0);
+                match (url, ranges.last_mut()) {
+                    (Some(u), Some(range)) if prev == Some(u) => range.1 += w,
+                    (Some(u), _) => ranges.push((col, w, urls[u].to_string())),
+                    (None, _) => {}
+                }
+                prev = url;
+                col += w;
             }
+            ranges
         })
         .collect()
 }
 
+/// Rows reserved for an image thumbnail. Half-block thumbnails are drawn as
+/// text; graphics protocol images are painted over blank rows after the frame.
+fn thumbnail_rows(thumb: &Thumbnail) -> Vec<Vec<Span<'static>>> {
+    match thumb.lines() {
+        Some(lines) => lines.iter().map(|line| line.spans.clone()).collect(),
+        None => (0..thumb.rows)
+            .map(|_| vec![Span::raw(" ".repeat(thumb.cols as usize))])
+            .collect(),
+    }
+}
+
 /// Returns the display column width of the delivery status indicator.
 fn display_width_of_status(status: MessageStatus) -> usize {
     match status {
@@ -169,14 +360,23 @@ fn display_width_of_status(status: MessageStatus) -> usize {
     }
 }
 
+/// A translation wrapped to `max_w` columns, the first row marked with `↳`.
+fn translation_rows(text: &str, max_w: usize) -> Vec<String> {
+    wrap_to_width(text, max_w.saturating_sub(2).max(1))
+        .into_iter()
+        .enumerate()
+        .map(|(n, line)| format!("{}{}", if n == 0 { "↳ " } else { "  " }, line))
+        .collect()
+}
+
 /// Returns the styled delivery-status span.
-fn status_span(status: MessageStatus) -> Span<'static> {
+fn status_span(status: MessageStatus, theme: &Theme) -> Span<'static> {
     let (text, color) = match status {
-        MessageStatus::Sending => ("···", Color::DarkGray),
-        MessageStatus::Sent => ("✓", Color::DarkGray),
-        MessageStatus::Delivered => ("✓✓", Color::DarkGray),
-        MessageStatus::Read => ("✓✓", Color::Green),
-        MessageStatus::Failed => ("✗", Color::Red),
+        MessageStatus::Sending => ("···", theme.muted),
+        MessageStatus::Sent => ("✓", theme.muted),
+        MessageStatus::Delivered => ("✓✓", theme.muted),
+        MessageStatus::Read => ("✓✓", theme.success),
+        MessageStatus::Failed => ("✗", theme.error),
     };
     Span::styled(text, Style::default().fg(color))
 }
@@ -191,11 +391,18 @@ pub fn render_message_view(
     active_panel: ActivePanel,
     new_message_count: usize,
     selected_message_idx: Option<usize>,
+    translations: &HashMap<String, String>,
+    previews: &mut ImagePreviews,
+    hits: &mut HitMap,
+    theme: &Theme,
 ) {
+    previews.placements.clear();
+    hits.push(area, HitTarget::MessageView);
+
     let border_color = if active_panel == ActivePanel::MessageView {
-        Color::Cyan
+        theme.accent
     } else {
-        Color::DarkGray
+        theme.muted
     };
 
     if messages.is_empty() {
@@ -204,7 +411,7 @@ pub fn render_message_view(
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color));
         let p = Paragraph::new("No messages yet. Press 'i' to start typing.")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(block);
         f.render_widget(p, area);
         return;
@@ -230,6 +437,14 @@ pub fn render_message_view(
     };
 
     let mut lines: Vec<Line> = Vec::new();
+    // (message id, first line index, column offset, rows) of each thumbnail
+    let mut thumbnails: Vec<(&str, usize, usize, u16)> = Vec::new();
+    // (message index, first line, end line) and (line, column, width, url) for hit-testing
+    let mut message_rows: Vec<(usize, usize, usize)> = Vec::new();
+    let mut url_hits: Vec<(usize, usize, usize, String)> = Vec::new();
+    let translation_style = Style::default()
+        .fg(theme.muted)
+        .add_modifier(Modifier::ITALIC);
 
     for (i, msg) in messages.iter().enumerate() {
         // Insert "─── N new ───" separator before first new message
@@ -240,7 +455,10 @@ pub fn render_message_view(
             let left = dashes / 2;
             let right = dashes - left;
             let separator = format!("{}{}{}", "─".repeat(left), label, "─".repeat(right));
-            lines.push(Line::styled(separator, Style::default().fg(Color::Yellow)));
+            lines.push(Line::styled(
+                separator,
+                Style::default().fg(theme.highlight),
+            ));
         }
 
         let time = msg
@@ -265,6 +483,7 @@ pub fn render_message_view(
         if is_group_start && i > 0 {
             lines.push(Line::from(""));
         }
+        let first_line = lines.len();
 
         let area_w = area.width.saturating_sub(2) as usize;
         let sender_display = if msg.sender.is_empty() {
@@ -283,127 +502,190 @@ pub fn render_message_view(
                         Span::raw(" ".repeat(pad)),
                         Span::styled(
                             sender_display.clone(),
-                            Style::default().bg(Color::Blue).fg(Color::Cyan),
+                            Style::default().bg(theme.selection).fg(theme.outgoing),
                         ),
                         Span::styled(
                             format!(" {}", time),
-                            Style::default().bg(Color::Blue).fg(Color::DarkGray),
+                            Style::default().bg(theme.selection).fg(theme.muted),
+                        ),
+                        Span::styled(
+                            " ▐",
+                            Style::default().fg(theme.outgoing).bg(theme.selection),
                         ),
-                        Span::styled(" ▐", Style::default().fg(Color::Cyan).bg(Color::Blue)),
                     ])
                 } else {
                     Line::from(vec![
                         Span::raw(" ".repeat(pad)),
-                        Span::styled(sender_display.clone(), Style::default().fg(Color::Cyan)),
-                        Span::styled(format!(" {}", time), Style::default().fg(Color::DarkGray)),
+                        Span::styled(sender_display.clone(), Style::default().fg(theme.outgoing)),
+                        Span::styled(format!(" {}", time), Style::default().fg(theme.muted)),
                     ])
                 };
                 lines.push(header_line);
             }
 
             let max_self_w = (area_w * 2 / 3).max(20);
-            let content_text = msg.content.as_text().to_string();
-            let mut all_wrapped: Vec<String> = Vec::new();
-            for original_line in content_text.split('\n') {
-                all_wrapped.extend(wrap_to_width(original_line, max_self_w));
+            let content = styled_content(msg);
+            let mut all_wrapped: Vec<Vec<Cell>> = Vec::new();
+            let mut all_urls = Vec::new();
+            for original_line in content.split(|&(c, _)| c == '\n') {
+                let wrapped = wrap_styled(original_line, max_self_w);
+                all_urls.extend(wrapped_urls(original_line, &wrapped));
+                all_wrapped.extend(wrapped);
             }
             let total = all_wrapped.len();
-            for (li, text_line) in all_wrapped.iter().enumerate() {
+            for (li, (text_line, urls)) in all_wrapped.iter().zip(all_urls).enumerate() {
                 let is_last = li == total - 1;
-                let line_w = UnicodeWidthStr::width(text_line.as_str());
+                let line_w = cells_width(text_line);
                 let mut spans: Vec<Span> =
-                    build_content_spans(text_line, is_selected, Color::White);
+                    build_content_spans(text_line, is_selected, theme.text, theme);
+                let status_w = if is_last {
+                    1 + display_width_of_status(msg.status)
+                } else {
+                    0
+                };
+                let pad = area_w.saturating_sub(line_w + 2 + status_w);
+                for (col, width, url) in urls {
+                    url_hits.push((lines.len(), pad + col, width, url));
+                }
                 if is_last {
-                    let status_w = display_width_of_status(msg.status);
-                    let pad = area_w.saturating_sub(line_w + 2 + 1 + status_w);
                     let mut row: Vec<Span> = vec![Span::raw(" ".repeat(pad))];
                     row.append(&mut spans);
                     row.push(Span::styled(
                         " ┃",
-                        Style::default().fg(Color::Cyan).bg(if is_selected {
-                            Color::Blue
+                        Style::default().fg(theme.outgoing).bg(if is_selected {
+                            theme.selection
                         } else {
-                            Color::Black
+                            theme.background
                         }),
                     ));
                     row.push(Span::raw(" "));
                     row.push({
-                        let mut s = status_span(msg.status);
+                        let mut s = status_span(msg.status, theme);
                         if is_selected {
-                            s = s.patch_style(Style::default().bg(Color::Blue));
+                            s = s.patch_style(Style::default().bg(theme.selection));
                         }
                         s
                     });
                     lines.push(Line::from(row));
                 } else {
-                    let pad = area_w.saturating_sub(line_w + 2);
                     let mut row: Vec<Span> = vec![Span::raw(" ".repeat(pad))];
                     row.append(&mut spans);
                     row.push(Span::styled(
                         " ┃",
-                        Style::default().fg(Color::Cyan).bg(if is_selected {
-                            Color::Blue
+                        Style::default().fg(theme.outgoing).bg(if is_selected {
+                            theme.selection
                         } else {
-                            Color::Black
+                            theme.background
                         }),
                     ));
                     lines.push(Line::from(row));
                 }
             }
+
+            if let Some(translation) = translations.get(&msg.id) {
+                for row in translation_rows(translation, max_self_w) {
+                    let pad = area_w.saturating_sub(row.width() + 2);
+                    lines.push(Line::from(vec![
+                        Span::raw(" ".repeat(pad)),
+                        Span::styled(row, translation_style),
+                        Span::styled(" ┃", Style::default().fg(theme.outgoing)),
+                    ]));
+                }
+            }
+
+            if let Some(thumb) = previews.get(&msg.id) {
+                if thumb.cols as usize + 2 <= area_w {
+                    let pad = area_w - thumb.cols as usize - 2;
+                    thumbnails.push((&msg.id, lines.len(), pad, thumb.rows));
+                    for mut spans in thumbnail_rows(thumb) {
+                        let mut row: Vec<Span> = vec![Span::raw(" ".repeat(pad))];
+                        row.append(&mut spans);
+                        row.push(Span::styled(" ┃", Style::default().fg(theme.outgoing)));
+                        lines.push(Line::from(row));
+                    }
+                }
+            }
         } else {
             // ── Incoming: left-aligned, purple ┃ ─────────────────────────────
             let is_new = new_start_idx.map(|s| i >= s).unwrap_or(false);
             let name_color = if is_new {
-                Color::Yellow
+                theme.incoming_new
             } else {
-                Color::Magenta
+                theme.incoming
             };
             let bar_color = if is_new {
-                Color::Yellow
+                theme.incoming_new
             } else {
-                Color::Magenta
+                theme.incoming
             };
-            let msg_color = if is_new { Color::White } else { Color::Gray };
+            let msg_color = if is_new { theme.text } else { theme.text_dim };
 
             if is_group_start {
                 let header_line = if is_selected {
                     Line::from(vec![
-                        Span::styled("▌ ", Style::default().fg(Color::Cyan).bg(Color::Blue)),
+                        Span::styled("▌ ", Style::default().fg(theme.accent).bg(theme.selection)),
                         Span::styled(
                             sender_display.clone(),
-                            Style::default().fg(name_color).bg(Color::Blue),
+                            Style::default().fg(name_color).bg(theme.selection),
                         ),
                         Span::styled(
                             format!(" {}", time),
-                            Style::default().fg(Color::DarkGray).bg(Color::Blue),
+                            Style::default().fg(theme.muted).bg(theme.selection),
                         ),
                     ])
                 } else {
                     Line::from(vec![
                         Span::styled("┃ ", Style::default().fg(bar_color)),
                         Span::styled(sender_display.clone(), Style::default().fg(name_color)),
-                        Span::styled(format!(" {}", time), Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!(" {}", time), Style::default().fg(theme.muted)),
                     ])
                 };
                 lines.push(header_line);
             }
 
             let content_w = area_w.saturating_sub(2); // "┃ " = 2 cols
-            let content_text = msg.content.as_text().to_string();
-            for original_line in content_text.split('\n') {
-                for text_line in wrap_to_width(original_line, content_w) {
+            let content = styled_content(msg);
+            for original_line in content.split(|&(c, _)| c == '\n') {
+                let wrapped = wrap_styled(original_line, content_w);
+                let urls = wrapped_urls(original_line, &wrapped);
+                for (text_line, urls) in wrapped.into_iter().zip(urls) {
+                    for (col, width, url) in urls {
+                        url_hits.push((lines.len(), 2 + col, width, url));
+                    }
                     let bar = if is_selected {
-                        Span::styled("▌ ", Style::default().fg(Color::Cyan).bg(Color::Blue))
+                        Span::styled("▌ ", Style::default().fg(theme.accent).bg(theme.selection))
                     } else {
                         Span::styled("┃ ", Style::default().fg(bar_color))
                     };
-                    let mut content_spans = build_content_spans(&text_line, is_selected, msg_color);
+                    let mut content_spans =
+                        build_content_spans(&text_line, is_selected, msg_color, theme);
                     let mut row = vec![bar];
                     row.append(&mut content_spans);
                     lines.push(Line::from(row));
                 }
             }
+
+            if let Some(translation) = translations.get(&msg.id) {
+                for row in translation_rows(translation, content_w) {
+                    lines.push(Line::from(vec![
+                        Span::styled("┃ ", Style::default().fg(bar_color)),
+                        Span::styled(row, translation_style),
+                    ]));
+                }
+            }
+
+            if let Some(thumb) = previews.get(&msg.id) {
+                if thumb.cols as usize + 2 <= area_w {
+                    thumbnails.push((&msg.id, lines.len(), 2, thumb.rows));
+                    for mut spans in thumbnail_rows(thumb) {
+                        let mut row = vec![Span::styled("┃ ", Style::default().fg(bar_color))];
+                        row.append(&mut spans);
+                        lines.push(Line::from(row));
+                    }
+                }
+            }
         }
+        message_rows.push((i, first_line, lines.len()));
     } // end message loop
 
     // Padding so the last message is never clipped by word-wrap miscalculation
@@ -425,6 +707,39 @@ pub fn render_message_view(
     // Apply manual scroll offset (scroll_offset moves UP from auto-scroll position)
     let effective_scroll = auto_scroll.saturating_sub(scroll_offset);
 
+    // Only fully visible thumbnails are painted by the graphics protocol
+    let first_visible = effective_scroll as usize;
+    for (msg_id, line_idx, x, rows) in thumbnails {
+        if line_idx >= first_visible && line_idx + rows as usize <= first_visible + visible_height {
+            previews.placements.push(Placement {
+                msg_id: msg_id.to_string(),
+                x: area.x + 1 + x as u16,
+                y: area.y + 1 + (line_idx - first_visible) as u16,
+            });
+        }
+    }
+
+    let screen_row = |line_idx: usize| area.y + 1 + (line_idx - first_visible) as u16;
+    let visible = first_visible..first_visible + visible_height;
+    for (idx, start, end) in message_rows {
+        let (start, end) = (start.max(visible.start), end.min(visible.end));
+        if start < end {
+            let rows = Rect::new(
+                area.x + 1,
+                screen_row(start),
+                area.width.saturating_sub(2),
+                (end - start) as u16,
+            );
+            hits.push(rows, HitTarget::Message(idx));
+        }
+    }
+    for (line_idx, x, width, url) in url_hits {
+        if visible.contains(&line_idx) {
+            let cell = Rect::new(area.x + 1 + x as u16, screen_row(line_idx), width as u16, 1);
+            hits.push(cell, HitTarget::Url(url));
+        }
+    }
+
     let block = Block::default()
         .title(format!(" {} ", chat_name))
         .borders(Borders::ALL)
@@ -439,7 +754,12 @@ pub fn render_message_view(
 
 #[cfg(test)]
 mod tests {
-    use super::{display_width_of_status, split_line_with_urls, status_span, wrap_to_width};
+    use super::{
+        display_width_of_status, split_line_with_urls, status_span, wrap_styled, wrap_to_width,
+        wrapped_urls,
+    };
+    use crate::core::markup;
+    use crate::tui::theme::Theme;
 
     #[test]
     fn plain_text_no_url() {
@@ -599,17 +919,44 @@ mod tests {
     fn status_span_content_and_color() {
         use crate::core::types::MessageStatus;
         use ratatui::style::Color;
-        assert_eq!(status_span(MessageStatus::Sending).content.as_ref(), "···");
-        assert_eq!(status_span(MessageStatus::Sent).content.as_ref(), "✓");
-        assert_eq!(status_span(MessageStatus::Delivered).content.as_ref(), "✓✓");
-        assert_eq!(status_span(MessageStatus::Read).content.as_ref(), "✓✓");
-        assert_eq!(status_span(MessageStatus::Failed).content.as_ref(), "✗");
         assert_eq!(
-            status_span(MessageStatus::Read).style.fg,
+            status_span(MessageStatus::Sending, &Theme::default())
+                .content
+                .as_ref(),
+            "···"
+        );
+        assert_eq!(
+            status_span(MessageStatus::Sent, &Theme::default())
+                .content
+                .as_ref(),
+            "✓"
+        );
+        assert_eq!(
+            status_span(MessageStatus::Delivered, &Theme::default())
+                .content
+                .as_ref(),
+            "✓✓"
+        );
+        assert_eq!(
+            status_span(MessageStatus::Read, &Theme::default())
+                .content
+                .as_ref(),
+            "✓✓"
+        );
+        assert_eq!(
+            status_span(MessageStatus::Failed, &Theme::default())
+                .content
+                .as_ref(),
+            "✗"
+        );
+        assert_eq!(
+            status_span(MessageStatus::Read, &Theme::default()).style.fg,
             Some(Color::Green)
         );
         assert_eq!(
-            status_span(MessageStatus::Failed).style.fg,
+            status_span(MessageStatus::Failed, &Theme::default())
+                .style
+                .fg,
             Some(Color::Red)
         );
     }
@@ -624,4 +971,18 @@ mod tests {
         let wrapped2 = wrap_to_width(&text, 60);
         assert_eq!(wrapped2.len(), 1);
     }
+
+    #[test]
+    fn wrapped_urls_keep_the_full_url_on_every_row() {
+        let line = markup::plain("see  https://example.com/abcdef ok");
+        let wrapped = wrap_styled(&line, 12);
+        let urls = wrapped_urls(&line, &wrapped);
+        let url = "https://example.com/abcdef".to_string();
+        // "see" / "https://exam" / "ple.com/abcd" / "ef ok"
+        assert_eq!(urls.len(), 4);
+        assert_eq!(urls[0], vec![]);
+        assert_eq!(urls[1], vec![(0, 12, url.clone())]);
+        assert_eq!(urls[2], vec![(0, 12, url.clone())]);
+        assert_eq!(urls[3], vec![(0, 2, url)]);
+    }
 }
@@ -1,10 +1,15 @@
+pub mod catch_up_overlay;
 pub mod chat_list;
 pub mod chat_menu;
+pub mod help_overlay;
 pub mod input_bar;
 pub mod message_view;
+pub mod palette_overlay;
 pub mod qr_overlay;
 pub mod schedule_overlay;
 pub mod search_overlay;
+pub mod sent_requests_overlay;
 pub mod settings_overlay;
+pub mod smart_replies;
 pub mod status_bar;
 pub mod telegram_auth_overlay;
@@ -0,0 +1,116 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::tui::app_state::PaletteState;
+use crate::tui::theme::Theme;
+
+const MAX_VISIBLE: usize = 12;
+
+pub fn render_palette_overlay(f: &mut Frame, state: &PaletteState, theme: &Theme) {
+    let area = f.area();
+    let result_count = state.results.len().min(MAX_VISIBLE);
+    // 2 borders + 1 query line + 1 divider + results (at least one row for "No matches")
+    let height = (4 + result_count.max(1) as u16).min(area.height.saturating_sub(2));
+    let width = (area.width * 50 / 100)
+        .max(40)
+        .min(area.width.saturating_sub(4));
+
+    let popup = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: area.height / 6,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, popup);
+
+    let title = Title::from(Line::from(vec![Span::styled(
+        " Commands ",
+        Style::default()
+            .fg(theme.highlight)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.background));
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    // Query line
+    let query_line = Line::from(vec![
+        Span::styled(
+            ": ",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{}▌", state.query), Style::default().fg(theme.text)),
+    ]);
+    f.render_widget(Paragraph::new(query_line), Rect { height: 1, ..inner });
+
+    // Divider
+    f.render_widget(
+        Paragraph::new("─".repeat(inner.width as usize)).style(Style::default().fg(theme.muted)),
+        Rect {
+            y: inner.y + 1,
+            height: 1,
+            ..inner
+        },
+    );
+
+    let results_area = Rect {
+        y: inner.y + 2,
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+
+    if state.results.is_empty() {
+        f.render_widget(
+            Paragraph::new("  No matching commands").style(Style::default().fg(theme.muted)),
+            results_area,
+        );
+        return;
+    }
+
+    let highlight = Style::default()
+        .bg(theme.list_highlight_bg)
+        .fg(theme.list_highlight_fg)
+        .add_modifier(Modifier::BOLD);
+
+    let row_width = results_area.width as usize;
+    let items: Vec<ListItem> = state
+        .results
+        .iter()
+        .enumerate()
+        .map(|(pos, &idx)| {
+            let entry = &state.entries[idx];
+            let selector = if pos == state.selected { "▶ " } else { "  " };
+            // Right-align the key hint
+            let used = 2 + entry.title.width() + entry.keys.width() + 1;
+            let pad = row_width.saturating_sub(used);
+            ListItem::new(Line::from(vec![
+                Span::raw(selector),
+                Span::styled(entry.title, Style::default().fg(theme.text)),
+                Span::raw(" ".repeat(pad)),
+                Span::styled(entry.keys.clone(), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+            ]))
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.selected.min(items.len() - 1)));
+    f.render_stateful_widget(
+        List::new(items).highlight_style(highlight),
+        results_area,
+        &mut list_state,
+    );
+}
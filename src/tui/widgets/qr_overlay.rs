@@ -7,15 +7,17 @@ use ratatui::{
     Frame,
 };
 
+use crate::tui::theme::Theme;
+
 /// Render a QR code as a centered overlay popup.
 /// Uses Unicode half-block characters (▀ ▄ █ and space) to render
 /// two rows of QR modules per terminal row for compact display.
-pub fn render_qr_overlay(f: &mut Frame, qr_string: &str) {
+pub fn render_qr_overlay(f: &mut Frame, qr_string: &str, theme: &Theme) {
     let qr = match QrCode::new(qr_string.as_bytes()) {
         Ok(q) => q,
         Err(e) => {
             // If QR generation fails, show error overlay
-            render_error_overlay(f, &format!("QR Error: {}", e));
+            render_error_overlay(f, &format!("QR Error: {}", e), theme);
             return;
         }
     };
@@ -89,10 +91,10 @@ pub fn render_qr_overlay(f: &mut Frame, qr_string: &str) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(" WhatsApp — Scan QR Code ")
         .title_alignment(Alignment::Center)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
 
     let inner = block.inner(popup);
     f.render_widget(block, popup);
@@ -110,29 +112,29 @@ pub fn render_qr_overlay(f: &mut Frame, qr_string: &str) {
         Line::from(""),
         Line::from(Span::styled(
             "Scan with WhatsApp to link this device",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
     ])
     .alignment(Alignment::Center);
     f.render_widget(instruction, chunks[1]);
 }
 
-fn render_error_overlay(f: &mut Frame, msg: &str) {
+fn render_error_overlay(f: &mut Frame, msg: &str, theme: &Theme) {
     let area = f.area();
     let popup = centered_rect(50, 5, area);
     f.render_widget(Clear, popup);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red))
+        .border_style(Style::default().fg(theme.error))
         .title(" Error ")
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
 
     let inner = block.inner(popup);
     f.render_widget(block, popup);
 
     let paragraph = Paragraph::new(msg)
-        .style(Style::default().fg(Color::Red))
+        .style(Style::default().fg(theme.error))
         .alignment(Alignment::Center);
     f.render_widget(paragraph, inner);
 }
@@ -1,18 +1,20 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{block::Title, Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use crate::tui::app_state::ScheduleListState;
+use crate::tui::theme::Theme;
 use crate::tui::time_parse::format_local_time;
 
 pub fn render_schedule_list_overlay(
     f: &mut Frame,
     state: &ScheduleListState,
     chat_names: &std::collections::HashMap<String, String>,
+    theme: &Theme,
 ) {
     let area = f.area();
     let count = state.messages.len();
@@ -41,26 +43,26 @@ pub fn render_schedule_list_overlay(
     let title = Title::from(Line::from(vec![Span::styled(
         " Scheduled Messages ",
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD),
     )]));
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(theme.highlight));
     let inner = block.inner(popup);
     f.render_widget(block, popup);
 
     if count == 0 {
         let empty =
-            Paragraph::new("  No scheduled messages").style(Style::default().fg(Color::DarkGray));
+            Paragraph::new("  No scheduled messages").style(Style::default().fg(theme.muted));
         f.render_widget(empty, inner);
         return;
     }
 
     let highlight = Style::default()
-        .bg(Color::Cyan)
-        .fg(Color::Black)
+        .bg(theme.list_highlight_bg)
+        .fg(theme.list_highlight_fg)
         .add_modifier(Modifier::BOLD);
 
     let items: Vec<ListItem> = state
@@ -73,7 +75,8 @@ pub fn render_schedule_list_overlay(
                 .get(&msg.chat_id)
                 .cloned()
                 .unwrap_or_else(|| msg.chat_id.clone());
-            let platform_tag = format!("[{}] ", msg.platform);
+            let (platform_fg, platform_bg) = theme.platform_colors(&msg.platform);
+            let platform_tag = format!(" {} ", msg.platform);
             let preview_full = msg.content.as_text();
             let preview = if preview_full.chars().count() > 40 {
                 let end = preview_full
@@ -86,20 +89,52 @@ pub fn render_schedule_list_overlay(
                 preview_full
             };
             let time_str = format!("→ {}", format_local_time(&msg.send_at));
+            let repeat = msg
+                .recurrence
+                .as_ref()
+                .map(|r| format!("  ↻ {}", r))
+                .unwrap_or_default();
+            let error = msg.last_error.as_deref().unwrap_or("unknown error");
+            let (state_label, state_color) = match msg.status.as_str() {
+                "failed" => (
+                    format!("  ✗ failed after {} attempts: {}", msg.attempts, error),
+                    theme.error,
+                ),
+                "missed" => ("  ⚠ missed while closed".to_string(), theme.error),
+                _ if msg.attempts > 0 => (
+                    format!(
+                        "  ⟳ retry {} at {}: {}",
+                        msg.attempts + 1,
+                        format_local_time(&msg.due_at()),
+                        error
+                    ),
+                    theme.muted,
+                ),
+                _ => (String::new(), theme.muted),
+            };
 
             ListItem::new(vec![
                 Line::from(vec![
                     Span::raw(selector.to_string()),
-                    Span::styled(platform_tag, Style::default().fg(Color::DarkGray)),
-                    Span::styled(chat_name, Style::default().fg(Color::White)),
+                    Span::styled(
+                        platform_tag,
+                        Style::default().fg(platform_fg).bg(platform_bg),
+                    ),
+                    Span::raw(" "),
+                    Span::styled(chat_name, Style::default().fg(theme.text)),
                 ]),
                 Line::from(vec![
                     Span::raw("    "),
-                    Span::styled(format!("\"{}\"", preview), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("\"{}\"", preview),
+                        Style::default().fg(theme.text_dim),
+                    ),
                 ]),
                 Line::from(vec![
                     Span::raw("    "),
-                    Span::styled(time_str, Style::default().fg(Color::Cyan)),
+                    Span::styled(time_str, Style::default().fg(theme.accent)),
+                    Span::styled(repeat, Style::default().fg(theme.highlight)),
+                    Span::styled(state_label, Style::default().fg(state_color)),
                 ]),
             ])
         })
@@ -127,12 +162,20 @@ pub fn render_schedule_list_overlay(
 
     // Footer hints
     let hints = Paragraph::new(Line::from(vec![
-        Span::styled("j/k", Style::default().fg(Color::Yellow)),
-        Span::styled(":Navigate  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("d", Style::default().fg(Color::Yellow)),
-        Span::styled(":Cancel  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("Esc", Style::default().fg(Color::Yellow)),
-        Span::styled(":Close", Style::default().fg(Color::DarkGray)),
+        Span::styled("j/k", Style::default().fg(theme.highlight)),
+        Span::styled(":Navigate  ", Style::default().fg(theme.muted)),
+        Span::styled("e", Style::default().fg(theme.highlight)),
+        Span::styled(":Edit  ", Style::default().fg(theme.muted)),
+        Span::styled("t", Style::default().fg(theme.highlight)),
+        Span::styled(":Time  ", Style::default().fg(theme.muted)),
+        Span::styled("d", Style::default().fg(theme.highlight)),
+        Span::styled(":Cancel  ", Style::default().fg(theme.muted)),
+        Span::styled("r", Style::default().fg(theme.highlight)),
+        Span::styled(":Repeat  ", Style::default().fg(theme.muted)),
+        Span::styled("s", Style::default().fg(theme.highlight)),
+        Span::styled(":Send now  ", Style::default().fg(theme.muted)),
+        Span::styled("Esc", Style::default().fg(theme.highlight)),
+        Span::styled(":Close", Style::default().fg(theme.muted)),
     ]));
     f.render_widget(hints, footer_area);
 }
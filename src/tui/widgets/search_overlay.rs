@@ -1,21 +1,25 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{block::Title, Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use crate::core::types::UnifiedChat;
-use crate::tui::app_state::SearchState;
+use crate::tui::app_state::{SearchMode, SearchState};
+use crate::tui::mouse::{HitMap, HitTarget};
+use crate::tui::theme::Theme;
 
 pub fn render_search_overlay(
     f: &mut Frame,
     chat_list_area: Rect,
     state: &SearchState,
     chats: &[UnifiedChat],
+    hits: &mut HitMap,
+    theme: &Theme,
 ) {
-    let result_count = state.results.len().min(5);
+    let result_count = state.result_count().min(5);
     // 2 borders + 1 query line + (1 divider + N results) if any results
     let inner_height = 1 + if result_count > 0 {
         1 + result_count
@@ -32,25 +36,27 @@ pub fn render_search_overlay(
     };
 
     f.render_widget(Clear, popup_area);
+    hits.push(popup_area, HitTarget::Overlay);
 
     let title = Title::from(Line::from(vec![
         Span::styled(
             " / ",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            "Find Chat ",
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            match state.mode {
+                SearchMode::Chats => "Find Chat ",
+                SearchMode::Semantic => "Search Messages ",
+            },
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
     ]));
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.accent));
     let inner = block.inner(popup_area);
     f.render_widget(block, popup_area);
 
@@ -61,18 +67,26 @@ pub fn render_search_overlay(
         width: inner.width,
         height: 1,
     };
-    let query_line = Line::from(vec![
+    let prompt = match state.mode {
+        SearchMode::Chats => "/ ",
+        SearchMode::Semantic => "≈ ",
+    };
+    let mut query_spans = vec![
         Span::styled(
-            "/ ",
+            prompt,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(
-            format!("{}▌", state.query),
-            Style::default().fg(Color::White),
-        ),
-    ]);
+        Span::styled(format!("{}▌", state.query), Style::default().fg(theme.text)),
+    ];
+    if state.searching {
+        query_spans.push(Span::styled(
+            "  searching…",
+            Style::default().fg(theme.muted),
+        ));
+    }
+    let query_line = Line::from(query_spans);
     f.render_widget(Paragraph::new(query_line), query_area);
 
     if result_count == 0 {
@@ -87,8 +101,7 @@ pub fn render_search_overlay(
         height: 1,
     };
     f.render_widget(
-        Paragraph::new("─".repeat(inner.width as usize))
-            .style(Style::default().fg(Color::DarkGray)),
+        Paragraph::new("─".repeat(inner.width as usize)).style(Style::default().fg(theme.muted)),
         sep_area,
     );
 
@@ -100,28 +113,65 @@ pub fn render_search_overlay(
         height: result_count as u16,
     };
 
+    for pos in 0..result_count {
+        hits.push(
+            Rect {
+                y: results_area.y + pos as u16,
+                height: 1,
+                ..results_area
+            },
+            HitTarget::SearchResult(pos),
+        );
+    }
+
     let highlight = Style::default()
-        .bg(Color::Cyan)
-        .fg(Color::Black)
+        .bg(theme.list_highlight_bg)
+        .fg(theme.list_highlight_fg)
         .add_modifier(Modifier::BOLD);
 
-    let items: Vec<ListItem> = state
-        .results
-        .iter()
-        .enumerate()
-        .take(result_count)
-        .filter_map(|(pos, &idx)| {
-            let chat = chats.get(idx)?;
-            let name = chat.display_name.as_deref().unwrap_or(&chat.name);
-            let selector = if pos == state.selected { "▶ " } else { "  " };
-            let tag = format!("[{}] ", chat.platform);
-            Some(ListItem::new(Line::from(vec![
-                Span::raw(selector),
-                Span::styled(tag, Style::default().fg(Color::DarkGray)),
-                Span::styled(name.to_string(), Style::default().fg(Color::White)),
-            ])))
-        })
-        .collect();
+    let items: Vec<ListItem> = match state.mode {
+        SearchMode::Chats => state
+            .results
+            .iter()
+            .enumerate()
+            .take(result_count)
+            .filter_map(|(pos, &idx)| {
+                let chat = chats.get(idx)?;
+                let name = chat.display_name.as_deref().unwrap_or(&chat.name);
+                let selector = if pos == state.selected { "▶ " } else { "  " };
+                let tag = format!("[{}] ", chat.platform);
+                Some(ListItem::new(Line::from(vec![
+                    Span::raw(selector),
+                    Span::styled(tag, Style::default().fg(theme.muted)),
+                    Span::styled(name.to_string(), Style::default().fg(theme.text)),
+                ])))
+            })
+            .collect(),
+        SearchMode::Semantic => state
+            .hits
+            .iter()
+            .enumerate()
+            .take(result_count)
+            .map(|(pos, hit)| {
+                let chat_name = chats
+                    .iter()
+                    .find(|c| c.id == hit.chat_id)
+                    .map(|c| c.display_name.as_deref().unwrap_or(&c.name))
+                    .unwrap_or(&hit.chat_id);
+                let selector = if pos == state.selected { "▶ " } else { "  " };
+                let text = hit.text.replace('\n', " ");
+                ListItem::new(Line::from(vec![
+                    Span::raw(selector),
+                    Span::styled(format!("{} ", chat_name), Style::default().fg(theme.muted)),
+                    Span::styled(
+                        format!("{}: ", hit.sender),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(text, Style::default().fg(theme.text)),
+                ]))
+            })
+            .collect(),
+    };
 
     let mut list_state = ListState::default();
     let clamped_selected = state.selected.min(items.len().saturating_sub(1));
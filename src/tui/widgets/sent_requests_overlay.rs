@@ -0,0 +1,139 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{block::Title, Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthChar;
+
+use crate::ai::providers::CompletionKind;
+use crate::tui::app_state::SentRequestsState;
+use crate::tui::theme::Theme;
+
+/// Break `text` into rows of at most `width` columns, keeping every
+/// character and space so it reads exactly as it was sent.
+fn rows(text: &str, width: usize) -> Vec<String> {
+    let mut rows = Vec::new();
+    for line in text.split('\n') {
+        let mut row = String::new();
+        let mut row_width = 0;
+        for c in line.chars() {
+            let w = c.width().unwrap_or(0);
+            if row_width + w > width && !row.is_empty() {
+                rows.push(std::mem::take(&mut row));
+                row_width = 0;
+            }
+            row.push(c);
+            row_width += w;
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+pub fn render_sent_requests_overlay(f: &mut Frame, state: &mut SentRequestsState, theme: &Theme) {
+    let area = f.area();
+    let width = (area.width * 80 / 100)
+        .max(50)
+        .min(area.width.saturating_sub(4));
+    let height = (area.height * 80 / 100).min(area.height.saturating_sub(2));
+
+    let popup = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, popup);
+
+    let title = Title::from(Line::from(vec![Span::styled(
+        " Sent to AI ",
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let text_width = (popup.width as usize).saturating_sub(4).max(10);
+    let label = |text: &'static str| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(theme.muted)
+                .add_modifier(Modifier::ITALIC),
+        ))
+    };
+    let mut lines: Vec<Line> = Vec::new();
+    if state.requests.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Nothing has been sent to the AI provider yet",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    for (i, sent) in state.requests.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::default());
+        }
+        let req = &sent.request;
+        let kind = match req.kind {
+            CompletionKind::Autocomplete => "autocomplete",
+            CompletionKind::Prompt => "prompt",
+        };
+        let masked = match sent.masked {
+            0 => "nothing masked".to_string(),
+            1 => "1 value masked".to_string(),
+            n => format!("{} values masked", n),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", sent.sent_at.format("%H:%M:%S")),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" {} · {} · {}", req.model, kind, masked),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+        let mut section = |name: &'static str, text: &str| {
+            lines.push(label(name));
+            for row in rows(text, text_width) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", row),
+                    Style::default().fg(theme.text),
+                )));
+            }
+        };
+        section(" system", &req.system);
+        if !req.context.is_empty() {
+            let context: Vec<String> = req.context.iter().map(|m| m.to_chat_line()).collect();
+            section(" context", &context.join("\n"));
+        }
+        section(" input", &req.partial_input);
+    }
+
+    // Clamp so scrolling past the end doesn't leave an empty box
+    let visible = popup.height.saturating_sub(2);
+    let max_scroll = (lines.len() as u16).saturating_sub(visible);
+    state.scroll = state.scroll.min(max_scroll);
+    let paragraph = Paragraph::new(lines).block(block).scroll((state.scroll, 0));
+    f.render_widget(paragraph, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_keep_text_as_sent() {
+        assert_eq!(rows("ab  cdef", 4), ["ab  ", "cdef"]);
+        assert_eq!(rows("one\n\ntwo", 10), ["one", "", "two"]);
+        assert_eq!(rows("", 10), [""]);
+    }
+}
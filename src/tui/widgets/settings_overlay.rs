@@ -1,14 +1,24 @@
 use ratatui::{
     layout::Alignment,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
 use crate::tui::app_state::{SettingsState, SettingsValue};
-
-pub fn render_settings_overlay(f: &mut Frame, settings: &SettingsState) {
+use crate::tui::mouse::{HitMap, HitTarget};
+use crate::tui::theme::Theme;
+
+/// Lines above the first setting: blank, dirty indicator, blank.
+const ITEMS_TOP: u16 = 3;
+
+pub fn render_settings_overlay(
+    f: &mut Frame,
+    settings: &SettingsState,
+    hits: &mut HitMap,
+    theme: &Theme,
+) {
     let area = f.area();
 
     let popup_width = 46u16.min(area.width);
@@ -21,14 +31,25 @@ pub fn render_settings_overlay(f: &mut Frame, settings: &SettingsState) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(" Settings ")
         .title_alignment(Alignment::Center)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
 
     let inner = block.inner(popup);
     f.render_widget(block, popup);
 
+    hits.push(popup, HitTarget::Overlay);
+    for i in 0..settings.items.len() {
+        let y = inner.y + ITEMS_TOP + i as u16;
+        if y < inner.bottom() {
+            hits.push(
+                ratatui::layout::Rect::new(inner.x, y, inner.width, 1),
+                HitTarget::SettingsItem(i),
+            );
+        }
+    }
+
     let mut lines: Vec<Line> = Vec::new();
 
     // Blank line
@@ -38,7 +59,7 @@ pub fn render_settings_overlay(f: &mut Frame, settings: &SettingsState) {
     if settings.dirty {
         lines.push(Line::from(Span::styled(
             "  * unsaved changes",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.highlight),
         )));
     } else {
         lines.push(Line::from(""));
@@ -54,10 +75,10 @@ pub fn render_settings_overlay(f: &mut Frame, settings: &SettingsState) {
         let prefix = if selected { "  > " } else { "    " };
         let label_style = if selected {
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme.text)
         };
 
         let value_str = match &item.value {
@@ -84,15 +105,15 @@ pub fn render_settings_overlay(f: &mut Frame, settings: &SettingsState) {
     // Footer hints
     lines.push(Line::from(Span::styled(
         "  j/k:Navigate  Enter/Space:Toggle",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )));
     lines.push(Line::from(Span::styled(
         "  Ctrl+s:Save  Esc:Cancel",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )));
     lines.push(Line::from(Span::styled(
         "  (some changes may require a restart)",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )));
 
     let paragraph = Paragraph::new(lines);
@@ -0,0 +1,72 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use unicode_width::UnicodeWidthChar;
+
+use crate::tui::app_state::InputMode;
+use crate::tui::mouse::{HitMap, HitTarget};
+use crate::tui::theme::Theme;
+
+/// `text` cut to `max` columns, ending in `…` when shortened.
+fn fit(text: &str, max: usize) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for ch in text.chars() {
+        let w = ch.width().unwrap_or(0);
+        if width + w + 1 > max {
+            out.push('…');
+            return out;
+        }
+        width += w;
+        out.push(ch);
+    }
+    out
+}
+
+/// One row of suggested-reply chips above the input bar, each labelled with
+/// the key that inserts it.
+pub fn render_smart_replies(
+    f: &mut Frame,
+    area: Rect,
+    replies: &[String],
+    mode: InputMode,
+    hits: &mut HitMap,
+    theme: &Theme,
+) {
+    let key_prefix = if mode == InputMode::Editing {
+        "Alt+"
+    } else {
+        ""
+    };
+    let chip_width = (area.width as usize / replies.len().max(1)).saturating_sub(1);
+
+    let mut spans = Vec::new();
+    let mut x = area.x;
+    for (i, reply) in replies.iter().enumerate() {
+        let key = format!(" {}{} ", key_prefix, i + 1);
+        let text = fit(reply, chip_width.saturating_sub(key.len() + 1));
+        let width =
+            (key.len() + text.chars().map(|c| c.width().unwrap_or(0)).sum::<usize>() + 1) as u16;
+        let width = width.min(area.right().saturating_sub(x));
+        hits.push(Rect::new(x, area.y, width, 1), HitTarget::SmartReply(i));
+        x += width + 1;
+
+        spans.push(Span::styled(
+            key,
+            Style::default()
+                .fg(theme.highlight)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(
+            format!("{} ", text),
+            Style::default().fg(theme.text).bg(theme.selection),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
@@ -1,58 +1,62 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
 
+use crate::storage::UsageTotals;
 use crate::tui::app_state::InputMode;
+use crate::tui::theme::Theme;
+
+/// `n` in at most four characters: 950, 3.1k, 12k, 1.2M.
+fn compact(n: u64) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=9_999 => format!("{}.{}k", n / 1_000, n % 1_000 / 100),
+        10_000..=999_999 => format!("{}k", n / 1_000),
+        _ => format!("{}.{}M", n / 1_000_000, n % 1_000_000 / 100_000),
+    }
+}
 
 #[allow(clippy::too_many_arguments)]
 pub fn render_status_bar(
     f: &mut Frame,
     area: Rect,
     mode: InputMode,
-    enter_sends: bool,
+    key_hints: &str,
     mock_enabled: bool,
     whatsapp_connected: bool,
+    away: bool,
+    ai_enabled: bool,
+    ai_usage: UsageTotals,
+    ai_backend: Option<&str>,
     copy_status: Option<&str>,
-    schedule_status: Option<&str>,
+    flash_status: Option<&str>,
+    theme: &Theme,
 ) {
-    let hints = match mode {
-        InputMode::Normal => "q:Quit | i:Insert | s:Settings | r:Rename | x:Menu | y:Copy last | v:Select msg | Ctrl+L:Scheduled | Tab:Switch",
-        InputMode::Editing => {
-            if enter_sends {
-                "Esc:Normal | Enter:Send | Shift+Enter/Ctrl+J:Newline | Ctrl+S:Send | Ctrl+U:Clear | Ctrl+D:Schedule"
-            } else {
-                "Esc:Normal | Enter:Newline | Shift+Enter/Ctrl+S:Send | Ctrl+U:Clear | Ctrl+D:Schedule"
-            }
-        }
-        InputMode::Settings => "j/k:Navigate | Enter/Space:Toggle | Ctrl+s:Save | Esc:Cancel",
-        InputMode::Renaming => "Enter:Confirm | Esc:Cancel | Type new name",
-        InputMode::ChatMenu => "j/k:Navigate | p/Enter:Confirm | Esc:Close",
-        InputMode::Searching => "Type to filter | j/k:Navigate | Enter:Open+Insert | Esc:Cancel",
-        InputMode::MessageSelect => "j/k:Navigate | y/Enter:Copy | Esc:Cancel",
-        InputMode::SchedulePrompt => "Type time (e.g. 'tomorrow 9am', 'fri 3pm', 'Mar 15 14:30') | Enter:Confirm | Esc:Cancel",
-        InputMode::ScheduleList => "j/k:Navigate | d:Cancel | Esc/q:Close",
-        InputMode::TelegramAuth => "Type | Enter:Confirm | Esc:Cancel",
-    };
-
     // Mode pill: colored badge on the left, rest of bar stays on black
-    let (pill_label, pill_bg, pill_fg) = match mode {
-        InputMode::Normal => (" NORMAL ", Color::DarkGray, Color::Black),
-        InputMode::Editing => (" ✏ INSERT ", Color::Yellow, Color::Black),
-        InputMode::Settings => (" SETTINGS ", Color::Cyan, Color::Black),
-        InputMode::Renaming => (" RENAME ", Color::Magenta, Color::Black),
-        InputMode::ChatMenu => (" MENU ", Color::Yellow, Color::Black),
-        InputMode::Searching => (" SEARCH ", Color::Cyan, Color::Black),
-        InputMode::MessageSelect => (" SELECT ", Color::Blue, Color::White),
-        InputMode::SchedulePrompt => (" SCHEDULE ", Color::Green, Color::Black),
-        InputMode::ScheduleList => (" SCHEDULED ", Color::Green, Color::Black),
-        InputMode::TelegramAuth => (" AUTH ", Color::Green, Color::Black),
+    let pill_label = match mode {
+        InputMode::Normal => " NORMAL ",
+        InputMode::Editing => " ✏ INSERT ",
+        InputMode::Settings => " SETTINGS ",
+        InputMode::Renaming => " RENAME ",
+        InputMode::ChatMenu => " MENU ",
+        InputMode::Searching => " SEARCH ",
+        InputMode::MessageSelect => " SELECT ",
+        InputMode::SchedulePrompt => " SCHEDULE ",
+        InputMode::ScheduleList => " SCHEDULED ",
+        InputMode::TelegramAuth => " AUTH ",
+        InputMode::Help => " HELP ",
+        InputMode::CatchUp => " CATCH UP ",
+        InputMode::StyleNotes => " STYLE ",
+        InputMode::SentRequests => " SENT TO AI ",
+        InputMode::Palette => " COMMAND ",
     };
+    let (pill_bg, pill_fg) = theme.mode_colors(mode);
 
-    let sep = Style::default().fg(Color::DarkGray);
+    let sep = Style::default().fg(theme.muted);
 
     let mut spans = vec![
         Span::styled(
@@ -65,48 +69,92 @@ pub fn render_status_bar(
         Span::styled(" │ ", sep),
         Span::styled(
             format!("v{} ", env!("CARGO_PKG_VERSION")),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
         Span::styled(" │ ", sep),
     ];
 
+    if away {
+        spans.push(Span::styled(
+            " AWAY ",
+            Style::default()
+                .bg(theme.highlight)
+                .fg(theme.background)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(" │ ", sep));
+    }
+
     if mock_enabled {
-        spans.push(Span::styled(" ● ", Style::default().fg(Color::Green)));
-        spans.push(Span::styled("Mock", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(" ● ", Style::default().fg(theme.success)));
+        spans.push(Span::styled("Mock", Style::default().fg(theme.muted)));
+        spans.push(Span::styled(" │ ", sep));
+    }
+
+    // The dot shows whether a backend answers
+    if ai_enabled {
+        let (ai_color, backend) = match ai_backend {
+            Some(name) => (theme.success, name),
+            None => (theme.error, "offline"),
+        };
+        spans.push(Span::styled(" ● ", Style::default().fg(ai_color)));
+        spans.push(Span::styled(
+            format!(
+                "AI {} · {} req · {} tok",
+                backend,
+                ai_usage.requests,
+                compact(ai_usage.tokens())
+            ),
+            Style::default().fg(theme.muted),
+        ));
         spans.push(Span::styled(" │ ", sep));
     }
 
     let wa_color = if whatsapp_connected {
-        Color::Green
+        theme.success
     } else {
-        Color::Red
+        theme.error
     };
     spans.push(Span::styled(" ● ", Style::default().fg(wa_color)));
-    spans.push(Span::styled("WA", Style::default().fg(Color::DarkGray)));
+    spans.push(Span::styled("WA", Style::default().fg(theme.muted)));
     spans.push(Span::styled(" │ ", sep));
 
-    // Show schedule feedback (highest priority), then copy feedback, then normal hints
-    if let Some(sched) = schedule_status {
-        let color = if sched.starts_with("Could not") {
-            Color::Red
+    // Show flash feedback (highest priority), then copy feedback, then normal hints
+    if let Some(flash) = flash_status {
+        let color = if flash.starts_with("Could not") {
+            theme.error
         } else {
-            Color::Green
+            theme.success
         };
         spans.push(Span::styled(
-            sched,
+            flash,
             Style::default().fg(color).add_modifier(Modifier::BOLD),
         ));
     } else if let Some(status) = copy_status {
         spans.push(Span::styled(
             status,
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         ));
     } else {
-        spans.push(Span::styled(hints, Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(key_hints, Style::default().fg(theme.muted)));
     }
 
-    let paragraph = Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Black));
+    let paragraph = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.background));
     f.render_widget(paragraph, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_token_counts() {
+        assert_eq!(compact(950), "950");
+        assert_eq!(compact(3_149), "3.1k");
+        assert_eq!(compact(9_999), "9.9k");
+        assert_eq!(compact(12_500), "12k");
+        assert_eq!(compact(1_250_000), "1.2M");
+    }
+}
@@ -7,8 +7,9 @@ use ratatui::{
 };
 
 use crate::tui::app_state::{TelegramAuthStage, TelegramAuthState};
+use crate::tui::theme::Theme;
 
-pub fn render_telegram_auth_overlay(f: &mut Frame, state: &TelegramAuthState) {
+pub fn render_telegram_auth_overlay(f: &mut Frame, state: &TelegramAuthState, theme: &Theme) {
     let area = f.area();
 
     if area.width < 20 || area.height < 6 {
@@ -34,7 +35,7 @@ pub fn render_telegram_auth_overlay(f: &mut Frame, state: &TelegramAuthState) {
         .border_style(Style::default().fg(border_color))
         .title(state.stage.title())
         .title_alignment(Alignment::Center)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
 
     let inner = block.inner(popup);
     f.render_widget(block, popup);
@@ -46,7 +47,7 @@ pub fn render_telegram_auth_overlay(f: &mut Frame, state: &TelegramAuthState) {
     // Prompt
     lines.push(Line::from(Span::styled(
         format!("  {}", state.stage.prompt()),
-        Style::default().fg(Color::White),
+        Style::default().fg(theme.text),
     )));
 
     lines.push(Line::from(""));
@@ -55,7 +56,9 @@ pub fn render_telegram_auth_overlay(f: &mut Frame, state: &TelegramAuthState) {
     if let Some(ref hint) = state.error_hint {
         lines.push(Line::from(Span::styled(
             format!("  ! {}", hint),
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
         )));
     } else {
         lines.push(Line::from(""));
@@ -73,11 +76,9 @@ pub fn render_telegram_auth_overlay(f: &mut Frame, state: &TelegramAuthState) {
         Span::styled("  > ", Style::default().fg(border_color)),
         Span::styled(
             display_input,
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         ),
-        Span::styled("_", Style::default().fg(Color::DarkGray)), // cursor
+        Span::styled("_", Style::default().fg(theme.muted)), // cursor
     ]));
 
     lines.push(Line::from(""));
@@ -85,7 +86,7 @@ pub fn render_telegram_auth_overlay(f: &mut Frame, state: &TelegramAuthState) {
     // Footer hints
     lines.push(Line::from(Span::styled(
         "  Enter: Submit   Esc: Cancel",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )));
 
     let paragraph = Paragraph::new(lines);